@@ -1,7 +1,7 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{
     instantiate2_address, to_binary, Addr, Binary, Coin, Decimal, Deps, DepsMut, Empty, Env,
-    Instantiate2AddressError, MessageInfo, StdError, StdResult, WasmMsg,
+    Instantiate2AddressError, MessageInfo, StdError, StdResult, Uint128, WasmMsg,
 };
 use cw2::set_contract_version;
 use infinity_factory::msg::InstantiateMsg as InfinityFactoryInstantiateMsg;
@@ -46,6 +46,16 @@ pub struct InstantiateMsg {
     pub default_royalty_fee_percent: Decimal,
     pub max_royalty_fee_percent: Decimal,
     pub max_swap_fee_percent: Decimal,
+    pub max_nfts_per_pair: Option<u64>,
+    pub pair_config_update_cooldown_seconds: Option<u64>,
+    pub trading_fee_recipient: Option<String>,
+    pub burn_share_percent: Option<Decimal>,
+    pub min_token_deposit: Option<Uint128>,
+    pub min_nft_count: Option<u64>,
+    pub pair_creation_fee_discount_percent: Option<Decimal>,
+    pub min_spot_price: Option<Uint128>,
+    pub max_spot_price: Option<Uint128>,
+    pub probe_collection_freeze: Option<bool>,
     pub code_ids: CodeIds,
     pub min_prices: Vec<Coin>,
     pub admin: Option<String>,
@@ -108,6 +118,16 @@ pub fn instantiate(
                 default_royalty_fee_percent: msg.default_royalty_fee_percent,
                 max_royalty_fee_percent: msg.max_royalty_fee_percent,
                 max_swap_fee_percent: msg.max_swap_fee_percent,
+                max_nfts_per_pair: msg.max_nfts_per_pair,
+                pair_config_update_cooldown_seconds: msg.pair_config_update_cooldown_seconds,
+                trading_fee_recipient: msg.trading_fee_recipient,
+                burn_share_percent: msg.burn_share_percent,
+                min_token_deposit: msg.min_token_deposit,
+                min_nft_count: msg.min_nft_count,
+                pair_creation_fee_discount_percent: msg.pair_creation_fee_discount_percent,
+                min_spot_price: msg.min_spot_price,
+                max_spot_price: msg.max_spot_price,
+                probe_collection_freeze: msg.probe_collection_freeze,
             },
             min_prices: msg.min_prices,
         })?,
@@ -1,11 +1,21 @@
-use crate::helpers::generate_salt;
+use crate::helpers::{generate_instantiate_2_addr, generate_salt, next_pair_creation_fee};
 use crate::msg::ExecuteMsg;
-use crate::state::{INFINITY_GLOBAL, SENDER_COUNTER, UNRESTRICTED_MIGRATIONS};
+use crate::reply::CREATE_AND_FUND_PAIR_REPLY_ID;
+use crate::state::{
+    PendingPairFunding, CLIENT_NONCES, INFINITY_GLOBAL, PENDING_PAIR_FUNDING, SENDER_COUNTER,
+    UNRESTRICTED_MIGRATIONS,
+};
 use crate::ContractError;
 
-use cosmwasm_std::{attr, ensure_eq, to_binary, DepsMut, Empty, Env, Event, MessageInfo, WasmMsg};
+use cosmwasm_std::{
+    attr, coin, ensure, ensure_eq, to_binary, BankMsg, Coin, DepsMut, Empty, Env, Event,
+    MessageInfo, SubMsg, Uint128, WasmMsg,
+};
+use cw721::Cw721ExecuteMsg;
 use infinity_global::load_global_config;
 use infinity_pair::msg::InstantiateMsg as InfinityPairInstantiateMsg;
+use infinity_pair::state::{PairConfig, PairImmutable, PairType};
+use infinity_shared::InfinityError;
 use sg_std::Response;
 
 #[cfg(not(feature = "library"))]
@@ -22,44 +32,98 @@ pub fn execute(
         ExecuteMsg::CreatePair {
             pair_immutable,
             pair_config,
+        } => execute_create_pair(deps, env, info, pair_immutable, pair_config),
+        ExecuteMsg::CreateNftPair {
+            collection,
+            denom,
+            bonding_curve,
+            is_active,
+            token_recipient,
         } => {
-            let infinity_global = INFINITY_GLOBAL.load(deps.storage)?;
-            let global_config = load_global_config(&deps.querier, &infinity_global)?;
-
-            let mut response = Response::new();
-
-            response = response.add_message(WasmMsg::Instantiate {
-                admin: Some(env.contract.address.into()),
-                code_id: global_config.infinity_pair_code_id,
-                label: "Infinity Pair".to_string(),
-                msg: to_binary(&InfinityPairInstantiateMsg {
-                    infinity_global: infinity_global.to_string(),
-                    pair_immutable,
-                    pair_config,
-                })?,
-                funds: info.funds,
-            });
-
-            // Event used by indexer to track pair creation
-            response = response.add_event(
-                Event::new("factory-create-pair".to_string()).add_attribute("sender", info.sender),
+            ensure!(
+                bonding_curve.constant_product_weight_bps().is_none(),
+                InfinityError::InvalidInput(
+                    "nft pairs cannot use a constant product bonding curve".to_string()
+                )
             );
 
-            Ok(response)
+            let pair_immutable = PairImmutable {
+                collection,
+                owner: info.sender.to_string(),
+                denom,
+                trait_filter: None,
+                price_oracle: None,
+            };
+            let pair_config = PairConfig {
+                pair_type: PairType::Nft,
+                bonding_curve,
+                is_active,
+                asset_recipient: None,
+                auto_activate: false,
+                anti_sandwich: false,
+                token_recipient,
+                nft_recipient: None,
+                allow_buy: true,
+                allow_sell: true,
+            };
+
+            execute_create_pair(deps, env, info, pair_immutable, pair_config)
         },
         ExecuteMsg::CreatePair2 {
             pair_immutable,
             pair_config,
+            client_nonce,
         } => {
+            // A retried request returns the pair that was already created, without charging fees again
+            if let Some(client_nonce) = &client_nonce {
+                let nonce_key = (info.sender.clone(), client_nonce.clone());
+                if let Some(pair) = CLIENT_NONCES.may_load(deps.storage, nonce_key)? {
+                    let mut response = Response::new();
+                    if !info.funds.is_empty() {
+                        response = response.add_message(BankMsg::Send {
+                            to_address: info.sender.to_string(),
+                            amount: info.funds,
+                        });
+                    }
+                    response = response.add_event(
+                        Event::new("factory-create-pair2-existing".to_string()).add_attributes(
+                            vec![
+                                attr("sender", info.sender),
+                                attr("client_nonce", client_nonce),
+                                attr("pair", pair),
+                            ],
+                        ),
+                    );
+                    return Ok(response);
+                }
+            }
+
             let infinity_global = INFINITY_GLOBAL.load(deps.storage)?;
             let global_config = load_global_config(&deps.querier, &infinity_global)?;
 
+            let pair_creation_fee = next_pair_creation_fee(
+                deps.storage,
+                &global_config,
+                deps.api.addr_validate(&pair_immutable.owner)?,
+            )?;
+
             let counter_key = (info.sender.clone(), global_config.infinity_pair_code_id);
             let counter =
                 SENDER_COUNTER.may_load(deps.storage, counter_key.clone())?.unwrap_or_default();
             let salt = generate_salt(&info.sender, counter);
             SENDER_COUNTER.save(deps.storage, counter_key, &(counter + 1))?;
 
+            if let Some(client_nonce) = client_nonce {
+                let (pair, _) = generate_instantiate_2_addr(
+                    deps.as_ref(),
+                    &env,
+                    &info.sender,
+                    counter,
+                    global_config.infinity_pair_code_id,
+                )?;
+                CLIENT_NONCES.save(deps.storage, (info.sender.clone(), client_nonce), &pair)?;
+            }
+
             let mut response = Response::new();
 
             response = response.add_message(WasmMsg::Instantiate2 {
@@ -70,6 +134,7 @@ pub fn execute(
                     infinity_global: infinity_global.to_string(),
                     pair_immutable,
                     pair_config,
+                    pair_creation_fee: Some(pair_creation_fee),
                 })?,
                 funds: info.funds,
                 salt,
@@ -82,6 +147,20 @@ pub fn execute(
 
             Ok(response)
         },
+        ExecuteMsg::CreateAndFundPair {
+            pair_immutable,
+            pair_config,
+            token_amount,
+            nft_token_ids,
+        } => execute_create_and_fund_pair(
+            deps,
+            env,
+            info,
+            pair_immutable,
+            pair_config,
+            token_amount,
+            nft_token_ids,
+        ),
         ExecuteMsg::UnrestrictedMigratePair {
             pair_address,
             target_code_id,
@@ -112,3 +191,130 @@ pub fn execute(
         },
     }
 }
+
+fn execute_create_pair(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pair_immutable: PairImmutable<String>,
+    pair_config: PairConfig<String>,
+) -> Result<Response, ContractError> {
+    let instantiate_msg =
+        build_instantiate_pair_msg(deps, &env, pair_immutable, pair_config, info.funds)?;
+
+    let mut response = Response::new();
+
+    response = response.add_message(instantiate_msg);
+
+    // Event used by indexer to track pair creation
+    response = response.add_event(
+        Event::new("factory-create-pair".to_string()).add_attribute("sender", info.sender),
+    );
+
+    Ok(response)
+}
+
+fn execute_create_and_fund_pair(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pair_immutable: PairImmutable<String>,
+    pair_config: PairConfig<String>,
+    token_amount: Uint128,
+    nft_token_ids: Vec<String>,
+) -> Result<Response, ContractError> {
+    ensure_eq!(
+        pair_immutable.owner,
+        info.sender.to_string(),
+        InfinityError::Unauthorized("sender must be the owner of the pair".to_string())
+    );
+    ensure!(
+        !token_amount.is_zero() || !nft_token_ids.is_empty(),
+        InfinityError::InvalidInput("pair must be funded with tokens or nfts".to_string())
+    );
+
+    let collection = deps.api.addr_validate(&pair_immutable.collection)?;
+    let tokens = coin(token_amount.u128(), &pair_immutable.denom);
+
+    // The token deposit is held by the factory until the pair has been instantiated
+    let mut instantiate_funds = info.funds;
+    if !token_amount.is_zero() {
+        match instantiate_funds.iter_mut().find(|c| c.denom == tokens.denom) {
+            Some(funds_coin) if funds_coin.amount >= token_amount => {
+                funds_coin.amount -= token_amount;
+            },
+            _ => {
+                return Err(InfinityError::InsufficientFunds {
+                    expected: tokens,
+                }
+                .into())
+            },
+        }
+        instantiate_funds.retain(|c| !c.amount.is_zero());
+    }
+
+    let mut response = Response::new();
+
+    // The NFTs are moved to the factory, and deposited once the pair address is known
+    for token_id in &nft_token_ids {
+        response = response.add_message(WasmMsg::Execute {
+            contract_addr: collection.to_string(),
+            msg: to_binary(&Cw721ExecuteMsg::TransferNft {
+                recipient: env.contract.address.to_string(),
+                token_id: token_id.clone(),
+            })?,
+            funds: vec![],
+        });
+    }
+
+    PENDING_PAIR_FUNDING.save(
+        deps.storage,
+        &PendingPairFunding {
+            collection,
+            nft_token_ids,
+            tokens,
+        },
+    )?;
+
+    let instantiate_msg =
+        build_instantiate_pair_msg(deps, &env, pair_immutable, pair_config, instantiate_funds)?;
+    response = response
+        .add_submessage(SubMsg::reply_on_success(instantiate_msg, CREATE_AND_FUND_PAIR_REPLY_ID));
+
+    // Event used by indexer to track pair creation
+    response = response.add_event(
+        Event::new("factory-create-pair".to_string()).add_attribute("sender", info.sender),
+    );
+
+    Ok(response)
+}
+
+fn build_instantiate_pair_msg(
+    deps: DepsMut,
+    env: &Env,
+    pair_immutable: PairImmutable<String>,
+    pair_config: PairConfig<String>,
+    funds: Vec<Coin>,
+) -> Result<WasmMsg, ContractError> {
+    let infinity_global = INFINITY_GLOBAL.load(deps.storage)?;
+    let global_config = load_global_config(&deps.querier, &infinity_global)?;
+
+    let pair_creation_fee = next_pair_creation_fee(
+        deps.storage,
+        &global_config,
+        deps.api.addr_validate(&pair_immutable.owner)?,
+    )?;
+
+    Ok(WasmMsg::Instantiate {
+        admin: Some(env.contract.address.to_string()),
+        code_id: global_config.infinity_pair_code_id,
+        label: "Infinity Pair".to_string(),
+        msg: to_binary(&InfinityPairInstantiateMsg {
+            infinity_global: infinity_global.to_string(),
+            pair_immutable,
+            pair_config,
+            pair_creation_fee: Some(pair_creation_fee),
+        })?,
+        funds,
+    })
+}
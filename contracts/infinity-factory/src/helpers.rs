@@ -1,6 +1,10 @@
+use crate::state::OWNER_PAIR_COUNTER;
 use crate::ContractError;
 
-use cosmwasm_std::{instantiate2_address, Addr, Binary, Deps, Env, Order};
+use cosmwasm_std::{
+    instantiate2_address, Addr, Binary, Coin, Deps, Env, Order, StdResult, Storage,
+};
+use infinity_global::GlobalConfig;
 use sg_index_query::{QueryBound, QueryOptions, QueryOptionsInternal};
 use sha2::{Digest, Sha256};
 use std::cmp::{max, min};
@@ -12,6 +16,17 @@ pub fn generate_salt(sender: &Addr, counter: u64) -> Binary {
     hasher.finalize().to_vec().into()
 }
 
+/// Counts a new pair for the owner, returns the pair creation fee discounted by the owner's prior pairs
+pub fn next_pair_creation_fee(
+    storage: &mut dyn Storage,
+    global_config: &GlobalConfig<Addr>,
+    owner: Addr,
+) -> StdResult<Coin> {
+    let num_prior_pairs = OWNER_PAIR_COUNTER.may_load(storage, owner.clone())?.unwrap_or_default();
+    OWNER_PAIR_COUNTER.save(storage, owner, &(num_prior_pairs + 1))?;
+    Ok(global_config.discounted_pair_creation_fee(num_prior_pairs))
+}
+
 pub fn generate_instantiate_2_addr(
     deps: Deps,
     env: &Env,
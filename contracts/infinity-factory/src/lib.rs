@@ -4,6 +4,7 @@ pub mod instantiate;
 pub mod migrate;
 pub mod msg;
 pub mod query;
+pub mod reply;
 pub mod state;
 pub mod sudo;
 
@@ -1,7 +1,7 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Binary, Uint128};
+use cosmwasm_std::{Addr, Binary, Coin, Uint128};
 use infinity_pair::pair::Pair;
-use infinity_pair::state::{PairConfig, PairImmutable};
+use infinity_pair::state::{BondingCurve, PairConfig, PairImmutable, QuoteSummary};
 use sg_index_query::QueryOptions;
 
 #[cw_serde]
@@ -23,6 +23,37 @@ pub enum ExecuteMsg {
         pair_immutable: PairImmutable<String>,
         /// The user configurable parameters of the pair
         pair_config: PairConfig<String>,
+        /// An optional nonce that makes retries idempotent. If the sender has already
+        /// created a pair with this nonce, the existing pair is returned and funds are refunded.
+        #[serde(default)]
+        client_nonce: Option<String>,
+    },
+    /// Create an NFT pair owned by the sender, which only sells NFTs to users.
+    /// Constant product curves are rejected, as they require both NFTs and tokens.
+    CreateNftPair {
+        /// The address of the NFT collection contract
+        collection: String,
+        /// The denom that NFTs are sold for
+        denom: String,
+        /// The bonding curve used to calculate the spot price
+        bonding_curve: BondingCurve,
+        /// Whether or not the pair is accepting trades
+        is_active: bool,
+        /// The address of the recipient of tokens received for NFTs, defaults to the sender
+        token_recipient: Option<String>,
+    },
+    /// Create a pair owned by the sender and fund it in the same transaction.
+    /// The NFTs must be approved for the factory, and the attached funds must cover
+    /// the pair creation fee and the token deposit. If any step fails the whole message reverts.
+    CreateAndFundPair {
+        /// The immutable parameters of the pair
+        pair_immutable: PairImmutable<String>,
+        /// The user configurable parameters of the pair
+        pair_config: PairConfig<String>,
+        /// The amount of the pair denom to deposit into the pair
+        token_amount: Uint128,
+        /// The token ids of the NFTs to deposit into the pair
+        nft_token_ids: Vec<String>,
     },
     UnrestrictedMigratePair {
         /// The address of the pair to migrate
@@ -68,6 +99,28 @@ pub enum QueryMsg {
     UnrestrictedMigrations {
         query_options: Option<QueryOptions<u64>>,
     },
+    /// The pair creation fee for the next pair created for the owner
+    #[returns(Coin)]
+    PairCreationFee {
+        owner: String,
+    },
+    #[returns(CollectionSupportedResponse)]
+    CollectionSupported {
+        collection: String,
+        denom: String,
+    },
+    #[returns(ValidatePairConfigResponse)]
+    ValidatePairConfig {
+        /// The immutable parameters of the pair
+        pair_immutable: PairImmutable<String>,
+        /// The user configurable parameters of the pair
+        pair_config: PairConfig<String>,
+    },
+    /// The current quote summaries of each of the given pairs, in the order requested
+    #[returns(Vec<PairQuoteSummaries>)]
+    QuoteSummariesByPair {
+        pairs: Vec<String>,
+    },
 }
 
 #[cw_serde]
@@ -79,6 +132,32 @@ pub struct QuotesResponse {
 
 pub type UnrestrictedMigrationsResponse = Vec<(u64, u64)>;
 
+#[cw_serde]
+pub struct CollectionSupportedResponse {
+    /// Whether or not pairs can be created for the collection and denom
+    pub supported: bool,
+    /// The reason the collection is not supported, None if it is supported
+    pub reason: Option<String>,
+}
+
+#[cw_serde]
+pub struct ValidatePairConfigResponse {
+    /// Whether or not a pair can be created with the given parameters
+    pub valid: bool,
+    /// The reason the parameters are invalid, None if they are valid
+    pub reason: Option<String>,
+}
+
+#[cw_serde]
+pub struct PairQuoteSummaries {
+    /// The address of the pair
+    pub pair: Addr,
+    /// The summary of the next swap of an NFT into the pair, None if the pair cannot buy
+    pub sell_to_pair_quote_summary: Option<QuoteSummary>,
+    /// The summary of the next swap of an NFT out of the pair, None if the pair cannot sell
+    pub buy_from_pair_quote_summary: Option<QuoteSummary>,
+}
+
 #[cw_serde]
 pub enum SudoMsg {
     AddUnrestrictedMigration {
@@ -1,11 +1,18 @@
 use crate::helpers::{generate_instantiate_2_addr, index_range_from_query_options};
-use crate::msg::{NextPairResponse, QueryMsg, QuotesResponse};
-use crate::state::{INFINITY_GLOBAL, SENDER_COUNTER, UNRESTRICTED_MIGRATIONS};
+use crate::msg::{
+    CollectionSupportedResponse, NextPairResponse, PairQuoteSummaries, QueryMsg, QuotesResponse,
+    ValidatePairConfigResponse,
+};
+use crate::state::{INFINITY_GLOBAL, OWNER_PAIR_COUNTER, SENDER_COUNTER, UNRESTRICTED_MIGRATIONS};
 
-use cosmwasm_std::{to_binary, Addr, Binary, Deps, Env, StdError, StdResult, Uint128};
+use cosmwasm_std::{to_binary, Addr, Binary, Coin, Deps, Env, StdError, StdResult, Uint128};
 use infinity_global::{load_global_config, GlobalConfig};
-use infinity_pair::helpers::load_payout_context;
+use infinity_pair::helpers::{
+    load_payout_context, only_supported_collection, validate_pair_params,
+};
+use infinity_pair::msg::QueryMsg as InfinityPairQueryMsg;
 use infinity_pair::pair::Pair;
+use infinity_pair::state::{PairConfig, PairImmutable};
 use sg_index_query::{QueryOptions, QueryOptionsInternal};
 
 #[cfg(not(feature = "library"))]
@@ -39,6 +46,31 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::UnrestrictedMigrations {
             query_options,
         } => to_binary(&query_unrestricted_migrations(deps, query_options.unwrap_or_default())?),
+        QueryMsg::PairCreationFee {
+            owner,
+        } => to_binary(&query_pair_creation_fee(deps, deps.api.addr_validate(&owner)?)?),
+        QueryMsg::CollectionSupported {
+            collection,
+            denom,
+        } => to_binary(&query_collection_supported(
+            deps,
+            env,
+            deps.api.addr_validate(&collection)?,
+            denom,
+        )?),
+        QueryMsg::ValidatePairConfig {
+            pair_immutable,
+            pair_config,
+        } => to_binary(&query_validate_pair_config(deps, env, pair_immutable, pair_config)?),
+        QueryMsg::QuoteSummariesByPair {
+            pairs,
+        } => to_binary(&query_quote_summaries_by_pair(
+            deps,
+            pairs
+                .iter()
+                .map(|pair| deps.api.addr_validate(pair))
+                .collect::<StdResult<Vec<Addr>>>()?,
+        )?),
     }
 }
 
@@ -195,3 +227,73 @@ pub fn query_unrestricted_migrations(
 
     Ok(results)
 }
+
+pub fn query_pair_creation_fee(deps: Deps, owner: Addr) -> StdResult<Coin> {
+    let infinity_global = INFINITY_GLOBAL.load(deps.storage)?;
+    let global_config = load_global_config(&deps.querier, &infinity_global)?;
+    let num_prior_pairs = OWNER_PAIR_COUNTER.may_load(deps.storage, owner)?.unwrap_or_default();
+
+    Ok(global_config.discounted_pair_creation_fee(num_prior_pairs))
+}
+
+pub fn query_collection_supported(
+    deps: Deps,
+    env: Env,
+    collection: Addr,
+    denom: String,
+) -> StdResult<CollectionSupportedResponse> {
+    let infinity_global = INFINITY_GLOBAL.load(deps.storage)?;
+
+    let reason =
+        only_supported_collection(&deps.querier, &env.block, &infinity_global, &collection, &denom)
+            .err()
+            .map(|err| err.to_string());
+
+    Ok(CollectionSupportedResponse {
+        supported: reason.is_none(),
+        reason,
+    })
+}
+
+pub fn query_validate_pair_config(
+    deps: Deps,
+    env: Env,
+    pair_immutable: PairImmutable<String>,
+    pair_config: PairConfig<String>,
+) -> StdResult<ValidatePairConfigResponse> {
+    let infinity_global = INFINITY_GLOBAL.load(deps.storage)?;
+
+    let reason = validate_pair_params(
+        deps.api,
+        &deps.querier,
+        &env.block,
+        &infinity_global,
+        pair_immutable,
+        pair_config,
+    )
+    .err()
+    .map(|err| err.to_string());
+
+    Ok(ValidatePairConfigResponse {
+        valid: reason.is_none(),
+        reason,
+    })
+}
+
+pub fn query_quote_summaries_by_pair(
+    deps: Deps,
+    pairs: Vec<Addr>,
+) -> StdResult<Vec<PairQuoteSummaries>> {
+    pairs
+        .into_iter()
+        .map(|pair| {
+            let pair_state =
+                deps.querier.query_wasm_smart::<Pair>(&pair, &InfinityPairQueryMsg::Pair {})?;
+            Ok(PairQuoteSummaries {
+                pair,
+                sell_to_pair_quote_summary: pair_state.internal.sell_to_pair_quote_summary,
+                buy_from_pair_quote_summary: pair_state.internal.buy_from_pair_quote_summary,
+            })
+        })
+        .collect()
+}
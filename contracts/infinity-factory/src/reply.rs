@@ -0,0 +1,77 @@
+use crate::state::{PendingPairFunding, PENDING_PAIR_FUNDING};
+use crate::ContractError;
+
+use cosmwasm_std::{attr, to_binary, DepsMut, Env, Event, Reply, WasmMsg};
+use cw721::Cw721ExecuteMsg;
+use cw_utils::parse_reply_instantiate_data;
+use infinity_pair::msg::ExecuteMsg as InfinityPairExecuteMsg;
+use infinity_shared::InfinityError;
+use sg_std::Response;
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+
+pub const CREATE_AND_FUND_PAIR_REPLY_ID: u64 = 1;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        CREATE_AND_FUND_PAIR_REPLY_ID => reply_create_and_fund_pair(deps, msg),
+        id => Err(InfinityError::InternalError(format!("unknown reply id: {}", id)).into()),
+    }
+}
+
+/// Deposits the assets held by the factory into the newly instantiated pair
+fn reply_create_and_fund_pair(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    let instantiate_response = parse_reply_instantiate_data(msg)
+        .map_err(|e| InfinityError::InternalError(e.to_string()))?;
+    let pair = deps.api.addr_validate(&instantiate_response.contract_address)?;
+
+    let PendingPairFunding {
+        collection,
+        nft_token_ids,
+        tokens,
+    } = PENDING_PAIR_FUNDING.load(deps.storage)?;
+    PENDING_PAIR_FUNDING.remove(deps.storage);
+
+    let mut response = Response::new();
+
+    if !nft_token_ids.is_empty() {
+        for token_id in &nft_token_ids {
+            response = response.add_message(WasmMsg::Execute {
+                contract_addr: collection.to_string(),
+                msg: to_binary(&Cw721ExecuteMsg::Approve {
+                    spender: pair.to_string(),
+                    token_id: token_id.clone(),
+                    expires: None,
+                })?,
+                funds: vec![],
+            });
+        }
+        response = response.add_message(WasmMsg::Execute {
+            contract_addr: pair.to_string(),
+            msg: to_binary(&InfinityPairExecuteMsg::DepositNfts {
+                collection: collection.to_string(),
+                token_ids: nft_token_ids.clone(),
+            })?,
+            funds: vec![],
+        });
+    }
+
+    if !tokens.amount.is_zero() {
+        response = response.add_message(WasmMsg::Execute {
+            contract_addr: pair.to_string(),
+            msg: to_binary(&InfinityPairExecuteMsg::DepositTokens {})?,
+            funds: vec![tokens.clone()],
+        });
+    }
+
+    response =
+        response.add_event(Event::new("factory-fund-pair".to_string()).add_attributes(vec![
+            attr("pair", pair),
+            attr("num_nfts", nft_token_ids.len().to_string()),
+            attr("tokens", tokens.to_string()),
+        ]));
+
+    Ok(response)
+}
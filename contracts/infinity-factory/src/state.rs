@@ -1,4 +1,5 @@
-use cosmwasm_std::Addr;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Coin};
 use cw_storage_plus::{Item, Map};
 
 pub const INFINITY_GLOBAL: Item<Addr> = Item::new("g");
@@ -6,7 +7,23 @@ pub const INFINITY_GLOBAL: Item<Addr> = Item::new("g");
 // (sender, code_id) => counter
 pub const SENDER_COUNTER: Map<(Addr, u64), u64> = Map::new("s");
 
+// (sender, client_nonce) => pair address created by CreatePair2
+pub const CLIENT_NONCES: Map<(Addr, String), Addr> = Map::new("n");
+
+// owner => number of pairs created for the owner, used to discount the pair creation fee
+pub const OWNER_PAIR_COUNTER: Map<Addr, u64> = Map::new("o");
+
 // code_id => code_id
 // This is a map of code ids that are allowed to migrate to subsequent code ids.
 // This set of migrations can be invoked by anyone.
 pub const UNRESTRICTED_MIGRATIONS: Map<u64, u64> = Map::new("um");
+
+/// The assets to deposit into a pair created by CreateAndFundPair, once its address is known
+#[cw_serde]
+pub struct PendingPairFunding {
+    pub collection: Addr,
+    pub nft_token_ids: Vec<String>,
+    pub tokens: Coin,
+}
+
+pub const PENDING_PAIR_FUNDING: Item<PendingPairFunding> = Item::new("pf");
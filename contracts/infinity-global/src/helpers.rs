@@ -21,3 +21,16 @@ pub fn load_min_price(
         },
     )
 }
+
+pub fn load_collection_allowed(
+    querier: &QuerierWrapper,
+    infinity_global: &Addr,
+    collection: &Addr,
+) -> StdResult<bool> {
+    querier.query_wasm_smart::<bool>(
+        infinity_global,
+        &QueryMsg::CollectionAllowed {
+            collection: collection.to_string(),
+        },
+    )
+}
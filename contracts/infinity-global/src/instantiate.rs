@@ -4,7 +4,7 @@ use crate::{
     state::{GLOBAL_CONFIG, MIN_PRICES},
 };
 
-use cosmwasm_std::{DepsMut, Env, MessageInfo, StdError};
+use cosmwasm_std::{ensure, Decimal, DepsMut, Env, MessageInfo, StdError};
 use cw2::set_contract_version;
 use sg_std::Response;
 
@@ -21,6 +21,19 @@ pub fn instantiate(
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     let global_config = msg.global_config.str_to_addr(deps.api)?;
+    ensure!(
+        global_config.max_nfts_per_pair != Some(0),
+        StdError::generic_err("max_nfts_per_pair must be greater than zero")
+    );
+    ensure!(
+        global_config.burn_share_percent.map_or(true, |p| p <= Decimal::one()),
+        StdError::generic_err("burn_share_percent cannot exceed 100%")
+    );
+    ensure!(
+        global_config.pair_creation_fee_discount_percent.map_or(true, |p| p <= Decimal::one()),
+        StdError::generic_err("pair_creation_fee_discount_percent cannot exceed 100%")
+    );
+    global_config.validate_spot_price_bounds()?;
     GLOBAL_CONFIG.save(deps.storage, &global_config)?;
 
     for min_price in msg.min_prices {
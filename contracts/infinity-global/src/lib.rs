@@ -11,5 +11,5 @@ pub mod sudo;
 mod error;
 
 pub use error::ContractError;
-pub use helpers::{load_global_config, load_min_price};
+pub use helpers::{load_collection_allowed, load_global_config, load_min_price};
 pub use state::GlobalConfig;
@@ -1,7 +1,7 @@
 use crate::state::GlobalConfig;
 
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Coin, Decimal};
+use cosmwasm_std::{Addr, Coin, Decimal, Uint128};
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -18,6 +18,26 @@ pub enum QueryMsg {
     MinPrice {
         denom: String,
     },
+    /// Whether or not pairs can be created for the collection
+    #[returns(bool)]
+    CollectionAllowed {
+        collection: String,
+    },
+}
+
+/// The optional settings of the global config that can be cleared
+#[cw_serde]
+pub enum OptionalConfigField {
+    MaxNftsPerPair,
+    PairConfigUpdateCooldownSeconds,
+    TradingFeeRecipient,
+    BurnSharePercent,
+    MinTokenDeposit,
+    MinNftCount,
+    PairCreationFeeDiscountPercent,
+    MinSpotPrice,
+    MaxSpotPrice,
+    ProbeCollectionFreeze,
 }
 
 #[cw_serde]
@@ -36,6 +56,20 @@ pub enum SudoMsg {
         default_royalty_fee_percent: Option<Decimal>,
         max_royalty_fee_percent: Option<Decimal>,
         max_swap_fee_percent: Option<Decimal>,
+        max_nfts_per_pair: Option<u64>,
+        pair_config_update_cooldown_seconds: Option<u64>,
+        trading_fee_recipient: Option<String>,
+        burn_share_percent: Option<Decimal>,
+        min_token_deposit: Option<Uint128>,
+        min_nft_count: Option<u64>,
+        pair_creation_fee_discount_percent: Option<Decimal>,
+        min_spot_price: Option<Uint128>,
+        max_spot_price: Option<Uint128>,
+        probe_collection_freeze: Option<bool>,
+    },
+    /// Resets the given optional settings to None, disabling them
+    ClearConfig {
+        fields: Vec<OptionalConfigField>,
     },
     AddMinPrices {
         min_prices: Vec<Coin>,
@@ -43,4 +77,8 @@ pub enum SudoMsg {
     RemoveMinPrices {
         denoms: Vec<String>,
     },
+    UpdateAllowedCollections {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
 }
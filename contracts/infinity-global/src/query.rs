@@ -1,9 +1,9 @@
 use crate::{
     msg::QueryMsg,
-    state::{GLOBAL_CONFIG, MIN_PRICES},
+    state::{ALLOWED_COLLECTIONS, GLOBAL_CONFIG, MIN_PRICES},
 };
 
-use cosmwasm_std::{coin, to_binary, Binary, Deps, Env, StdResult};
+use cosmwasm_std::{coin, to_binary, Binary, Deps, Env, Order, StdResult};
 
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
@@ -18,5 +18,16 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             let min_amount = MIN_PRICES.may_load(deps.storage, denom.clone())?;
             to_binary(&Some(min_amount.map(|a| coin(a.u128(), denom))))
         },
+        QueryMsg::CollectionAllowed {
+            collection,
+        } => {
+            let collection = deps.api.addr_validate(&collection)?;
+            let allowed = ALLOWED_COLLECTIONS.has(deps.storage, collection)
+                || ALLOWED_COLLECTIONS
+                    .keys(deps.storage, None, None, Order::Ascending)
+                    .next()
+                    .is_none();
+            to_binary(&allowed)
+        },
     }
 }
@@ -1,6 +1,6 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, StdError, Uint128};
-use cosmwasm_std::{Api, Coin, Decimal};
+use cosmwasm_std::{coin, Api, Coin, Decimal};
+use cosmwasm_std::{ensure, Addr, Empty, StdError, Uint128};
 use cw_address_like::AddressLike;
 use cw_storage_plus::{Item, Map};
 
@@ -30,6 +30,32 @@ pub struct GlobalConfig<T: AddressLike> {
     pub max_royalty_fee_percent: Decimal,
     /// The maximum percentage amount of a sale that can be paid to LPs
     pub max_swap_fee_percent: Decimal,
+    /// The maximum number of NFTs that a single pair can hold through deposits or reinvested
+    /// swaps, unlimited if None
+    pub max_nfts_per_pair: Option<u64>,
+    /// The minimum number of seconds between pair config updates, disabled if None or zero.
+    /// Updates that only deactivate a pair are not subject to the cooldown.
+    pub pair_config_update_cooldown_seconds: Option<u64>,
+    /// The address that receives the share of the fair burn fee that is not burned
+    pub trading_fee_recipient: Option<T>,
+    /// The percentage of the fair burn fee that is burned when a trading fee recipient is set,
+    /// the entire fee is burned if None
+    pub burn_share_percent: Option<Decimal>,
+    /// The minimum token balance a pair must hold to be listed in the sell to pair index
+    pub min_token_deposit: Option<Uint128>,
+    /// The minimum number of NFTs a pair must hold to be listed in the buy from pair index
+    pub min_nft_count: Option<u64>,
+    /// The pair creation fee discount applied per pair an owner has already created,
+    /// the fee is never discounted below zero. No discount if None.
+    pub pair_creation_fee_discount_percent: Option<Decimal>,
+    /// The minimum spot price of a new pair, guards against prices entered in the wrong units.
+    /// Disabled if None or zero.
+    pub min_spot_price: Option<Uint128>,
+    /// The maximum spot price of a new pair, disabled if None or zero
+    pub max_spot_price: Option<Uint128>,
+    /// Whether or not pairs query the collection for a transfer freeze before accepting
+    /// NFT deposits. Best effort, collections that do not support the query are not checked.
+    pub probe_collection_freeze: Option<bool>,
 }
 
 impl GlobalConfig<String> {
@@ -47,10 +73,55 @@ impl GlobalConfig<String> {
             default_royalty_fee_percent: self.default_royalty_fee_percent,
             max_royalty_fee_percent: self.max_royalty_fee_percent,
             max_swap_fee_percent: self.max_swap_fee_percent,
+            max_nfts_per_pair: self.max_nfts_per_pair,
+            pair_config_update_cooldown_seconds: self.pair_config_update_cooldown_seconds,
+            trading_fee_recipient: self
+                .trading_fee_recipient
+                .map(|recipient| api.addr_validate(&recipient))
+                .transpose()?,
+            burn_share_percent: self.burn_share_percent,
+            min_token_deposit: self.min_token_deposit,
+            min_nft_count: self.min_nft_count,
+            pair_creation_fee_discount_percent: self.pair_creation_fee_discount_percent,
+            min_spot_price: self.min_spot_price,
+            max_spot_price: self.max_spot_price,
+            probe_collection_freeze: self.probe_collection_freeze,
         })
     }
 }
 
+impl<T: AddressLike> GlobalConfig<T> {
+    /// The pair creation fee for an owner that has already created `num_prior_pairs` pairs
+    pub fn discounted_pair_creation_fee(&self, num_prior_pairs: u64) -> Coin {
+        let discount = self
+            .pair_creation_fee_discount_percent
+            .unwrap_or_default()
+            .checked_mul(Decimal::from_ratio(num_prior_pairs, 1u64))
+            .unwrap_or(Decimal::one());
+        let amount =
+            self.pair_creation_fee.amount.mul_floor(Decimal::one().saturating_sub(discount));
+        coin(amount.u128(), &self.pair_creation_fee.denom)
+    }
+
+    /// Ensures that the spot price bounds, when both are enabled, do not exclude every price
+    pub fn validate_spot_price_bounds(&self) -> Result<(), StdError> {
+        if let (Some(min_spot_price), Some(max_spot_price)) =
+            (self.min_spot_price, self.max_spot_price)
+        {
+            ensure!(
+                min_spot_price.is_zero()
+                    || max_spot_price.is_zero()
+                    || min_spot_price <= max_spot_price,
+                StdError::generic_err("min_spot_price cannot exceed max_spot_price")
+            );
+        }
+        Ok(())
+    }
+}
+
 pub const GLOBAL_CONFIG: Item<GlobalConfig<Addr>> = Item::new("g");
 
 pub const MIN_PRICES: Map<String, Uint128> = Map::new("m");
+
+// The collections that pairs can be created for, all collections are allowed if empty
+pub const ALLOWED_COLLECTIONS: Map<Addr, Empty> = Map::new("ac");
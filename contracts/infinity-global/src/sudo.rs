@@ -1,9 +1,9 @@
 use crate::{
-    msg::SudoMsg,
-    state::{GLOBAL_CONFIG, MIN_PRICES},
+    msg::{OptionalConfigField, SudoMsg},
+    state::{ALLOWED_COLLECTIONS, GLOBAL_CONFIG, MIN_PRICES},
 };
 
-use cosmwasm_std::{attr, Coin, Decimal, DepsMut, Env, Event, StdError};
+use cosmwasm_std::{attr, ensure, Coin, Decimal, DepsMut, Empty, Env, Event, StdError, Uint128};
 use sg_std::Response;
 
 #[cfg(not(feature = "library"))]
@@ -25,6 +25,16 @@ pub fn sudo(deps: DepsMut, _env: Env, msg: SudoMsg) -> Result<Response, StdError
             default_royalty_fee_percent,
             max_royalty_fee_percent,
             max_swap_fee_percent,
+            max_nfts_per_pair,
+            pair_config_update_cooldown_seconds,
+            trading_fee_recipient,
+            burn_share_percent,
+            min_token_deposit,
+            min_nft_count,
+            pair_creation_fee_discount_percent,
+            min_spot_price,
+            max_spot_price,
+            probe_collection_freeze,
         } => sudo_update_config(
             deps,
             fair_burn,
@@ -39,13 +49,30 @@ pub fn sudo(deps: DepsMut, _env: Env, msg: SudoMsg) -> Result<Response, StdError
             default_royalty_fee_percent,
             max_royalty_fee_percent,
             max_swap_fee_percent,
+            max_nfts_per_pair,
+            pair_config_update_cooldown_seconds,
+            trading_fee_recipient,
+            burn_share_percent,
+            min_token_deposit,
+            min_nft_count,
+            pair_creation_fee_discount_percent,
+            min_spot_price,
+            max_spot_price,
+            probe_collection_freeze,
         ),
+        SudoMsg::ClearConfig {
+            fields,
+        } => sudo_clear_config(deps, fields),
         SudoMsg::AddMinPrices {
             min_prices,
         } => sudo_add_min_prices(deps, min_prices),
         SudoMsg::RemoveMinPrices {
             denoms,
         } => sudo_remove_min_prices(deps, denoms),
+        SudoMsg::UpdateAllowedCollections {
+            add,
+            remove,
+        } => sudo_update_allowed_collections(deps, add, remove),
     }
 }
 
@@ -64,6 +91,16 @@ pub fn sudo_update_config(
     default_royalty_fee_percent: Option<Decimal>,
     max_royalty_fee_percent: Option<Decimal>,
     max_swap_fee_percent: Option<Decimal>,
+    max_nfts_per_pair: Option<u64>,
+    pair_config_update_cooldown_seconds: Option<u64>,
+    trading_fee_recipient: Option<String>,
+    burn_share_percent: Option<Decimal>,
+    min_token_deposit: Option<Uint128>,
+    min_nft_count: Option<u64>,
+    pair_creation_fee_discount_percent: Option<Decimal>,
+    min_spot_price: Option<Uint128>,
+    max_spot_price: Option<Uint128>,
+    probe_collection_freeze: Option<bool>,
 ) -> Result<Response, StdError> {
     let api = deps.api;
 
@@ -132,6 +169,134 @@ pub fn sudo_update_config(
         config.max_swap_fee_percent = max_swap_fee_percent;
     }
 
+    if let Some(max_nfts_per_pair) = max_nfts_per_pair {
+        ensure!(
+            max_nfts_per_pair > 0,
+            StdError::generic_err("max_nfts_per_pair must be greater than zero")
+        );
+        event = event.add_attribute("max_nfts_per_pair", max_nfts_per_pair.to_string());
+        config.max_nfts_per_pair = Some(max_nfts_per_pair);
+    }
+
+    if let Some(pair_config_update_cooldown_seconds) = pair_config_update_cooldown_seconds {
+        event = event.add_attribute(
+            "pair_config_update_cooldown_seconds",
+            pair_config_update_cooldown_seconds.to_string(),
+        );
+        config.pair_config_update_cooldown_seconds = Some(pair_config_update_cooldown_seconds);
+    }
+
+    if let Some(trading_fee_recipient) = trading_fee_recipient {
+        event = event.add_attribute("trading_fee_recipient", &trading_fee_recipient);
+        config.trading_fee_recipient = Some(api.addr_validate(&trading_fee_recipient)?);
+    }
+
+    if let Some(burn_share_percent) = burn_share_percent {
+        ensure!(
+            burn_share_percent <= Decimal::one(),
+            StdError::generic_err("burn_share_percent cannot exceed 100%")
+        );
+        event = event.add_attribute("burn_share_percent", burn_share_percent.to_string());
+        config.burn_share_percent = Some(burn_share_percent);
+    }
+
+    if let Some(min_token_deposit) = min_token_deposit {
+        event = event.add_attribute("min_token_deposit", min_token_deposit.to_string());
+        config.min_token_deposit = Some(min_token_deposit);
+    }
+
+    if let Some(min_nft_count) = min_nft_count {
+        event = event.add_attribute("min_nft_count", min_nft_count.to_string());
+        config.min_nft_count = Some(min_nft_count);
+    }
+
+    if let Some(pair_creation_fee_discount_percent) = pair_creation_fee_discount_percent {
+        ensure!(
+            pair_creation_fee_discount_percent <= Decimal::one(),
+            StdError::generic_err("pair_creation_fee_discount_percent cannot exceed 100%")
+        );
+        event = event.add_attribute(
+            "pair_creation_fee_discount_percent",
+            pair_creation_fee_discount_percent.to_string(),
+        );
+        config.pair_creation_fee_discount_percent = Some(pair_creation_fee_discount_percent);
+    }
+
+    if let Some(min_spot_price) = min_spot_price {
+        event = event.add_attribute("min_spot_price", min_spot_price.to_string());
+        config.min_spot_price = Some(min_spot_price);
+    }
+
+    if let Some(max_spot_price) = max_spot_price {
+        event = event.add_attribute("max_spot_price", max_spot_price.to_string());
+        config.max_spot_price = Some(max_spot_price);
+    }
+
+    if let Some(probe_collection_freeze) = probe_collection_freeze {
+        event = event.add_attribute("probe_collection_freeze", probe_collection_freeze.to_string());
+        config.probe_collection_freeze = Some(probe_collection_freeze);
+    }
+
+    config.validate_spot_price_bounds()?;
+
+    GLOBAL_CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_event(event))
+}
+
+pub fn sudo_clear_config(
+    deps: DepsMut,
+    fields: Vec<OptionalConfigField>,
+) -> Result<Response, StdError> {
+    let mut config = GLOBAL_CONFIG.load(deps.storage)?;
+
+    let mut event = Event::new("sudo-clear-config");
+    for field in fields {
+        let name = match field {
+            OptionalConfigField::MaxNftsPerPair => {
+                config.max_nfts_per_pair = None;
+                "max_nfts_per_pair"
+            },
+            OptionalConfigField::PairConfigUpdateCooldownSeconds => {
+                config.pair_config_update_cooldown_seconds = None;
+                "pair_config_update_cooldown_seconds"
+            },
+            OptionalConfigField::TradingFeeRecipient => {
+                config.trading_fee_recipient = None;
+                "trading_fee_recipient"
+            },
+            OptionalConfigField::BurnSharePercent => {
+                config.burn_share_percent = None;
+                "burn_share_percent"
+            },
+            OptionalConfigField::MinTokenDeposit => {
+                config.min_token_deposit = None;
+                "min_token_deposit"
+            },
+            OptionalConfigField::MinNftCount => {
+                config.min_nft_count = None;
+                "min_nft_count"
+            },
+            OptionalConfigField::PairCreationFeeDiscountPercent => {
+                config.pair_creation_fee_discount_percent = None;
+                "pair_creation_fee_discount_percent"
+            },
+            OptionalConfigField::MinSpotPrice => {
+                config.min_spot_price = None;
+                "min_spot_price"
+            },
+            OptionalConfigField::MaxSpotPrice => {
+                config.max_spot_price = None;
+                "max_spot_price"
+            },
+            OptionalConfigField::ProbeCollectionFreeze => {
+                config.probe_collection_freeze = None;
+                "probe_collection_freeze"
+            },
+        };
+        event = event.add_attribute("field", name);
+    }
+
     GLOBAL_CONFIG.save(deps.storage, &config)?;
 
     Ok(Response::new().add_event(event))
@@ -159,3 +324,21 @@ pub fn sudo_remove_min_prices(deps: DepsMut, denoms: Vec<String>) -> Result<Resp
 
     Ok(Response::new().add_event(event))
 }
+
+pub fn sudo_update_allowed_collections(
+    deps: DepsMut,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, StdError> {
+    let mut event = Event::new("sudo-update-allowed-collections");
+    for collection in add {
+        ALLOWED_COLLECTIONS.save(deps.storage, deps.api.addr_validate(&collection)?, &Empty {})?;
+        event = event.add_attribute("add", collection);
+    }
+    for collection in remove {
+        ALLOWED_COLLECTIONS.remove(deps.storage, deps.api.addr_validate(&collection)?);
+        event = event.add_attribute("remove", collection);
+    }
+
+    Ok(Response::new().add_event(event))
+}
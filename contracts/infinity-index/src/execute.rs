@@ -1,12 +1,15 @@
 use crate::helpers::only_infinity_pair;
 use crate::msg::ExecuteMsg;
-use crate::state::PairQuote;
+use crate::state::{PairAssets, PairCreated, PairQuote};
 use crate::{
     error::ContractError,
-    state::{buy_from_pair_quotes, sell_to_pair_quotes},
+    state::{
+        buy_from_pair_quotes, pairs_created, sell_to_pair_quotes, COLLECTION_STATS, PAIR_ASSETS,
+        TOTAL_NFTS_LOCKED, TOTAL_TOKENS_LOCKED,
+    },
 };
 
-use cosmwasm_std::{coin, Addr, DepsMut, Env, MessageInfo, Uint128};
+use cosmwasm_std::{coin, Addr, DepsMut, Env, MessageInfo, StdResult, Storage, Timestamp, Uint128};
 use sg_std::Response;
 
 #[cfg(not(feature = "library"))]
@@ -27,6 +30,8 @@ pub fn execute(
             denom,
             sell_to_pair_quote,
             buy_from_pair_quote,
+            total_tokens,
+            total_nfts,
         } => execute_update_pair_indices(
             deps,
             env,
@@ -35,10 +40,37 @@ pub fn execute(
             denom,
             sell_to_pair_quote,
             buy_from_pair_quote,
+            total_tokens,
+            total_nfts,
+        ),
+        ExecuteMsg::UpdateCollectionStats {
+            collection,
+            denom,
+            volume,
+        } => execute_update_collection_stats(
+            deps,
+            env,
+            info,
+            api.addr_validate(&collection)?,
+            denom,
+            volume,
+        ),
+        ExecuteMsg::RegisterPair {
+            collection,
+            denom,
+            created_at,
+        } => execute_register_pair(
+            deps,
+            env,
+            info,
+            api.addr_validate(&collection)?,
+            denom,
+            created_at,
         ),
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn execute_update_pair_indices(
     deps: DepsMut,
     _env: Env,
@@ -47,9 +79,23 @@ pub fn execute_update_pair_indices(
     denom: String,
     sell_to_pair_quote: Option<Uint128>,
     buy_from_pair_quote: Option<Uint128>,
+    total_tokens: Option<Uint128>,
+    total_nfts: Option<u64>,
 ) -> Result<Response, ContractError> {
     only_infinity_pair(deps.as_ref(), &info.sender)?;
 
+    // Pairs running older code do not report their assets
+    if let (Some(total_tokens), Some(total_nfts)) = (total_tokens, total_nfts) {
+        update_total_value_locked(
+            deps.storage,
+            &info.sender,
+            PairAssets {
+                total_tokens: coin(total_tokens.u128(), &denom),
+                total_nfts,
+            },
+        )?;
+    }
+
     match sell_to_pair_quote {
         Some(amount) => {
             sell_to_pair_quotes().save(
@@ -86,3 +132,81 @@ pub fn execute_update_pair_indices(
 
     Ok(Response::new())
 }
+
+/// Replaces the pair's previously reported assets in the running totals with its current assets
+fn update_total_value_locked(
+    storage: &mut dyn Storage,
+    pair: &Addr,
+    pair_assets: PairAssets,
+) -> Result<(), ContractError> {
+    let mut total_nfts = TOTAL_NFTS_LOCKED.may_load(storage)?.unwrap_or_default();
+
+    if let Some(prev_assets) = PAIR_ASSETS.may_load(storage, pair.clone())? {
+        TOTAL_TOKENS_LOCKED.update(
+            storage,
+            prev_assets.total_tokens.denom,
+            |total| -> StdResult<_> {
+                Ok(total.unwrap_or_default().checked_sub(prev_assets.total_tokens.amount)?)
+            },
+        )?;
+        total_nfts -= prev_assets.total_nfts;
+    }
+
+    TOTAL_TOKENS_LOCKED.update(
+        storage,
+        pair_assets.total_tokens.denom.clone(),
+        |total| -> StdResult<_> {
+            Ok(total.unwrap_or_default().checked_add(pair_assets.total_tokens.amount)?)
+        },
+    )?;
+    total_nfts += pair_assets.total_nfts;
+    TOTAL_NFTS_LOCKED.save(storage, &total_nfts)?;
+
+    PAIR_ASSETS.save(storage, pair.clone(), &pair_assets)?;
+
+    Ok(())
+}
+
+pub fn execute_update_collection_stats(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    collection: Addr,
+    denom: String,
+    volume: Uint128,
+) -> Result<Response, ContractError> {
+    only_infinity_pair(deps.as_ref(), &info.sender)?;
+
+    let key = (collection, denom);
+    let mut collection_stats =
+        COLLECTION_STATS.may_load(deps.storage, key.clone())?.unwrap_or_default();
+    collection_stats.total_volume += volume;
+    collection_stats.total_swaps += 1;
+    COLLECTION_STATS.save(deps.storage, key, &collection_stats)?;
+
+    Ok(Response::new())
+}
+
+pub fn execute_register_pair(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    collection: Addr,
+    denom: String,
+    created_at: Timestamp,
+) -> Result<Response, ContractError> {
+    only_infinity_pair(deps.as_ref(), &info.sender)?;
+
+    pairs_created().save(
+        deps.storage,
+        info.sender.clone(),
+        &PairCreated {
+            address: info.sender,
+            collection,
+            denom,
+            created_at,
+        },
+    )?;
+
+    Ok(Response::new())
+}
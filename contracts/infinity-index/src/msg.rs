@@ -1,8 +1,8 @@
 #[cfg_attr(not(debug_assertions), allow(unused_imports))]
-use crate::state::PairQuote;
+use crate::state::{CollectionStats, PairCreated, PairQuote};
 
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{Coin, Timestamp, Uint128};
 use sg_index_query::QueryOptions;
 
 #[cw_serde]
@@ -19,9 +19,35 @@ pub enum ExecuteMsg {
         denom: String,
         sell_to_pair_quote: Option<Uint128>,
         buy_from_pair_quote: Option<Uint128>,
+        /// The tokens held by the pair, used to maintain the total value locked
+        #[serde(default)]
+        total_tokens: Option<Uint128>,
+        /// The NFTs held by the pair, used to maintain the total value locked
+        #[serde(default)]
+        total_nfts: Option<u64>,
+    },
+    /// Record a swap performed by a pair in the collection stats
+    UpdateCollectionStats {
+        collection: String,
+        denom: String,
+        volume: Uint128,
+    },
+    /// Record the creation of a pair, so pairs can be queried by creation time
+    RegisterPair {
+        collection: String,
+        denom: String,
+        created_at: Timestamp,
     },
 }
 
+#[cw_serde]
+pub struct PairCreatedOffset {
+    /// The address of the infinity pair contract
+    pub pair: String,
+    /// The block time at which the pair was created
+    pub created_at: Timestamp,
+}
+
 #[cw_serde]
 pub struct PairQuoteOffset {
     /// The address of the infinity pair contract
@@ -30,6 +56,15 @@ pub struct PairQuoteOffset {
     pub amount: Uint128,
 }
 
+/// Defines which of the quote indices is being referenced
+#[cw_serde]
+pub enum QuoteSide {
+    /// Quotes for users selling NFTs into pairs
+    SellToPair,
+    /// Quotes for users buying NFTs from pairs
+    BuyFromPair,
+}
+
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
@@ -45,4 +80,42 @@ pub enum QueryMsg {
         denom: String,
         query_options: Option<QueryOptions<PairQuoteOffset>>,
     },
+    /// The number of pairs currently quoting a collection on one side of the market,
+    /// which is the maximum number of swaps a single batch can fill
+    #[returns(u64)]
+    MaxSwapsForCollection {
+        collection: String,
+        denom: String,
+        side: QuoteSide,
+    },
+    /// The best quotes of a collection on one side of the market, best first. The highest
+    /// quotes are best for users selling to pairs, the lowest for users buying from pairs.
+    #[returns(Vec<PairQuote>)]
+    BestQuotes {
+        collection: String,
+        denom: String,
+        side: QuoteSide,
+        limit: u32,
+    },
+    #[returns(CollectionStats)]
+    CollectionStats {
+        collection: String,
+        denom: String,
+    },
+    /// The pairs of a collection and denom, ordered by creation time
+    #[returns(Vec<PairCreated>)]
+    PairsByCreatedAt {
+        collection: String,
+        denom: String,
+        query_options: Option<QueryOptions<PairCreatedOffset>>,
+    },
+    /// The tokens, per denom, and NFTs held across all pairs
+    #[returns(TotalValueLockedResponse)]
+    TotalValueLocked {},
+}
+
+#[cw_serde]
+pub struct TotalValueLockedResponse {
+    pub total_tokens: Vec<Coin>,
+    pub total_nfts: u64,
 }
@@ -1,7 +1,12 @@
-use crate::msg::{PairQuoteOffset, QueryMsg};
-use crate::state::{buy_from_pair_quotes, sell_to_pair_quotes, PairQuote};
+use crate::msg::{
+    PairCreatedOffset, PairQuoteOffset, QueryMsg, QuoteSide, TotalValueLockedResponse,
+};
+use crate::state::{
+    buy_from_pair_quotes, pairs_created, sell_to_pair_quotes, CollectionStats, PairCreated,
+    PairQuote, COLLECTION_STATS, TOTAL_NFTS_LOCKED, TOTAL_TOKENS_LOCKED,
+};
 
-use cosmwasm_std::{to_binary, Addr, Binary, Deps, Env, StdResult};
+use cosmwasm_std::{coin, to_binary, Addr, Binary, Coin, Deps, Env, Order, StdResult};
 use sg_index_query::{QueryOptions, QueryOptionsInternal};
 
 #[cfg(not(feature = "library"))]
@@ -30,6 +35,43 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             denom,
             query_options.unwrap_or_default(),
         )?),
+        QueryMsg::MaxSwapsForCollection {
+            collection,
+            denom,
+            side,
+        } => to_binary(&query_max_swaps_for_collection(
+            deps,
+            deps.api.addr_validate(&collection)?,
+            denom,
+            side,
+        )?),
+        QueryMsg::BestQuotes {
+            collection,
+            denom,
+            side,
+            limit,
+        } => to_binary(&query_best_quotes(
+            deps,
+            deps.api.addr_validate(&collection)?,
+            denom,
+            side,
+            limit,
+        )?),
+        QueryMsg::CollectionStats {
+            collection,
+            denom,
+        } => to_binary(&query_collection_stats(deps, deps.api.addr_validate(&collection)?, denom)?),
+        QueryMsg::PairsByCreatedAt {
+            collection,
+            denom,
+            query_options,
+        } => to_binary(&query_pairs_by_created_at(
+            deps,
+            deps.api.addr_validate(&collection)?,
+            denom,
+            query_options.unwrap_or_default(),
+        )?),
+        QueryMsg::TotalValueLocked {} => to_binary(&query_total_value_locked(deps)?),
     }
 }
 
@@ -90,3 +132,114 @@ pub fn query_buy_from_pair_quotes(
 
     Ok(results)
 }
+
+pub fn query_best_quotes(
+    deps: Deps,
+    collection: Addr,
+    denom: String,
+    side: QuoteSide,
+    limit: u32,
+) -> StdResult<Vec<PairQuote>> {
+    match side {
+        QuoteSide::SellToPair => query_sell_to_pair_quotes(
+            deps,
+            collection,
+            denom,
+            QueryOptions {
+                descending: Some(true),
+                limit: Some(limit),
+                min: None,
+                max: None,
+            },
+        ),
+        QuoteSide::BuyFromPair => query_buy_from_pair_quotes(
+            deps,
+            collection,
+            denom,
+            QueryOptions {
+                descending: Some(false),
+                limit: Some(limit),
+                min: None,
+                max: None,
+            },
+        ),
+    }
+}
+
+pub fn query_max_swaps_for_collection(
+    deps: Deps,
+    collection: Addr,
+    denom: String,
+    side: QuoteSide,
+) -> StdResult<u64> {
+    let count = match side {
+        QuoteSide::SellToPair => sell_to_pair_quotes()
+            .idx
+            .collection_quote
+            .sub_prefix((collection, denom))
+            .keys_raw(deps.storage, None, None, Order::Ascending)
+            .count(),
+        QuoteSide::BuyFromPair => buy_from_pair_quotes()
+            .idx
+            .collection_quote
+            .sub_prefix((collection, denom))
+            .keys_raw(deps.storage, None, None, Order::Ascending)
+            .count(),
+    };
+
+    Ok(count as u64)
+}
+
+pub fn query_collection_stats(
+    deps: Deps,
+    collection: Addr,
+    denom: String,
+) -> StdResult<CollectionStats> {
+    let collection_stats =
+        COLLECTION_STATS.may_load(deps.storage, (collection, denom))?.unwrap_or_default();
+
+    Ok(collection_stats)
+}
+
+pub fn query_pairs_by_created_at(
+    deps: Deps,
+    collection: Addr,
+    denom: String,
+    query_options: QueryOptions<PairCreatedOffset>,
+) -> StdResult<Vec<PairCreated>> {
+    let QueryOptionsInternal {
+        limit,
+        order,
+        min,
+        max,
+    } = query_options.unpack(
+        &(|offset| (offset.created_at.nanos(), Addr::unchecked(offset.pair.clone()))),
+        None,
+        None,
+    );
+
+    let results = pairs_created()
+        .idx
+        .collection_created_at
+        .sub_prefix((collection, denom))
+        .range_raw(deps.storage, min, max, order)
+        .take(limit)
+        .map(|res| res.map(|(_, pc)| pc))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(results)
+}
+
+pub fn query_total_value_locked(deps: Deps) -> StdResult<TotalValueLockedResponse> {
+    let total_tokens = TOTAL_TOKENS_LOCKED
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter(|item| item.as_ref().map_or(true, |(_, amount)| !amount.is_zero()))
+        .map(|item| item.map(|(denom, amount)| coin(amount.u128(), denom)))
+        .collect::<StdResult<Vec<Coin>>>()?;
+    let total_nfts = TOTAL_NFTS_LOCKED.may_load(deps.storage)?.unwrap_or_default();
+
+    Ok(TotalValueLockedResponse {
+        total_tokens,
+        total_nfts,
+    })
+}
@@ -1,10 +1,39 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Coin};
+use cosmwasm_std::{Addr, Coin, Timestamp, Uint128};
 use cw_storage_macro::index_list;
-use cw_storage_plus::{IndexedMap, Item, MultiIndex};
+use cw_storage_plus::{IndexedMap, Item, Map, MultiIndex};
 
 pub const INFINITY_GLOBAL: Item<Addr> = Item::new("g");
 
+/// CollectionStats is a rollup of the swaps performed by all pairs of a collection and denom
+#[cw_serde]
+#[derive(Default)]
+pub struct CollectionStats {
+    /// The sum of the amounts paid for NFTs across all swaps
+    pub total_volume: Uint128,
+    /// The number of swaps
+    pub total_swaps: u64,
+}
+
+// A map of swap stats, keyed by (collection, denom)
+pub const COLLECTION_STATS: Map<(Addr, String), CollectionStats> = Map::new("c");
+
+/// PairAssets are the assets held by a pair, as last reported to the index
+#[cw_serde]
+pub struct PairAssets {
+    pub total_tokens: Coin,
+    pub total_nfts: u64,
+}
+
+// A map of the assets held by each pair, keyed by pair address
+pub const PAIR_ASSETS: Map<Addr, PairAssets> = Map::new("a");
+
+// A running total of the tokens held by all pairs, keyed by denom
+pub const TOTAL_TOKENS_LOCKED: Map<String, Uint128> = Map::new("t");
+
+// A running total of the NFTs held by all pairs
+pub const TOTAL_NFTS_LOCKED: Item<u64> = Item::new("n");
+
 #[cw_serde]
 pub struct PairQuote {
     pub address: Addr,
@@ -47,3 +76,30 @@ pub fn sell_to_pair_quotes<'a>() -> IndexedMap<'a, Addr, PairQuote, SellPairQuot
     };
     IndexedMap::new("s", indexes)
 }
+
+/// PairCreated records the creation time of a pair
+#[cw_serde]
+pub struct PairCreated {
+    pub address: Addr,
+    pub collection: Addr,
+    pub denom: String,
+    pub created_at: Timestamp,
+}
+
+#[index_list(PairCreated)]
+pub struct PairCreatedIndices<'a> {
+    pub collection_created_at: MultiIndex<'a, (Addr, String, u64), PairCreated, Addr>,
+}
+
+pub fn pairs_created<'a>() -> IndexedMap<'a, Addr, PairCreated, PairCreatedIndices<'a>> {
+    let indexes = PairCreatedIndices {
+        collection_created_at: MultiIndex::new(
+            |_pk: &[u8], p: &PairCreated| {
+                (p.collection.clone(), p.denom.clone(), p.created_at.nanos())
+            },
+            "p",
+            "pc",
+        ),
+    };
+    IndexedMap::new("p", indexes)
+}
@@ -1,6 +1,32 @@
 pub const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
 pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// The maximum number of spot price snapshots retained by a pair
+pub const MAX_PRICE_SNAPSHOTS: usize = 32;
+
+/// The maximum delta of exponential bonding curves, 10000 bps (100%)
+pub const MAX_EXPONENTIAL_DELTA_BPS: u64 = 10000;
+
+/// The share of a constant product pair's value held in tokens, 5000 bps (50%)
+pub const CONSTANT_PRODUCT_WEIGHT_BPS: u64 = 5000;
+
+/// The minimum and maximum token weight of weighted constant product curves, 50% to 99%.
+/// Below 50% an NFT bought from the pair could be sold straight back to it at a profit.
+pub const MIN_CONSTANT_PRODUCT_WEIGHT_BPS: u64 = 5000;
+pub const MAX_CONSTANT_PRODUCT_WEIGHT_BPS: u64 = 9900;
+
+/// The maximum deviation of the spot price from a price oracle, 10000 bps (100%)
+pub const MAX_ORACLE_DEVIATION_BPS: u64 = 10000;
+
+/// The default number of NFTs withdrawn by WithdrawAnyNfts
+pub const DEFAULT_WITHDRAW_NFTS_LIMIT: u32 = 30;
+
+/// The maximum number of NFTs withdrawn by WithdrawAnyNfts, to prevent running out of gas
+pub const MAX_WITHDRAW_NFTS_LIMIT: u32 = 100;
+
+/// The maximum number of NFTs deposited by a single DepositNfts, to prevent running out of gas
+pub const MAX_DEPOSIT_NFTS_LIMIT: u32 = 100;
+
 /// Top level storage key. Values must not conflict.
 /// Each key is only one byte long to ensure we use the smallest possible storage keys.
 #[repr(u8)]
@@ -10,6 +36,8 @@ pub enum TopKey {
     PairImmutable = b'P',
     PairConfig = b'C',
     PairInternal = b'I',
+    PendingNftWithdrawals = b'W',
+    NftWithdrawal = b'F',
 }
 
 impl TopKey {
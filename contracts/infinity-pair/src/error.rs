@@ -1,5 +1,6 @@
 use cosmwasm_std::{
-    CheckedFromRatioError, CheckedMultiplyFractionError, DivideByZeroError, OverflowError, StdError,
+    CheckedFromRatioError, CheckedMultiplyFractionError, ConversionOverflowError,
+    DecimalRangeExceeded, DivideByZeroError, OverflowError, StdError,
 };
 use cw_utils::PaymentError;
 use infinity_shared::InfinityError;
@@ -18,6 +19,12 @@ pub enum ContractError {
     #[error("{0}")]
     CheckedFromRatioError(#[from] CheckedFromRatioError),
 
+    #[error("{0}")]
+    ConversionOverflowError(#[from] ConversionOverflowError),
+
+    #[error("{0}")]
+    DecimalRangeExceeded(#[from] DecimalRangeExceeded),
+
     #[error("{0}")]
     DivideByZeroError(#[from] DivideByZeroError),
 
@@ -39,6 +46,38 @@ pub enum ContractError {
     #[error("InvalidPair: {0}")]
     InvalidPair(String),
 
+    #[error("InvalidPair: pair is inactive")]
+    PairInactive,
+
+    #[error("InvalidPair: pair cannot produce quote")]
+    PairCannotProduceQuote,
+
+    #[error("InvalidPair: pair does not have any NFTs")]
+    PairHasNoNfts,
+
+    #[error("InvalidPair: opposite swaps within the same block are not allowed")]
+    OppositeSwapsInSameBlock,
+
+    #[error("InvalidPair: pair config update cooldown is active")]
+    ConfigUpdateCooldownActive,
+
+    #[error(
+        "InvalidInput: constant product pairs must be trade pairs holding both nfts and tokens"
+    )]
+    ConstantProductRequiresBothAssets,
+
+    #[error("InvalidInput: cannot convert to a token pair while holding nfts")]
+    TokenPairCannotHoldNfts,
+
+    #[error("InvalidInput: cannot convert to an nft pair while holding tokens")]
+    NftPairCannotHoldTokens,
+
+    #[error("InvalidInput: cannot deposit nfts into a token pair")]
+    CannotDepositNftsIntoTokenPool,
+
     #[error("InvalidPairQuote: {0}")]
     InvalidPairQuote(String),
+
+    #[error("SwapError: {0}")]
+    SwapError(String),
 }
@@ -1,6 +1,6 @@
 use crate::{pair::Pair, state::QuoteSummary};
 
-use cosmwasm_std::{attr, Addr, Coin, Event};
+use cosmwasm_std::{attr, Addr, Coin, Decimal, Event, Uint128};
 use std::vec;
 
 pub struct CreatePairEvent<'a> {
@@ -17,11 +17,19 @@ impl<'a> From<CreatePairEvent<'a>> for Event {
             "swap_fee_percent",
             "reinvest_tokens",
             "reinvest_nfts",
+            "max_nfts",
             "bonding_curve",
             "spot_price",
             "delta",
             "is_active",
             "asset_recipient",
+            "token_recipient",
+            "nft_recipient",
+            "auto_activate",
+            "anti_sandwich",
+            "allow_buy",
+            "allow_sell",
+            "created_at",
         ]))
     }
 }
@@ -38,11 +46,18 @@ impl<'a> From<UpdatePairEvent<'a>> for Event {
             "swap_fee_percent",
             "reinvest_tokens",
             "reinvest_nfts",
+            "max_nfts",
             "bonding_curve",
             "spot_price",
             "delta",
             "is_active",
             "asset_recipient",
+            "token_recipient",
+            "nft_recipient",
+            "auto_activate",
+            "anti_sandwich",
+            "allow_buy",
+            "allow_sell",
         ]))
     }
 }
@@ -68,7 +83,10 @@ pub struct TokenTransferEvent<'a> {
 
 impl<'a> From<TokenTransferEvent<'a>> for Event {
     fn from(tte: TokenTransferEvent) -> Self {
-        Event::new(tte.ty.to_string()).add_attribute("funds", tte.funds.to_string())
+        Event::new(tte.ty.to_string()).add_attributes(vec![
+            attr("denom", tte.funds.denom.to_string()),
+            attr("funds", tte.funds.to_string()),
+        ])
     }
 }
 
@@ -78,12 +96,14 @@ pub struct SwapEvent<'a> {
     pub token_id: &'a str,
     pub sender_recipient: &'a Addr,
     pub quote_summary: &'a QuoteSummary,
+    // The sale amount the quote summary fees were derived from
+    pub sale_amount: Uint128,
 }
 
 impl<'a> From<SwapEvent<'a>> for Event {
     fn from(se: SwapEvent) -> Self {
         let mut event = Event::new(se.ty.to_string())
-            .add_attributes(se.pair.get_event_attrs(vec!["spot_price", "is_active"]));
+            .add_attributes(se.pair.get_event_attrs(vec!["denom", "spot_price", "is_active"]));
 
         event = event.add_attributes(vec![
             attr("token_id", se.token_id),
@@ -92,8 +112,18 @@ impl<'a> From<SwapEvent<'a>> for Event {
             attr("seller_amount", se.quote_summary.seller_amount),
         ]);
 
+        if let Some(new_spot_price) = se.pair.spot_price() {
+            event = event.add_attribute("new_spot_price", new_spot_price);
+        }
         if let Some(royalty) = se.quote_summary.royalty.as_ref() {
-            event = event.add_attribute("royalty_fee", royalty.amount);
+            // The percent is derived from the royalty that was paid out, so that it always
+            // matches the quote summary, even when the royalty settings have since changed
+            let royalty_fee_percent =
+                Decimal::checked_from_ratio(royalty.amount, se.sale_amount).unwrap_or_default();
+            event = event.add_attributes(vec![
+                attr("royalty_fee", royalty.amount),
+                attr("royalty_fee_percent", royalty_fee_percent.to_string()),
+            ]);
         }
         if let Some(swap) = se.quote_summary.swap.as_ref() {
             event = event.add_attribute("swap_fee", swap.amount);
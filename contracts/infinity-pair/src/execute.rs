@@ -1,18 +1,33 @@
+use crate::constants::{
+    DEFAULT_WITHDRAW_NFTS_LIMIT, MAX_DEPOSIT_NFTS_LIMIT, MAX_WITHDRAW_NFTS_LIMIT,
+};
 use crate::error::ContractError;
 use crate::events::{
     NftTransferEvent, PairInternalEvent, SwapEvent, TokenTransferEvent, UpdatePairEvent,
 };
-use crate::helpers::{load_pair, load_payout_context, only_active, only_pair_owner};
-use crate::msg::ExecuteMsg;
+use crate::helpers::{
+    has_pending_nft_withdrawals, load_pair, load_payout_context, only_active, only_matching_trait,
+    only_pair_owner, only_pair_owner_or_factory, only_unfrozen_collection, only_within_oracle_band,
+    validate_bonding_curve, validate_pair_type_conversion, validate_trade_directions,
+    PayoutContext,
+};
+use crate::msg::{ExecuteMsg, NftDepositsResponse, QueryMsg, TransactionType};
 use crate::pair::Pair;
-use crate::state::{BondingCurve, PairType, INFINITY_GLOBAL, NFT_DEPOSITS};
+use crate::state::{
+    BondingCurve, NftWithdrawal, PairType, PendingNftWithdrawal, INFINITY_GLOBAL, NFT_DEPOSITS,
+    NFT_WITHDRAWAL, PENDING_NFT_WITHDRAWALS,
+};
 
 use cosmwasm_std::{
-    coin, ensure, ensure_eq, has_coins, Addr, Coin, DepsMut, Env, MessageInfo, Order, StdResult,
+    coin, ensure, ensure_eq, has_coins, to_binary, Addr, Coin, DepsMut, Env, Event, MessageInfo,
+    Order, StdResult, SubMsg, Uint128, WasmMsg,
 };
-use cw721::{Cw721QueryMsg, TokensResponse};
+use cw721::{Cw721ExecuteMsg, Cw721QueryMsg, TokensResponse};
 use cw_utils::{maybe_addr, must_pay, nonpayable};
+use infinity_index::msg::{QueryMsg as InfinityIndexQueryMsg, QuoteSide};
+use infinity_index::state::PairQuote;
 use infinity_shared::{only_nft_owner, InfinityError};
+use sg_index_query::QueryOptions;
 use sg_marketplace_common::address::address_or;
 use sg_marketplace_common::coin::transfer_coins;
 use sg_marketplace_common::nft::transfer_nft;
@@ -30,7 +45,27 @@ pub fn execute(
 ) -> Result<Response, ContractError> {
     let pair = load_pair(&env.contract.address, deps.storage, &deps.querier)?;
 
-    let (mut pair, mut response) = handle_execute_msg(deps.branch(), env, info, msg, pair)?;
+    // The sale amount of the swap is captured before the quote summaries are updated
+    let (is_deposit, swap_volume) = match &msg {
+        ExecuteMsg::DepositNfts {
+            ..
+        }
+        | ExecuteMsg::DepositTokens {}
+        | ExecuteMsg::CompoundPair {
+            ..
+        } => (true, None),
+        ExecuteMsg::SwapNftForTokens {
+            ..
+        } => (false, pair.internal.sell_to_pair_quote_summary.as_ref().map(|qs| qs.total())),
+        ExecuteMsg::SwapTokensForSpecificNft {
+            ..
+        }
+        | ExecuteMsg::SwapTokensForAnyNft {
+            ..
+        } => (false, pair.internal.buy_from_pair_quote_summary.as_ref().map(|qs| qs.total())),
+        _ => (false, None),
+    };
+    let is_swap = swap_volume.is_some();
 
     let infinity_global = INFINITY_GLOBAL.load(deps.storage)?;
     let payout_context = load_payout_context(
@@ -40,7 +75,31 @@ pub fn execute(
         &pair.immutable.denom,
     )?;
 
-    response = pair.save_and_update_indices(deps.storage, &payout_context, response)?;
+    let (mut pair, mut response) =
+        handle_execute_msg(deps.branch(), env, info, msg, pair, &payout_context)?;
+
+    // Deposits may reactivate a pair, swaps may deactivate it
+    if pair.config.auto_activate
+        && ((is_deposit && !pair.config.is_active) || (is_swap && pair.config.is_active))
+    {
+        pair.config.is_active = pair.is_quotable(&payout_context);
+    }
+
+    // NFT withdrawals update activation and the index from their final reply,
+    // once failed transfers are restored
+    if has_pending_nft_withdrawals(deps.storage) {
+        pair.save(deps.storage, &payout_context)?;
+    } else {
+        response = pair.save_and_update_indices(deps.storage, &payout_context, response)?;
+    }
+
+    if let Some(volume) = swap_volume {
+        response = pair.update_collection_stats(
+            &payout_context.global_config.infinity_index,
+            volume,
+            response,
+        );
+    }
 
     response = response.add_event(
         PairInternalEvent {
@@ -58,6 +117,7 @@ pub fn handle_execute_msg(
     info: MessageInfo,
     msg: ExecuteMsg,
     pair: Pair,
+    payout_context: &PayoutContext,
 ) -> Result<(Pair, Response), ContractError> {
     let api = deps.api;
 
@@ -67,13 +127,26 @@ pub fn handle_execute_msg(
             token_ids,
         } => {
             nonpayable(&info)?;
-            only_pair_owner(&info, &pair)?;
-            execute_deposit_nfts(deps, info, env, pair, api.addr_validate(&collection)?, token_ids)
+            only_pair_owner_or_factory(
+                &info,
+                &pair,
+                &payout_context.global_config.infinity_factory,
+            )?;
+            execute_deposit_nfts(
+                deps,
+                info,
+                env,
+                pair,
+                api.addr_validate(&collection)?,
+                token_ids,
+                payout_context,
+            )
         },
         ExecuteMsg::WithdrawNfts {
             collection,
             token_ids,
             asset_recipient,
+            keep_active,
         } => {
             nonpayable(&info)?;
             only_pair_owner(&info, &pair)?;
@@ -84,6 +157,8 @@ pub fn handle_execute_msg(
                 api.addr_validate(&collection)?,
                 token_ids,
                 maybe_addr(api, asset_recipient)?,
+                keep_active.unwrap_or(false),
+                false,
             )
         },
         ExecuteMsg::WithdrawAnyNfts {
@@ -104,7 +179,11 @@ pub fn handle_execute_msg(
             )
         },
         ExecuteMsg::DepositTokens {} => {
-            only_pair_owner(&info, &pair)?;
+            only_pair_owner_or_factory(
+                &info,
+                &pair,
+                &payout_context.global_config.infinity_factory,
+            )?;
             execute_deposit_tokens(deps, info, env, pair)
         },
         ExecuteMsg::WithdrawTokens {
@@ -122,11 +201,32 @@ pub fn handle_execute_msg(
             only_pair_owner(&info, &pair)?;
             execute_withdraw_all_tokens(deps, info, env, pair, maybe_addr(api, asset_recipient)?)
         },
+        ExecuteMsg::CloseAndWithdraw {
+            nft_limit,
+            asset_recipient,
+        } => {
+            nonpayable(&info)?;
+            only_pair_owner(&info, &pair)?;
+            execute_close_and_withdraw(
+                deps,
+                info,
+                env,
+                pair,
+                nft_limit,
+                maybe_addr(api, asset_recipient)?,
+            )
+        },
         ExecuteMsg::UpdatePairConfig {
             is_active,
             pair_type,
             bonding_curve,
             asset_recipient,
+            auto_activate,
+            anti_sandwich,
+            token_recipient,
+            nft_recipient,
+            allow_buy,
+            allow_sell,
         } => {
             nonpayable(&info)?;
             only_pair_owner(&info, &pair)?;
@@ -139,6 +239,36 @@ pub fn handle_execute_msg(
                 pair_type,
                 bonding_curve,
                 maybe_addr(api, asset_recipient)?,
+                auto_activate,
+                anti_sandwich,
+                maybe_addr(api, token_recipient)?,
+                maybe_addr(api, nft_recipient)?,
+                allow_buy,
+                allow_sell,
+                payout_context,
+            )
+        },
+        ExecuteMsg::ConvertPairType {
+            pair_type,
+        } => {
+            nonpayable(&info)?;
+            only_pair_owner(&info, &pair)?;
+            execute_update_pair_config(
+                deps,
+                info,
+                env,
+                pair,
+                None,
+                Some(pair_type),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                payout_context,
             )
         },
         ExecuteMsg::SwapNftForTokens {
@@ -149,6 +279,7 @@ pub fn handle_execute_msg(
             nonpayable(&info)?;
             only_active(&pair)?;
             only_nft_owner(&deps.querier, &info, &pair.immutable.collection, &token_id)?;
+            only_within_oracle_band(&deps.querier, &pair)?;
             execute_swap_nft_for_tokens(
                 deps,
                 info,
@@ -157,6 +288,7 @@ pub fn handle_execute_msg(
                 token_id,
                 min_output,
                 maybe_addr(api, asset_recipient)?,
+                payout_context,
             )
         },
         ExecuteMsg::SwapTokensForSpecificNft {
@@ -164,6 +296,7 @@ pub fn handle_execute_msg(
             asset_recipient,
         } => {
             only_active(&pair)?;
+            only_within_oracle_band(&deps.querier, &pair)?;
             execute_swap_tokens_for_specific_nft(
                 deps,
                 info,
@@ -171,20 +304,30 @@ pub fn handle_execute_msg(
                 pair,
                 token_id,
                 maybe_addr(api, asset_recipient)?,
+                payout_context,
             )
         },
         ExecuteMsg::SwapTokensForAnyNft {
             asset_recipient,
         } => {
             only_active(&pair)?;
+            only_within_oracle_band(&deps.querier, &pair)?;
             execute_swap_tokens_for_any_nft(
                 deps,
                 info,
                 env,
                 pair,
                 maybe_addr(api, asset_recipient)?,
+                payout_context,
             )
         },
+        ExecuteMsg::CompoundPair {
+            max_buys,
+        } => {
+            nonpayable(&info)?;
+            only_pair_owner(&info, &pair)?;
+            execute_compound_pair(deps, env, pair, max_buys, payout_context)
+        },
     }
 }
 
@@ -195,21 +338,79 @@ pub fn execute_deposit_nfts(
     mut pair: Pair,
     collection: Addr,
     token_ids: Vec<String>,
+    payout_context: &PayoutContext,
 ) -> Result<(Pair, Response), ContractError> {
     ensure_eq!(
         &collection,
         &pair.immutable.collection,
         InfinityError::InvalidInput("invalid collection".to_string())
     );
+    ensure!(
+        !matches!(pair.config.pair_type, PairType::Token),
+        ContractError::CannotDepositNftsIntoTokenPool
+    );
     ensure!(
         !token_ids.is_empty(),
         InfinityError::InvalidInput("token_ids should not be empty".to_string())
     );
+    ensure!(
+        token_ids.len() <= MAX_DEPOSIT_NFTS_LIMIT as usize,
+        InfinityError::InvalidInput(format!(
+            "cannot deposit more than {} nfts at once",
+            MAX_DEPOSIT_NFTS_LIMIT
+        ))
+    );
 
-    let mut response = Response::new();
+    // Duplicates and NFTs already held by the pair would inflate total_nfts,
+    // so every offending token id is reported before anything is transferred
+    let mut duplicate_token_ids: Vec<String> = vec![];
+    for (idx, token_id) in token_ids.iter().enumerate() {
+        if token_ids[..idx].contains(token_id) && !duplicate_token_ids.contains(token_id) {
+            duplicate_token_ids.push(token_id.clone());
+        }
+    }
+    ensure!(
+        duplicate_token_ids.is_empty(),
+        InfinityError::InvalidInput(format!(
+            "duplicate token_ids: {}",
+            duplicate_token_ids.join(", ")
+        ))
+    );
+
+    let deposited_token_ids = token_ids
+        .iter()
+        .filter(|token_id| NFT_DEPOSITS.has(deps.storage, token_id.to_string()))
+        .cloned()
+        .collect::<Vec<String>>();
+    ensure!(
+        deposited_token_ids.is_empty(),
+        InfinityError::InvalidInput(format!(
+            "nfts already deposited: {}",
+            deposited_token_ids.join(", ")
+        ))
+    );
+
+    if let Some(max_nfts_per_pair) = payout_context.global_config.max_nfts_per_pair {
+        ensure!(
+            pair.internal.total_nfts + token_ids.len() as u64 <= max_nfts_per_pair,
+            InfinityError::InvalidInput(format!(
+                "pair cannot hold more than {} nfts",
+                max_nfts_per_pair
+            ))
+        );
+    }
+
+    only_unfrozen_collection(&deps.querier, &payout_context.global_config, &collection)?;
 
+    // Validate every NFT before any transfer messages are constructed
     for token_id in &token_ids {
         only_nft_owner(&deps.querier, &info, &collection, token_id)?;
+        only_matching_trait(&deps.querier, &pair, token_id)?;
+    }
+
+    let mut response = Response::new();
+
+    for token_id in &token_ids {
         response = transfer_nft(&collection, token_id, &env.contract.address, response);
         NFT_DEPOSITS.save(deps.storage, token_id.clone(), &true)?;
     }
@@ -217,17 +418,18 @@ pub fn execute_deposit_nfts(
     pair.internal.total_nfts += token_ids.len() as u64;
 
     response = response.add_event(
-        NftTransferEvent {
+        Event::from(NftTransferEvent {
             ty: "deposit-nfts",
             pair: &pair,
             token_ids: &token_ids,
-        }
-        .into(),
+        })
+        .add_attribute("count", token_ids.len().to_string()),
     );
 
     Ok((pair, response))
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn execute_withdraw_nfts(
     deps: DepsMut,
     _info: MessageInfo,
@@ -235,6 +437,8 @@ pub fn execute_withdraw_nfts(
     collection: Addr,
     token_ids: Vec<String>,
     asset_recipient: Option<Addr>,
+    keep_active: bool,
+    is_close: bool,
 ) -> Result<(Pair, Response), ContractError> {
     ensure!(
         !token_ids.is_empty(),
@@ -243,30 +447,54 @@ pub fn execute_withdraw_nfts(
 
     let mut response = Response::new();
 
-    let asset_recipient = address_or(asset_recipient.as_ref(), &pair.asset_recipient());
+    let asset_recipient = address_or(asset_recipient.as_ref(), &pair.nft_recipient());
 
-    for token_id in &token_ids {
-        response = transfer_nft(&collection, token_id, &asset_recipient, response);
+    // Each transfer is replied to, so an NFT that cannot be transferred stays in the pair
+    // without reverting the rest of the withdrawal
+    for (idx, token_id) in token_ids.iter().enumerate() {
+        let reply_id = idx as u64;
+        response = response.add_submessage(SubMsg::reply_always(
+            WasmMsg::Execute {
+                contract_addr: collection.to_string(),
+                msg: to_binary(&Cw721ExecuteMsg::TransferNft {
+                    recipient: asset_recipient.to_string(),
+                    token_id: token_id.to_string(),
+                })?,
+                funds: vec![],
+            },
+            reply_id,
+        ));
 
-        if collection == pair.immutable.collection
-            && NFT_DEPOSITS.has(deps.storage, token_id.to_string())
-        {
+        let is_deposit = collection == pair.immutable.collection
+            && NFT_DEPOSITS.has(deps.storage, token_id.to_string());
+        if is_deposit {
             pair.internal.total_nfts -= 1u64;
             NFT_DEPOSITS.remove(deps.storage, token_id.to_string());
         }
-    }
 
-    if collection == pair.immutable.collection {
-        response = response.add_event(
-            NftTransferEvent {
-                ty: "withdraw-nfts",
-                pair: &pair,
-                token_ids: &token_ids,
-            }
-            .into(),
-        );
+        PENDING_NFT_WITHDRAWALS.save(
+            deps.storage,
+            reply_id,
+            &PendingNftWithdrawal {
+                collection: collection.clone(),
+                token_id: token_id.to_string(),
+                is_deposit,
+            },
+        )?;
     }
 
+    // Activation, counts and events are settled by the final reply, from the NFTs that transferred
+    NFT_WITHDRAWAL.save(
+        deps.storage,
+        &NftWithdrawal {
+            collection,
+            keep_active,
+            is_close,
+            withdrawn_token_ids: vec![],
+            failed_token_ids: vec![],
+        },
+    )?;
+
     Ok((pair, response))
 }
 
@@ -276,9 +504,11 @@ pub fn execute_withdraw_any_nfts(
     info: MessageInfo,
     pair: Pair,
     collection: Addr,
-    limit: u32,
+    limit: Option<u32>,
     asset_recipient: Option<Addr>,
 ) -> Result<(Pair, Response), ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_WITHDRAW_NFTS_LIMIT).min(MAX_WITHDRAW_NFTS_LIMIT);
+
     let token_ids = deps
         .querier
         .query_wasm_smart::<TokensResponse>(
@@ -291,7 +521,7 @@ pub fn execute_withdraw_any_nfts(
         )?
         .tokens;
 
-    execute_withdraw_nfts(deps, info, pair, collection, token_ids, asset_recipient)
+    execute_withdraw_nfts(deps, info, pair, collection, token_ids, asset_recipient, false, false)
 }
 
 pub fn execute_deposit_tokens(
@@ -337,7 +567,7 @@ pub fn execute_withdraw_tokens(
         }
     }
 
-    let asset_recipient = address_or(asset_recipient.as_ref(), &pair.asset_recipient());
+    let asset_recipient = address_or(asset_recipient.as_ref(), &pair.token_recipient());
 
     response = transfer_coins(funds, &asset_recipient, response);
 
@@ -355,35 +585,168 @@ pub fn execute_withdraw_all_tokens(
     execute_withdraw_tokens(deps, info, env, pair, all_tokens, asset_recipient)
 }
 
+pub fn execute_close_and_withdraw(
+    mut deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    mut pair: Pair,
+    nft_limit: Option<u32>,
+    asset_recipient: Option<Addr>,
+) -> Result<(Pair, Response), ContractError> {
+    pair.config.is_active = false;
+
+    let mut response = Response::new();
+
+    // Tokens are sent before the NFT transfers, so that the final withdrawal reply
+    // indexes the pair with its remaining token balance
+    let all_tokens = deps.querier.query_all_balances(&env.contract.address)?;
+    if !all_tokens.is_empty() {
+        let (next_pair, withdraw_response) = execute_withdraw_tokens(
+            deps.branch(),
+            info.clone(),
+            env,
+            pair,
+            all_tokens,
+            asset_recipient.clone(),
+        )?;
+        pair = next_pair;
+        response = response
+            .add_submessages(withdraw_response.messages)
+            .add_events(withdraw_response.events);
+    }
+
+    let nft_limit = nft_limit.unwrap_or(DEFAULT_WITHDRAW_NFTS_LIMIT).min(MAX_WITHDRAW_NFTS_LIMIT);
+    let token_ids = NFT_DEPOSITS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .take(nft_limit as usize)
+        .collect::<StdResult<Vec<_>>>()?;
+    let has_nft_withdrawal = !token_ids.is_empty();
+
+    if has_nft_withdrawal {
+        let collection = pair.immutable.collection.clone();
+        let (next_pair, withdraw_response) = execute_withdraw_nfts(
+            deps,
+            info,
+            pair,
+            collection,
+            token_ids,
+            asset_recipient,
+            false,
+            true,
+        )?;
+        pair = next_pair;
+        response = response
+            .add_submessages(withdraw_response.messages)
+            .add_events(withdraw_response.events);
+    }
+
+    // When NFTs are withdrawn the final reply reports the close, once failed transfers are restored
+    if !has_nft_withdrawal {
+        response = response.add_event(
+            Event::new("close-and-withdraw")
+                .add_attribute("num_withdrawn", "0")
+                .add_attribute("remaining_nft_count", pair.internal.total_nfts.to_string()),
+        );
+    }
+
+    Ok((pair, response))
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn execute_update_pair_config(
     _deps: DepsMut,
     info: MessageInfo,
-    _env: Env,
+    env: Env,
     mut pair: Pair,
     is_active: Option<bool>,
     pair_type: Option<PairType>,
     bonding_curve: Option<BondingCurve>,
     asset_recipient: Option<Addr>,
+    auto_activate: Option<bool>,
+    anti_sandwich: Option<bool>,
+    token_recipient: Option<Addr>,
+    nft_recipient: Option<Addr>,
+    allow_buy: Option<bool>,
+    allow_sell: Option<bool>,
+    payout_context: &PayoutContext,
 ) -> Result<(Pair, Response), ContractError> {
     nonpayable(&info)?;
 
+    // Deactivating the pair is not subject to the cooldown, so that a mispriced pair
+    // can always be pulled by its owner
+    let is_deactivation_only = is_active == Some(false)
+        && pair_type.is_none()
+        && bonding_curve.is_none()
+        && asset_recipient.is_none()
+        && auto_activate.is_none()
+        && anti_sandwich.is_none()
+        && token_recipient.is_none()
+        && nft_recipient.is_none()
+        && allow_buy.is_none()
+        && allow_sell.is_none();
+
+    if !is_deactivation_only {
+        let cooldown = payout_context.global_config.pair_config_update_cooldown_seconds;
+        if let (Some(cooldown), Some(last_config_update)) =
+            (cooldown.filter(|c| *c > 0), pair.internal.last_config_update)
+        {
+            ensure!(
+                env.block.time >= last_config_update.plus_seconds(cooldown),
+                ContractError::ConfigUpdateCooldownActive
+            );
+        }
+        pair.internal.last_config_update = Some(env.block.time);
+    }
+
     if let Some(is_active) = is_active {
         pair.config.is_active = is_active;
     }
 
+    // The pair type and bonding curve are validated together, against the final config
+    let is_pair_type_or_curve_update = pair_type.is_some() || bonding_curve.is_some();
+
     if let Some(pair_type) = pair_type {
         pair.config.pair_type = pair_type;
     }
 
     if let Some(bonding_curve) = bonding_curve {
+        validate_bonding_curve(&bonding_curve)?;
         pair.config.bonding_curve = bonding_curve;
     }
 
+    if is_pair_type_or_curve_update {
+        validate_pair_type_conversion(&pair, &pair.config.pair_type)?;
+    }
+
     if let Some(asset_recipient) = asset_recipient {
         pair.config.asset_recipient = Some(asset_recipient);
     }
 
+    if let Some(auto_activate) = auto_activate {
+        pair.config.auto_activate = auto_activate;
+    }
+
+    if let Some(anti_sandwich) = anti_sandwich {
+        pair.config.anti_sandwich = anti_sandwich;
+    }
+
+    if let Some(token_recipient) = token_recipient {
+        pair.config.token_recipient = Some(token_recipient);
+    }
+
+    if let Some(nft_recipient) = nft_recipient {
+        pair.config.nft_recipient = Some(nft_recipient);
+    }
+
+    if let Some(allow_buy) = allow_buy {
+        pair.config.allow_buy = allow_buy;
+    }
+
+    if let Some(allow_sell) = allow_sell {
+        pair.config.allow_sell = allow_sell;
+    }
+    validate_trade_directions(&pair.config)?;
+
     let response = Response::new().add_event(
         UpdatePairEvent {
             ty: "update-pair",
@@ -404,12 +767,26 @@ pub fn execute_swap_nft_for_tokens(
     token_id: String,
     min_output: Coin,
     asset_recipient: Option<Addr>,
+    payout_context: &PayoutContext,
 ) -> Result<(Pair, Response), ContractError> {
     let quote_summary = pair
         .internal
         .sell_to_pair_quote_summary
         .clone()
-        .ok_or(ContractError::InvalidPair("pair cannot produce quote".to_string()))?;
+        .ok_or(ContractError::PairCannotProduceQuote)?;
+
+    // The quote may predate a lower global max NFTs per pair, so the cap is checked again
+    if let Some(max_nfts_per_pair) = payout_context.global_config.max_nfts_per_pair {
+        ensure!(
+            !pair.reached_max_nfts_per_pair(&payout_context.global_config),
+            InfinityError::InvalidInput(format!(
+                "pair cannot hold more than {} nfts",
+                max_nfts_per_pair
+            ))
+        );
+    }
+
+    only_matching_trait(&deps.querier, &pair, &token_id)?;
 
     let seller_coin = coin(quote_summary.seller_amount.u128(), &pair.immutable.denom);
     ensure!(
@@ -421,19 +798,26 @@ pub fn execute_swap_nft_for_tokens(
 
     // Payout token fees
     let seller_recipient = address_or(asset_recipient.as_ref(), &info.sender);
-    response = quote_summary.payout(&pair.immutable.denom, &seller_recipient, response)?;
+    response = quote_summary.payout(
+        &pair.immutable.denom,
+        &seller_recipient,
+        &payout_context.global_config,
+        response,
+    )?;
 
     // Payout NFT, handle reinvest NFTs
     let nft_recipient = if pair.reinvest_nfts() {
         NFT_DEPOSITS.save(deps.storage, token_id.clone(), &true)?;
         env.contract.address
     } else {
-        pair.asset_recipient()
+        pair.nft_recipient()
     };
     response = transfer_nft(&pair.immutable.collection, &token_id, &nft_recipient, response);
 
     // Update pair state
     pair.swap_nft_for_tokens();
+    pair.record_swap(env.block.height, TransactionType::UserSubmitsNfts)?;
+    pair.record_price_snapshot(env.block.time);
 
     // Attach swap event
     response = response.add_event(
@@ -443,6 +827,7 @@ pub fn execute_swap_nft_for_tokens(
             token_id: &token_id,
             sender_recipient: &seller_recipient,
             quote_summary: &quote_summary,
+            sale_amount: quote_summary.total(),
         }
         .into(),
     );
@@ -457,14 +842,21 @@ pub fn execute_swap_tokens_for_specific_nft(
     mut pair: Pair,
     token_id: String,
     asset_recipient: Option<Addr>,
+    payout_context: &PayoutContext,
 ) -> Result<(Pair, Response), ContractError> {
+    // Reject unknown token ids before validating funds, so the error is unambiguous
+    ensure!(
+        NFT_DEPOSITS.has(deps.storage, token_id.clone()),
+        InfinityError::InvalidInput("pair does not own NFT".to_string())
+    );
+
     let received_amount = must_pay(&info, &pair.immutable.denom)?;
 
     let quote_summary = pair
         .internal
         .buy_from_pair_quote_summary
         .clone()
-        .ok_or(ContractError::InvalidPair("pair cannot produce quote".to_string()))?;
+        .ok_or(ContractError::PairCannotProduceQuote)?;
 
     let quote_total = quote_summary.total();
 
@@ -480,15 +872,16 @@ pub fn execute_swap_tokens_for_specific_nft(
     let seller_recipient = if pair.reinvest_tokens() {
         env.contract.address
     } else {
-        pair.asset_recipient()
+        pair.token_recipient()
     };
-    response = quote_summary.payout(&pair.immutable.denom, &seller_recipient, response)?;
+    response = quote_summary.payout(
+        &pair.immutable.denom,
+        &seller_recipient,
+        &payout_context.global_config,
+        response,
+    )?;
 
     // Payout NFT
-    ensure!(
-        NFT_DEPOSITS.has(deps.storage, token_id.clone()),
-        InfinityError::InvalidInput("pair does not own NFT".to_string())
-    );
     NFT_DEPOSITS.remove(deps.storage, token_id.clone());
 
     let nft_recipient = address_or(asset_recipient.as_ref(), &info.sender);
@@ -497,6 +890,8 @@ pub fn execute_swap_tokens_for_specific_nft(
     // Update pair state
     pair.total_tokens -= received_amount;
     pair.swap_tokens_for_nft();
+    pair.record_swap(env.block.height, TransactionType::UserSubmitsTokens)?;
+    pair.record_price_snapshot(env.block.time);
 
     // Attach swap event
     response = response.add_event(
@@ -506,6 +901,7 @@ pub fn execute_swap_tokens_for_specific_nft(
             token_id: &token_id,
             sender_recipient: &nft_recipient,
             quote_summary: &quote_summary,
+            sale_amount: quote_summary.seller_amount,
         }
         .into(),
     );
@@ -519,6 +915,7 @@ pub fn execute_swap_tokens_for_any_nft(
     env: Env,
     pair: Pair,
     asset_recipient: Option<Addr>,
+    payout_context: &PayoutContext,
 ) -> Result<(Pair, Response), ContractError> {
     let token_id = NFT_DEPOSITS
         .range(deps.storage, None, None, Order::Ascending)
@@ -526,7 +923,129 @@ pub fn execute_swap_tokens_for_any_nft(
         .map(|item| item.map(|(k, _)| k))
         .collect::<StdResult<Vec<String>>>()?
         .pop()
-        .ok_or(ContractError::InvalidPair("pair does not have any NFTs".to_string()))?;
+        .ok_or(ContractError::PairHasNoNfts)?;
+
+    execute_swap_tokens_for_specific_nft(
+        deps,
+        info,
+        env,
+        pair,
+        token_id,
+        asset_recipient,
+        payout_context,
+    )
+}
+
+pub fn execute_compound_pair(
+    deps: DepsMut,
+    env: Env,
+    mut pair: Pair,
+    max_buys: u32,
+    payout_context: &PayoutContext,
+) -> Result<(Pair, Response), ContractError> {
+    ensure!(
+        matches!(pair.config.pair_type, PairType::Trade { .. }),
+        InfinityError::InvalidInput("only trade pairs can compound tokens into nfts".to_string())
+    );
+    ensure!(
+        max_buys > 0 && max_buys <= MAX_DEPOSIT_NFTS_LIMIT,
+        InfinityError::InvalidInput(format!(
+            "max_buys must be between 1 and {}",
+            MAX_DEPOSIT_NFTS_LIMIT
+        ))
+    );
+
+    // The pair's own quote may be among the cheapest, so one extra quote is requested
+    let best_quotes = deps.querier.query_wasm_smart::<Vec<PairQuote>>(
+        &payout_context.global_config.infinity_index,
+        &InfinityIndexQueryMsg::BestQuotes {
+            collection: pair.immutable.collection.to_string(),
+            denom: pair.immutable.denom.clone(),
+            side: QuoteSide::BuyFromPair,
+            limit: max_buys + 1,
+        },
+    )?;
+
+    let mut response = Response::new();
+    let mut token_ids: Vec<String> = vec![];
+    let mut paid_amount = Uint128::zero();
+    for pair_quote in best_quotes {
+        let reached_max_nfts_per_pair = payout_context
+            .global_config
+            .max_nfts_per_pair
+            .map_or(false, |max_nfts_per_pair| pair.internal.total_nfts >= max_nfts_per_pair);
+        if token_ids.len() == max_buys as usize
+            || pair.reached_max_nfts()
+            || reached_max_nfts_per_pair
+        {
+            break;
+        }
+        if pair_quote.address == env.contract.address {
+            continue;
+        }
+
+        // The quote the other pair charges is the one stored with its state, not the index
+        let other_pair =
+            deps.querier.query_wasm_smart::<Pair>(&pair_quote.address, &QueryMsg::Pair {})?;
+        let quote_total = match &other_pair.internal.buy_from_pair_quote_summary {
+            Some(quote_summary) if other_pair.config.is_active => quote_summary.total(),
+            _ => continue,
+        };
+
+        // Quotes are sorted by ascending price, so the first unaffordable quote ends the walk
+        if quote_total > pair.total_tokens {
+            break;
+        }
+
+        let nft_deposits = deps.querier.query_wasm_smart::<NftDepositsResponse>(
+            &pair_quote.address,
+            &QueryMsg::NftDeposits {
+                query_options: Some(QueryOptions {
+                    limit: Some(1),
+                    descending: Some(false),
+                    min: None,
+                    max: None,
+                }),
+            },
+        )?;
+        let token_id = match nft_deposits.token_ids.into_iter().next() {
+            Some(token_id) => token_id,
+            None => continue,
+        };
+        if only_matching_trait(&deps.querier, &pair, &token_id).is_err() {
+            continue;
+        }
+
+        response = response.add_message(WasmMsg::Execute {
+            contract_addr: pair_quote.address.to_string(),
+            msg: to_binary(&ExecuteMsg::SwapTokensForSpecificNft {
+                token_id: token_id.clone(),
+                asset_recipient: None,
+            })?,
+            funds: vec![coin(quote_total.u128(), &pair.immutable.denom)],
+        });
+        NFT_DEPOSITS.save(deps.storage, token_id.clone(), &true)?;
 
-    execute_swap_tokens_for_specific_nft(deps, info, env, pair, token_id, asset_recipient)
+        pair.total_tokens -= quote_total;
+        pair.internal.total_nfts += 1u64;
+        paid_amount += quote_total;
+        token_ids.push(token_id);
+    }
+
+    ensure!(
+        !token_ids.is_empty(),
+        InfinityError::InvalidInput("no nfts could be bought with the pair tokens".to_string())
+    );
+
+    response = response.add_event(
+        Event::from(NftTransferEvent {
+            ty: "compound-pair",
+            pair: &pair,
+            token_ids: &token_ids,
+        })
+        .add_attribute("count", token_ids.len().to_string())
+        .add_attribute("paid_amount", paid_amount.to_string()),
+    );
+
+    Ok((pair, response))
 }
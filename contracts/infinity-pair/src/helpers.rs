@@ -1,15 +1,31 @@
 use crate::{
+    constants::MAX_ORACLE_DEVIATION_BPS,
+    math,
+    msg::{
+        AttributesOracleQueryMsg, CollectionFreezeQueryMsg, HasTraitResponse, PriceOracleQueryMsg,
+        PriceResponse, TransfersFrozenResponse,
+    },
     pair::Pair,
-    state::{QuoteSummary, TokenPayment, PAIR_CONFIG, PAIR_IMMUTABLE, PAIR_INTERNAL},
+    state::{
+        BondingCurve, PairConfig, PairImmutable, PairType, QuoteSummary, TokenPayment, PAIR_CONFIG,
+        PAIR_IMMUTABLE, PAIR_INTERNAL, PENDING_NFT_WITHDRAWALS,
+    },
     ContractError,
 };
 
 use cosmwasm_std::{
-    ensure_eq, Addr, Coin, Decimal, Deps, MessageInfo, QuerierWrapper, Storage, Uint128,
+    ensure, ensure_eq, Addr, Api, BlockInfo, Coin, Decimal, Deps, MessageInfo, Order,
+    QuerierWrapper, Storage, Uint128,
+};
+use infinity_global::{
+    load_collection_allowed, load_global_config, load_min_price, state::GlobalConfig,
 };
-use infinity_global::{load_global_config, load_min_price, state::GlobalConfig};
 use infinity_shared::InfinityError;
+use sg721_base::msg::{CollectionInfoResponse, QueryMsg as Sg721QueryMsg};
+use sg_marketplace_common::nft::only_tradable;
+use sg_std::Response;
 use stargaze_royalty_registry::{
+    fetch_or_set_royalties,
     msg::{QueryMsg as RoyaltyRegistryQueryMsg, RoyaltyPaymentResponse},
     state::RoyaltyEntry,
 };
@@ -24,15 +40,282 @@ pub fn only_pair_owner(info: &MessageInfo, pair: &Pair) -> Result<(), ContractEr
     Ok(())
 }
 
+/// Deposits may also be made by the factory, when it funds a pair on behalf of its owner
+pub fn only_pair_owner_or_factory(
+    info: &MessageInfo,
+    pair: &Pair,
+    infinity_factory: &Addr,
+) -> Result<(), ContractError> {
+    if info.sender == infinity_factory {
+        return Ok(());
+    }
+    only_pair_owner(info, pair)
+}
+
 pub fn only_active(pair: &Pair) -> Result<(), ContractError> {
-    ensure_eq!(
-        pair.config.is_active,
-        true,
-        ContractError::InvalidPair("pair is inactive".to_string())
+    ensure_eq!(pair.config.is_active, true, ContractError::PairInactive);
+    Ok(())
+}
+
+/// Ensures that pairs can be created for the collection and denom, returns the denom's min price
+pub fn only_supported_collection(
+    querier: &QuerierWrapper,
+    block: &BlockInfo,
+    infinity_global: &Addr,
+    collection: &Addr,
+    denom: &str,
+) -> Result<Coin, ContractError> {
+    let min_price = load_min_price(querier, infinity_global, denom)?
+        .ok_or(InfinityError::InvalidInput("denom not supported".to_string()))?;
+
+    ensure!(
+        load_collection_allowed(querier, infinity_global, collection)?,
+        InfinityError::InvalidInput("collection not allowed".to_string())
     );
+
+    only_tradable(querier, block, collection)?;
+
+    Ok(min_price)
+}
+
+/// Ensures that the NFT matches the trait filter of the pair, if one is set
+pub fn only_matching_trait(
+    querier: &QuerierWrapper,
+    pair: &Pair,
+    token_id: &str,
+) -> Result<(), ContractError> {
+    if let Some(trait_filter) = &pair.immutable.trait_filter {
+        let has_trait_response = querier
+            .query_wasm_smart::<HasTraitResponse>(
+                &trait_filter.oracle,
+                &AttributesOracleQueryMsg::HasTrait {
+                    collection: pair.immutable.collection.to_string(),
+                    token_id: token_id.to_string(),
+                    trait_type: trait_filter.trait_type.clone(),
+                    value: trait_filter.value.clone(),
+                },
+            )
+            .map_err(|err| {
+                InfinityError::InvalidInput(format!("attributes oracle query failed: {}", err))
+            })?;
+
+        ensure!(
+            has_trait_response.has_trait,
+            InfinityError::InvalidInput(format!(
+                "nft {} does not match the pair trait filter",
+                token_id
+            ))
+        );
+    }
     Ok(())
 }
 
+/// Rejects NFT deposits from a collection that reports its transfers as frozen, since the NFTs
+/// could never be withdrawn. Collections that do not support the query are not checked.
+pub fn only_unfrozen_collection(
+    querier: &QuerierWrapper,
+    global_config: &GlobalConfig<Addr>,
+    collection: &Addr,
+) -> Result<(), ContractError> {
+    if !global_config.probe_collection_freeze.unwrap_or(false) {
+        return Ok(());
+    }
+
+    let frozen = querier
+        .query_wasm_smart::<TransfersFrozenResponse>(
+            collection,
+            &CollectionFreezeQueryMsg::TransfersFrozen {},
+        )
+        .map_or(false, |response| response.frozen);
+
+    ensure!(
+        !frozen,
+        InfinityError::InvalidInput(format!("collection {} transfers are frozen", collection))
+    );
+    Ok(())
+}
+
+/// Ensures that the spot price of the pair is within the band around the oracle price,
+/// if the pair has a price oracle
+pub fn only_within_oracle_band(querier: &QuerierWrapper, pair: &Pair) -> Result<(), ContractError> {
+    let price_oracle = match &pair.immutable.price_oracle {
+        Some(price_oracle) => price_oracle,
+        None => return Ok(()),
+    };
+
+    let oracle_price = querier
+        .query_wasm_smart::<PriceResponse>(
+            &price_oracle.oracle,
+            &PriceOracleQueryMsg::Price {
+                collection: pair.immutable.collection.to_string(),
+                denom: pair.immutable.denom.clone(),
+            },
+        )
+        .map_err(|err| ContractError::SwapError(format!("price oracle query failed: {}", err)))?
+        .price;
+
+    if let Some(spot_price) = pair.spot_price() {
+        let deviation = if spot_price > oracle_price {
+            spot_price - oracle_price
+        } else {
+            oracle_price - spot_price
+        };
+        ensure!(
+            deviation <= oracle_price.mul_floor(Decimal::bps(price_oracle.max_deviation_bps)),
+            ContractError::SwapError(format!(
+                "spot price {} deviates from the oracle price {} by more than {} bps",
+                spot_price, oracle_price, price_oracle.max_deviation_bps
+            ))
+        );
+    }
+    Ok(())
+}
+
+/// Ensures that the bonding curve parameters are within bounds
+pub fn validate_bonding_curve(bonding_curve: &BondingCurve) -> Result<(), ContractError> {
+    match bonding_curve {
+        BondingCurve::Exponential {
+            delta,
+            ..
+        } => math::validate_exponential_delta(*delta)?,
+        BondingCurve::WeightedConstantProduct {
+            weight_bps,
+        } => math::validate_constant_product_weight(*weight_bps)?,
+        _ => {},
+    }
+    Ok(())
+}
+
+/// Ensures the spot price of a new pair is within the bounds set in the global config,
+/// which catches prices entered in whole coins instead of micro denoms and vice versa
+pub fn validate_spot_price(
+    bonding_curve: &BondingCurve,
+    global_config: &GlobalConfig<Addr>,
+) -> Result<(), ContractError> {
+    let spot_price = match bonding_curve {
+        BondingCurve::Linear {
+            spot_price,
+            ..
+        }
+        | BondingCurve::Exponential {
+            spot_price,
+            ..
+        } => *spot_price,
+        _ => return Ok(()),
+    };
+
+    if let Some(min_spot_price) = global_config.min_spot_price.filter(|p| !p.is_zero()) {
+        ensure!(
+            spot_price >= min_spot_price,
+            InfinityError::InvalidInput(format!(
+                "spot price must be at least {}, received {}",
+                min_spot_price, spot_price
+            ))
+        );
+    }
+
+    if let Some(max_spot_price) = global_config.max_spot_price.filter(|p| !p.is_zero()) {
+        ensure!(
+            spot_price <= max_spot_price,
+            InfinityError::InvalidInput(format!(
+                "spot price must be at most {}, received {}",
+                max_spot_price, spot_price
+            ))
+        );
+    }
+
+    Ok(())
+}
+
+/// Ensures the pair trades in at least one direction
+pub fn validate_trade_directions(pair_config: &PairConfig<Addr>) -> Result<(), ContractError> {
+    ensure!(
+        pair_config.allow_buy || pair_config.allow_sell,
+        InfinityError::InvalidInput("pair must allow buying or selling NFTs".to_string())
+    );
+    Ok(())
+}
+
+/// Ensures the pair holds the assets that the new pair type can trade with,
+/// given the pair's bonding curve
+pub fn validate_pair_type_conversion(
+    pair: &Pair,
+    pair_type: &PairType,
+) -> Result<(), ContractError> {
+    match pair_type {
+        PairType::Token => {
+            ensure!(pair.internal.total_nfts == 0, ContractError::TokenPairCannotHoldNfts)
+        },
+        PairType::Nft => {
+            ensure!(pair.total_tokens.is_zero(), ContractError::NftPairCannotHoldTokens)
+        },
+        PairType::Trade {
+            ..
+        } => {},
+    }
+
+    if pair.config.bonding_curve.constant_product_weight_bps().is_some() {
+        ensure!(
+            matches!(pair_type, PairType::Trade { .. })
+                && pair.internal.total_nfts > 0
+                && !pair.total_tokens.is_zero(),
+            ContractError::ConstantProductRequiresBothAssets
+        );
+    }
+
+    Ok(())
+}
+
+/// Validates the parameters used to create a pair, returns the validated parameters
+/// and the denom's min price
+pub fn validate_pair_params(
+    api: &dyn Api,
+    querier: &QuerierWrapper,
+    block: &BlockInfo,
+    infinity_global: &Addr,
+    pair_immutable: PairImmutable<String>,
+    pair_config: PairConfig<String>,
+) -> Result<(PairImmutable<Addr>, PairConfig<Addr>, Coin), ContractError> {
+    let pair_immutable = pair_immutable.str_to_addr(api)?;
+    let pair_config = pair_config.str_to_addr(api)?;
+    validate_bonding_curve(&pair_config.bonding_curve)?;
+    validate_trade_directions(&pair_config)?;
+
+    let global_config = load_global_config(querier, infinity_global)?;
+    validate_spot_price(&pair_config.bonding_curve, &global_config)?;
+
+    if let Some(price_oracle) = &pair_immutable.price_oracle {
+        ensure!(
+            price_oracle.max_deviation_bps <= MAX_ORACLE_DEVIATION_BPS,
+            InfinityError::InvalidInput(format!(
+                "max oracle deviation cannot exceed {} bps",
+                MAX_ORACLE_DEVIATION_BPS
+            ))
+        );
+    }
+
+    ensure!(
+        !pair_immutable.denom.trim().is_empty(),
+        InfinityError::InvalidInput("denom must not be empty".to_string())
+    );
+
+    // Only denoms with a min price in the global config are supported
+    let min_price = only_supported_collection(
+        querier,
+        block,
+        infinity_global,
+        &pair_immutable.collection,
+        &pair_immutable.denom,
+    )?;
+
+    Ok((pair_immutable, pair_config, min_price))
+}
+
+/// Whether or not NFT withdrawals of the current transaction are awaiting their reply
+pub fn has_pending_nft_withdrawals(storage: &dyn Storage) -> bool {
+    PENDING_NFT_WITHDRAWALS.keys(storage, None, None, Order::Ascending).next().is_some()
+}
+
 pub fn load_pair(
     contract: &Addr,
     storage: &dyn Storage,
@@ -54,6 +337,14 @@ pub struct PayoutContext {
 }
 
 impl PayoutContext {
+    /// The royalty fee percent applied to swaps, capped at the global max royalty fee percent
+    pub fn royalty_fee_percent(&self) -> Decimal {
+        min(
+            self.royalty_entry.as_ref().map_or(Decimal::zero(), |r| r.share),
+            self.global_config.max_royalty_fee_percent,
+        )
+    }
+
     fn _derive_quote_summary_parts(
         &self,
         pair: &Pair,
@@ -65,10 +356,7 @@ impl PayoutContext {
         };
 
         let royalty = if let Some(royalty_entry) = &self.royalty_entry {
-            let royalty_fee_percent = min(
-                self.royalty_entry.as_ref().map_or(Decimal::zero(), |r| r.share),
-                self.global_config.max_royalty_fee_percent,
-            );
+            let royalty_fee_percent = self.royalty_fee_percent();
             if royalty_fee_percent > Decimal::zero() {
                 Some(TokenPayment {
                     recipient: royalty_entry.recipient.clone(),
@@ -85,7 +373,7 @@ impl PayoutContext {
             min(pair.swap_fee_percent(), self.global_config.max_swap_fee_percent);
         let swap = if swap_fee_percent > Decimal::zero() {
             Some(TokenPayment {
-                recipient: pair.asset_recipient(),
+                recipient: pair.token_recipient(),
                 amount: sale_ammount.mul_ceil(swap_fee_percent),
             })
         } else {
@@ -144,6 +432,37 @@ impl PayoutContext {
     }
 }
 
+/// Drops a royalty entry whose recipient is not a valid address. The recipient originates from
+/// the collection, so malformed royalty info is treated as zero royalty instead of failing swaps.
+pub fn validate_royalty_entry(
+    api: &dyn Api,
+    royalty_entry: Option<RoyaltyEntry>,
+) -> Option<RoyaltyEntry> {
+    royalty_entry
+        .filter(|royalty_entry| api.addr_validate(royalty_entry.recipient.as_str()).is_ok())
+}
+
+/// Fetches the royalties of a collection from the royalty registry, registering them if needed.
+/// The registry reads royalties from the sg721 collection info, so collections that do not
+/// implement that query fall back to zero royalty instead of failing pair creation.
+pub fn fetch_or_default_royalties(
+    deps: Deps,
+    royalty_registry: &Addr,
+    collection: &Addr,
+    protocol: Option<&Addr>,
+    response: Response,
+) -> Result<(Option<RoyaltyEntry>, Response), ContractError> {
+    let has_collection_info = deps
+        .querier
+        .query_wasm_smart::<CollectionInfoResponse>(collection, &Sg721QueryMsg::CollectionInfo {})
+        .is_ok();
+    if !has_collection_info {
+        return Ok((None, response));
+    }
+
+    Ok(fetch_or_set_royalties(deps, royalty_registry, collection, protocol, response)?)
+}
+
 pub fn load_payout_context(
     deps: Deps,
     infinity_global: &Addr,
@@ -174,6 +493,7 @@ pub fn load_payout_context(
     } else {
         None
     };
+    let royalty_entry = validate_royalty_entry(deps.api, royalty_entry);
 
     Ok(PayoutContext {
         global_config,
@@ -1,5 +1,7 @@
 use crate::events::CreatePairEvent;
-use crate::helpers::PayoutContext;
+use crate::helpers::{
+    fetch_or_default_royalties, validate_pair_params, validate_royalty_entry, PayoutContext,
+};
 use crate::msg::InstantiateMsg;
 use crate::pair::Pair;
 use crate::state::INFINITY_GLOBAL;
@@ -11,12 +13,10 @@ use crate::{
 use cosmwasm_std::{ensure_eq, DepsMut, Env, MessageInfo, Uint128};
 use cw2::set_contract_version;
 use cw_utils::may_pay;
-use infinity_global::{load_global_config, load_min_price};
+use infinity_global::load_global_config;
 use infinity_shared::InfinityError;
-use sg_marketplace_common::nft::only_tradable;
 use sg_std::Response;
 use stargaze_fair_burn::append_fair_burn_msg;
-use stargaze_royalty_registry::fetch_or_set_royalties;
 
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
@@ -36,41 +36,46 @@ pub fn instantiate(
     let infinity_global = deps.api.addr_validate(&msg.infinity_global)?;
     INFINITY_GLOBAL.save(deps.storage, &infinity_global)?;
 
-    let mut pair = Pair::initialize(
-        deps.storage,
-        msg.pair_immutable.str_to_addr(deps.api)?,
-        msg.pair_config.str_to_addr(deps.api)?,
+    let (pair_immutable, pair_config, min_price) = validate_pair_params(
+        deps.api,
+        &deps.querier,
+        &env.block,
+        &infinity_global,
+        msg.pair_immutable,
+        msg.pair_config,
     )?;
 
-    let global_config = load_global_config(&deps.querier, &infinity_global)?;
-
-    let min_price = load_min_price(&deps.querier, &infinity_global, &pair.immutable.denom)?
-        .ok_or(InfinityError::InvalidInput("denom not supported".to_string()))?;
+    let mut pair = Pair::initialize(deps.storage, pair_immutable, pair_config, env.block.time)?;
 
-    only_tradable(&deps.querier, &env.block, &pair.immutable.collection)?;
+    let global_config = load_global_config(&deps.querier, &infinity_global)?;
 
-    let (royalty_entry, mut response) = fetch_or_set_royalties(
+    let (royalty_entry, mut response) = fetch_or_default_royalties(
         deps.as_ref(),
         &global_config.royalty_registry,
         &pair.immutable.collection,
         Some(&infinity_global),
         response,
     )?;
+    let royalty_entry = validate_royalty_entry(deps.api, royalty_entry);
+
+    // The factory applies the repeat creator discount to the pair creation fee
+    let pair_creation_fee = match msg.pair_creation_fee {
+        Some(pair_creation_fee) if info.sender == global_config.infinity_factory => {
+            pair_creation_fee
+        },
+        _ => global_config.pair_creation_fee.clone(),
+    };
 
     // Pay pair creation fee, handle 0 fee case
-    let received_amount = may_pay(&info, &global_config.pair_creation_fee.denom)?;
+    let received_amount = may_pay(&info, &pair_creation_fee.denom)?;
     ensure_eq!(
         received_amount,
-        global_config.pair_creation_fee.amount,
+        pair_creation_fee.amount,
         InfinityError::InvalidInput("incorrect pair creation fee".to_string())
     );
     if received_amount > Uint128::zero() {
-        response = append_fair_burn_msg(
-            &global_config.fair_burn,
-            vec![global_config.pair_creation_fee.clone()],
-            None,
-            response,
-        );
+        response =
+            append_fair_burn_msg(&global_config.fair_burn, vec![pair_creation_fee], None, response);
     }
 
     let payout_context = PayoutContext {
@@ -81,6 +86,7 @@ pub fn instantiate(
         denom: pair.immutable.denom.clone(),
     };
 
+    response = pair.register_with_index(&payout_context.global_config.infinity_index, response);
     response = pair.save_and_update_indices(deps.storage, &payout_context, response)?;
 
     response = response
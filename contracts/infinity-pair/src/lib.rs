@@ -8,6 +8,7 @@ pub mod migrate;
 pub mod msg;
 pub mod pair;
 pub mod query;
+pub mod reply;
 pub mod state;
 
 mod error;
@@ -1,6 +1,12 @@
+use crate::constants::{
+    MAX_CONSTANT_PRODUCT_WEIGHT_BPS, MAX_EXPONENTIAL_DELTA_BPS, MIN_CONSTANT_PRODUCT_WEIGHT_BPS,
+};
+use crate::state::PriceSnapshot;
 use crate::ContractError;
 
-use cosmwasm_std::{ensure, Decimal, Uint128};
+use cosmwasm_std::{ensure, Decimal, Decimal256, Timestamp, Uint128, Uint256};
+use infinity_shared::InfinityError;
+use std::cmp::{max, min};
 
 pub fn calc_linear_spot_price_user_submits_nft(
     spot_price: Uint128,
@@ -16,19 +22,85 @@ pub fn calc_linear_spot_price_user_submits_tokens(
     Ok(spot_price.checked_add(delta)?)
 }
 
+/// Ensures the exponential delta, a percentage, does not exceed `MAX_EXPONENTIAL_DELTA_BPS`.
+/// Checked when the bonding curve is set, the pricing functions accept any stored delta.
+pub fn validate_exponential_delta(delta: Decimal) -> Result<(), ContractError> {
+    ensure!(
+        delta <= Decimal::bps(MAX_EXPONENTIAL_DELTA_BPS),
+        InfinityError::InvalidInput(format!(
+            "exponential delta cannot exceed {} bps",
+            MAX_EXPONENTIAL_DELTA_BPS
+        ))
+    );
+    Ok(())
+}
+
+/// Ensures the token weight of a weighted constant product curve is within
+/// `MIN_CONSTANT_PRODUCT_WEIGHT_BPS` and `MAX_CONSTANT_PRODUCT_WEIGHT_BPS`.
+/// Checked when the bonding curve is set, the pricing functions accept any stored weight.
+pub fn validate_constant_product_weight(weight_bps: u64) -> Result<(), ContractError> {
+    ensure!(
+        (MIN_CONSTANT_PRODUCT_WEIGHT_BPS..=MAX_CONSTANT_PRODUCT_WEIGHT_BPS).contains(&weight_bps),
+        InfinityError::InvalidInput(format!(
+            "constant product weight must be between {} and {} bps",
+            MIN_CONSTANT_PRODUCT_WEIGHT_BPS, MAX_CONSTANT_PRODUCT_WEIGHT_BPS
+        ))
+    );
+    Ok(())
+}
+
+/// The fraction `nft_count * weight / (1 - weight)`. Dividing the tokens by it prices each NFT
+/// so that tokens make up `weight_bps` of the pair's value.
+fn cp_weighted_fraction(
+    nft_count: u64,
+    weight_bps: u64,
+) -> Result<(Uint128, Uint128), ContractError> {
+    Ok((
+        Uint128::from(nft_count).checked_mul(Uint128::from(weight_bps))?,
+        Uint128::from(10000u64).checked_sub(Uint128::from(weight_bps))?,
+    ))
+}
+
+/// Converts a whole token amount into a Decimal256, which holds any Uint128 amount
+fn uint_to_decimal(value: Uint128) -> Result<Decimal256, ContractError> {
+    Ok(Decimal256::from_atomics(value, 0)?)
+}
+
+/// Rounds a Decimal256 down to a whole token amount, erroring if it exceeds the Uint128 range
+fn decimal_to_uint_floor(value: Decimal256) -> Result<Uint128, ContractError> {
+    let fractional = Uint256::from(10u128.pow(Decimal256::DECIMAL_PLACES));
+    Ok(Uint128::try_from(value.atomics() / fractional)?)
+}
+
+/// Rounds a Decimal256 up to a whole token amount, erroring if it exceeds the Uint128 range
+fn decimal_to_uint_ceil(value: Decimal256) -> Result<Uint128, ContractError> {
+    let fractional = Uint256::from(10u128.pow(Decimal256::DECIMAL_PLACES));
+    let mut amount = value.atomics() / fractional;
+    if !(value.atomics() % fractional).is_zero() {
+        amount = amount.checked_add(Uint256::one())?;
+    }
+    Ok(Uint128::try_from(amount)?)
+}
+
+/// The exponential curve is computed in Decimal256 and rounded once at the end.
+/// Rounding down when the price falls and up when it rises means any non-zero delta
+/// moves the spot price by at least 1, even for very small spot prices.
 pub fn calc_exponential_spot_price_user_submits_nft(
     spot_price: Uint128,
     delta: Decimal,
 ) -> Result<Uint128, ContractError> {
-    let net_delta = Decimal::one().checked_add(delta)?;
-    Ok(spot_price.checked_div_floor(net_delta)?)
+    let net_delta = Decimal256::from(Decimal::one().checked_add(delta)?);
+    let new_spot_price = uint_to_decimal(spot_price)?.checked_div(net_delta)?;
+    decimal_to_uint_floor(new_spot_price)
 }
 
 pub fn calc_exponential_spot_price_user_submits_tokens(
     spot_price: Uint128,
     delta: Decimal,
 ) -> Result<Uint128, ContractError> {
-    Ok(spot_price.mul_ceil(Decimal::one().checked_add(delta)?))
+    let net_delta = Decimal256::from(Decimal::one().checked_add(delta)?);
+    let new_spot_price = uint_to_decimal(spot_price)?.checked_mul(net_delta)?;
+    decimal_to_uint_ceil(new_spot_price)
 }
 
 pub fn calc_linear_trade_buy_from_pair_price(
@@ -42,33 +114,84 @@ pub fn calc_exponential_trade_buy_from_pair_price(
     spot_price: Uint128,
     delta: Decimal,
 ) -> Result<Uint128, ContractError> {
-    Ok(spot_price.checked_mul_ceil(Decimal::one() + delta)?)
+    let net_delta = Decimal256::from(Decimal::one().checked_add(delta)?);
+    let buy_from_pair_price = uint_to_decimal(spot_price)?.checked_mul(net_delta)?;
+    decimal_to_uint_ceil(buy_from_pair_price)
 }
 
+/// The price of an NFT at which tokens make up `weight_bps` of the pair's value
+pub fn calc_cp_spot_price(
+    total_tokens: Uint128,
+    total_nfts: u64,
+    weight_bps: u64,
+) -> Result<Uint128, ContractError> {
+    Ok(total_tokens.checked_div_floor(cp_weighted_fraction(total_nfts, weight_bps)?)?)
+}
+
+/// The constant product price scaled by `r = (1 - weight) / weight`, rounded down.
+/// Since the weight is at least 5000 bps, `r <= 1` and the pair pays no more than the weighted
+/// invariant `total_tokens^weight * total_nfts^(1 - weight)` allows, so selling an NFT to the pair
+/// never decreases it. At 5000 bps this is `k = total_tokens * total_nfts`.
 pub fn calc_cp_trade_sell_to_pair_price(
     total_tokens: Uint128,
     total_nfts: u64,
+    weight_bps: u64,
 ) -> Result<Uint128, ContractError> {
     ensure!(
         total_nfts != 0u64,
         ContractError::InvalidPair("pair must have at least 1 NFT".to_string(),)
     );
-    let fraction = (Uint128::from(total_nfts + 1u64), Uint128::one());
+    let fraction = cp_weighted_fraction(total_nfts + 1u64, weight_bps)?;
     Ok(total_tokens.checked_div_floor(fraction)?)
 }
 
+/// The constant product price scaled by `r = (1 - weight) / weight`, rounded up.
+/// Since the weight is at least 5000 bps, `r <= 1` and the buyer pays no less than the weighted
+/// invariant `total_tokens^weight * total_nfts^(1 - weight)` requires, so buying an NFT from the
+/// pair never decreases it. At 5000 bps this is `k = total_tokens * total_nfts`.
 pub fn calc_cp_trade_buy_from_pair_price(
     total_tokens: Uint128,
     total_nfts: u64,
+    weight_bps: u64,
 ) -> Result<Uint128, ContractError> {
     ensure!(
         total_nfts > 1u64,
         ContractError::InvalidPair("pair must have greater than 1 NFT".to_string(),)
     );
-    let fraction = (Uint128::from(total_nfts - 1u64), Uint128::one());
+    let fraction = cp_weighted_fraction(total_nfts - 1u64, weight_bps)?;
     Ok(total_tokens.checked_div_ceil(fraction)?)
 }
 
+/// Calculates the time weighted average price over the window `[start, end]`.
+/// Each snapshot price is held from its timestamp until the next snapshot, or until `end`.
+pub fn calc_twap(
+    price_snapshots: &[PriceSnapshot],
+    start: Timestamp,
+    end: Timestamp,
+) -> Result<Option<Uint128>, ContractError> {
+    let mut weighted_sum = Uint128::zero();
+    let mut total_seconds = 0u64;
+
+    for (idx, snapshot) in price_snapshots.iter().enumerate() {
+        let period_start = max(snapshot.timestamp, start);
+        let period_end = price_snapshots.get(idx + 1).map_or(end, |next| min(next.timestamp, end));
+        if period_end <= period_start {
+            continue;
+        }
+
+        let seconds = period_end.seconds() - period_start.seconds();
+        weighted_sum =
+            weighted_sum.checked_add(snapshot.spot_price.checked_mul(Uint128::from(seconds))?)?;
+        total_seconds += seconds;
+    }
+
+    if total_seconds == 0u64 {
+        return Ok(price_snapshots.last().map(|snapshot| snapshot.spot_price));
+    }
+
+    Ok(Some(weighted_sum.checked_div(Uint128::from(total_seconds))?))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -129,18 +252,165 @@ mod tests {
 
     #[test]
     fn try_calc_cp_trade_prices() {
-        let result = calc_cp_trade_sell_to_pair_price(Uint128::from(250_000_000u128), 0u64);
+        let result = calc_cp_trade_sell_to_pair_price(Uint128::from(250_000_000u128), 0u64, 5000);
         assert!(result.is_err());
 
         let sell_to_pair_price =
-            calc_cp_trade_sell_to_pair_price(Uint128::from(250_000_000u128), 20u64).unwrap();
+            calc_cp_trade_sell_to_pair_price(Uint128::from(250_000_000u128), 20u64, 5000).unwrap();
         assert_eq!(sell_to_pair_price, Uint128::from(11_904_761u128));
 
-        let result = calc_cp_trade_buy_from_pair_price(Uint128::from(250_000_000u128), 1u64);
+        let result = calc_cp_trade_buy_from_pair_price(Uint128::from(250_000_000u128), 1u64, 5000);
         assert!(result.is_err());
 
         let buy_from_pair_price =
-            calc_cp_trade_buy_from_pair_price(Uint128::from(250_000_000u128), 20u64).unwrap();
+            calc_cp_trade_buy_from_pair_price(Uint128::from(250_000_000u128), 20u64, 5000).unwrap();
         assert_eq!(buy_from_pair_price, Uint128::from(13_157_895u128));
     }
+
+    #[test]
+    fn try_calc_weighted_cp_trade_prices() {
+        let total_tokens = Uint128::from(250_000_000u128);
+        let total_nfts = 20u64;
+
+        assert!(validate_constant_product_weight(5000).is_ok());
+        assert!(validate_constant_product_weight(8000).is_ok());
+        assert!(validate_constant_product_weight(0).is_err());
+        assert!(validate_constant_product_weight(2000).is_err());
+        assert!(validate_constant_product_weight(10000).is_err());
+
+        // An 80/20 pair holds 80% of its value in tokens, so it prices NFTs at a quarter
+        // of a 50/50 pair with the same reserves
+        let spot_price = calc_cp_spot_price(total_tokens, total_nfts, 5000).unwrap();
+        assert_eq!(spot_price, Uint128::from(12_500_000u128));
+        let spot_price = calc_cp_spot_price(total_tokens, total_nfts, 8000).unwrap();
+        assert_eq!(spot_price, Uint128::from(3_125_000u128));
+
+        let sell_to_pair_price =
+            calc_cp_trade_sell_to_pair_price(total_tokens, total_nfts, 8000).unwrap();
+        assert_eq!(sell_to_pair_price, Uint128::from(2_976_190u128));
+
+        let buy_from_pair_price =
+            calc_cp_trade_buy_from_pair_price(total_tokens, total_nfts, 8000).unwrap();
+        assert_eq!(buy_from_pair_price, Uint128::from(3_289_474u128));
+
+        // Weights outside the bounds are rejected when the curve is set, but pairs stored
+        // with them keep quoting
+        assert!(calc_cp_trade_sell_to_pair_price(total_tokens, total_nfts, 2000).is_ok());
+        assert!(calc_cp_trade_buy_from_pair_price(total_tokens, total_nfts, 2000).is_ok());
+
+        // Weights that cannot price an NFT error rather than panic
+        assert!(calc_cp_trade_sell_to_pair_price(total_tokens, total_nfts, 0).is_err());
+        assert!(calc_cp_trade_sell_to_pair_price(total_tokens, total_nfts, 10000).is_err());
+        assert!(calc_cp_trade_buy_from_pair_price(total_tokens, total_nfts, 10001).is_err());
+    }
+
+    #[test]
+    fn try_calc_weighted_cp_round_trips() {
+        for weight_bps in [MIN_CONSTANT_PRODUCT_WEIGHT_BPS, 8000] {
+            for (total_tokens, total_nfts) in
+                [(1_000u128, 10u64), (250_000_000u128, 20u64), (1_000_000_000_000u128, 3u64)]
+            {
+                let total_tokens = Uint128::from(total_tokens);
+                // Buying an NFT and selling it straight back never profits
+                let buy_from_pair_price =
+                    calc_cp_trade_buy_from_pair_price(total_tokens, total_nfts, weight_bps)
+                        .unwrap();
+                let sell_back_price = calc_cp_trade_sell_to_pair_price(
+                    total_tokens + buy_from_pair_price,
+                    total_nfts - 1,
+                    weight_bps,
+                )
+                .unwrap();
+                assert!(sell_back_price <= buy_from_pair_price);
+
+                // Selling an NFT and buying it straight back never profits
+                let sell_to_pair_price =
+                    calc_cp_trade_sell_to_pair_price(total_tokens, total_nfts, weight_bps).unwrap();
+                let buy_back_price = calc_cp_trade_buy_from_pair_price(
+                    total_tokens - sell_to_pair_price,
+                    total_nfts + 1,
+                    weight_bps,
+                )
+                .unwrap();
+                assert!(buy_back_price >= sell_to_pair_price);
+            }
+        }
+    }
+
+    #[test]
+    fn try_calc_weighted_cp_invariant() {
+        // The weighted invariant `total_tokens^weight * total_nfts^(1 - weight)`, in log space
+        let log_invariant = |total_tokens: Uint128, total_nfts: u64, weight_bps: u64| {
+            let weight = weight_bps as f64 / 10000f64;
+            weight * (total_tokens.u128() as f64).ln() + (1f64 - weight) * (total_nfts as f64).ln()
+        };
+
+        for weight_bps in [5000u64, 8000, 9900] {
+            let mut total_tokens = Uint128::from(250_000_000u128);
+            let mut total_nfts = 20u64;
+            let mut prev_invariant = log_invariant(total_tokens, total_nfts, weight_bps);
+
+            // Buy NFTs from the pair, then sell them back
+            for idx in 0..20 {
+                if idx < 10 {
+                    let price =
+                        calc_cp_trade_buy_from_pair_price(total_tokens, total_nfts, weight_bps)
+                            .unwrap();
+                    total_tokens += price;
+                    total_nfts -= 1;
+                } else {
+                    let price =
+                        calc_cp_trade_sell_to_pair_price(total_tokens, total_nfts, weight_bps)
+                            .unwrap();
+                    total_tokens -= price;
+                    total_nfts += 1;
+                }
+
+                let invariant = log_invariant(total_tokens, total_nfts, weight_bps);
+                assert!(invariant >= prev_invariant - 1e-12);
+                prev_invariant = invariant;
+            }
+        }
+    }
+
+    #[test]
+    fn try_calc_twap() {
+        let result = calc_twap(&[], Timestamp::from_seconds(0), Timestamp::from_seconds(100));
+        assert_eq!(result.unwrap(), None);
+
+        let price_snapshots = vec![
+            PriceSnapshot {
+                timestamp: Timestamp::from_seconds(100),
+                spot_price: Uint128::from(10_000_000u128),
+            },
+            PriceSnapshot {
+                timestamp: Timestamp::from_seconds(200),
+                spot_price: Uint128::from(20_000_000u128),
+            },
+        ];
+
+        // Window covering both snapshots equally
+        let twap =
+            calc_twap(&price_snapshots, Timestamp::from_seconds(100), Timestamp::from_seconds(300))
+                .unwrap();
+        assert_eq!(twap, Some(Uint128::from(15_000_000u128)));
+
+        // Window before the first snapshot only counts time after the first snapshot
+        let twap =
+            calc_twap(&price_snapshots, Timestamp::from_seconds(0), Timestamp::from_seconds(300))
+                .unwrap();
+        assert_eq!(twap, Some(Uint128::from(15_000_000u128)));
+
+        // Window after the last snapshot uses the last price
+        let twap =
+            calc_twap(&price_snapshots, Timestamp::from_seconds(250), Timestamp::from_seconds(300))
+                .unwrap();
+        assert_eq!(twap, Some(Uint128::from(20_000_000u128)));
+
+        // Zero length window returns the latest price
+        let twap =
+            calc_twap(&price_snapshots, Timestamp::from_seconds(300), Timestamp::from_seconds(300))
+                .unwrap();
+        assert_eq!(twap, Some(Uint128::from(20_000_000u128)));
+    }
 }
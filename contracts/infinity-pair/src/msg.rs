@@ -5,7 +5,7 @@ use crate::{
 };
 
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Coin, Uint128};
+use cosmwasm_std::{Addr, Coin, Timestamp, Uint128};
 use sg_index_query::QueryOptions;
 
 /// Defines whether the end user is buying or selling NFTs
@@ -23,11 +23,14 @@ pub struct InstantiateMsg {
     pub pair_immutable: PairImmutable<String>,
     /// The configuration object for the pair
     pub pair_config: PairConfig<String>,
+    /// The discounted pair creation fee, only honored when the pair is created by the factory
+    #[serde(default)]
+    pub pair_creation_fee: Option<Coin>,
 }
 
 #[cw_serde]
 pub enum ExecuteMsg {
-    /// Deposit NFTs into the pair
+    /// Deposit NFTs into the pair, token pairs do not accept NFT deposits
     DepositNfts {
         collection: String,
         token_ids: Vec<TokenId>,
@@ -37,11 +40,14 @@ pub enum ExecuteMsg {
         collection: String,
         token_ids: Vec<TokenId>,
         asset_recipient: Option<String>,
+        /// When true, the pair is left active after the withdrawal as long as it can still quote
+        keep_active: Option<bool>,
     },
     /// Withdraw any NFTs, from the pair
     WithdrawAnyNfts {
         collection: String,
-        limit: u32,
+        /// The max number of NFTs to withdraw, defaults to 30 and is capped at 100
+        limit: Option<u32>,
         asset_recipient: Option<String>,
     },
     /// Deposit tokens into the pair
@@ -55,12 +61,30 @@ pub enum ExecuteMsg {
     WithdrawAllTokens {
         asset_recipient: Option<String>,
     },
+    /// Deactivate the pair, withdraw up to `nft_limit` of its NFTs and all of its tokens.
+    /// Large pairs can be drained by calling this repeatedly.
+    CloseAndWithdraw {
+        /// The max number of NFTs to withdraw, defaults to 30 and is capped at 100
+        nft_limit: Option<u32>,
+        asset_recipient: Option<String>,
+    },
     /// Update the parameters of a pair
     UpdatePairConfig {
         is_active: Option<bool>,
         pair_type: Option<PairType>,
         bonding_curve: Option<BondingCurve>,
         asset_recipient: Option<String>,
+        auto_activate: Option<bool>,
+        anti_sandwich: Option<bool>,
+        token_recipient: Option<String>,
+        nft_recipient: Option<String>,
+        allow_buy: Option<bool>,
+        allow_sell: Option<bool>,
+    },
+    /// Convert the pair to a different pair type, keeping its assets and bonding curve.
+    /// The pair must hold the assets required by the new pair type.
+    ConvertPairType {
+        pair_type: PairType,
     },
     // Swap NFT for Tokens at the pair price
     SwapNftForTokens {
@@ -77,6 +101,11 @@ pub enum ExecuteMsg {
     SwapTokensForAnyNft {
         asset_recipient: Option<String>,
     },
+    /// Spend the tokens of a trade pair on the cheapest NFTs sold by other pairs of the
+    /// collection, at most one from each pair, and deposit them into the pair
+    CompoundPair {
+        max_buys: u32,
+    },
 }
 
 #[cw_serde]
@@ -84,6 +113,8 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     #[returns(Pair)]
     Pair {},
+    /// The token ids of the NFTs held by the pair, ordered lexicographically by token id.
+    /// The order is independent of deposit order, so `min` / `max` bounds can be used as cursors.
     #[returns(NftDepositsResponse)]
     NftDeposits {
         query_options: Option<QueryOptions<String>>,
@@ -96,6 +127,60 @@ pub enum QueryMsg {
     SimBuyFromPairSwaps {
         limit: u32,
     },
+    /// The amount paid by the buyer, including fees, for each of the next `count` NFTs bought
+    /// from the pair. The curve is truncated when the pair can no longer sell NFTs.
+    #[returns(Vec<Uint128>)]
+    BuyFromPairCurve {
+        count: u32,
+    },
+    /// The number of NFTs that `budget` can buy from the pair, walking the pair's bonding curve.
+    /// Each purchase is priced including fees.
+    #[returns(NftsForBudgetResponse)]
+    NftsForBudget {
+        budget: Uint128,
+    },
+    /// The spot price of the pair after `count` swaps of the given transaction type.
+    /// None if the pair cannot complete that many swaps.
+    #[returns(Option<Uint128>)]
+    SpotPriceAfterSwaps {
+        transaction_type: TransactionType,
+        count: u32,
+    },
+    #[returns(TwapResponse)]
+    Twap {
+        /// The length of the window, ending at the current block time
+        seconds: u64,
+    },
+    /// The most recent `limit` price snapshots of the pair, oldest first.
+    /// Bounded by the number of snapshots retained by the pair.
+    #[returns(Vec<(Timestamp, Uint128)>)]
+    PriceHistory {
+        limit: u32,
+    },
+    #[returns(CapabilitiesResponse)]
+    Capabilities {},
+    /// The reserves and invariant `k = total_tokens * total_nfts` of a constant product pair.
+    /// Weighted constant product pairs, other than at 5000 bps, are rejected.
+    #[returns(ConstantProductInvariantResponse)]
+    ConstantProductInvariant {},
+    /// The next quotes of a hypothetical pair, sharing the collection, denom and fees of this pair,
+    /// but with the given pair type, bonding curve and balances
+    #[returns(HypotheticalQuotesResponse)]
+    SimQuoteForHypotheticalPool {
+        pair_type: PairType,
+        bonding_curve: BondingCurve,
+        total_tokens: Uint128,
+        total_nfts: u64,
+    },
+    /// The assets withdrawn by the next `CloseAndWithdraw` call with the given `nft_limit`
+    #[returns(PreviewCloseResponse)]
+    PreviewClose {
+        /// The max number of NFTs to withdraw, defaults to 30 and is capped at 100
+        nft_limit: Option<u32>,
+    },
+    /// The resolved recipients of the assets traded into the pair
+    #[returns(AssetRecipientResponse)]
+    AssetRecipient {},
 }
 
 #[cw_serde]
@@ -110,3 +195,112 @@ pub struct QuotesResponse {
     pub sell_to_pair_quotes: Vec<Uint128>,
     pub buy_from_pair_quotes: Vec<Uint128>,
 }
+
+#[cw_serde]
+pub struct NftsForBudgetResponse {
+    /// The number of NFTs the budget can buy
+    pub num_nfts: u32,
+    /// The total amount paid for the NFTs, including fees
+    pub total_cost: Uint128,
+}
+
+#[cw_serde]
+pub struct TwapResponse {
+    pub denom: String,
+    /// The time weighted average spot price, None if the pair has never been swapped
+    pub twap: Option<Uint128>,
+}
+
+#[cw_serde]
+pub struct ConstantProductInvariantResponse {
+    pub total_tokens: Uint128,
+    pub total_nfts: u64,
+    /// The product of the reserves, which constant product swaps never decrease
+    pub k: Uint128,
+}
+
+#[cw_serde]
+pub struct AssetRecipientResponse {
+    /// The recipient of tokens paid for NFTs bought from the pair
+    pub token_recipient: Addr,
+    /// The recipient of NFTs sold into the pair
+    pub nft_recipient: Addr,
+}
+
+#[cw_serde]
+pub struct CapabilitiesResponse {
+    /// Whether or not the pair is currently accepting "sell to" trades (buying NFTs)
+    pub sell_to_pair: bool,
+    /// Whether or not the pair is currently accepting "buy from" trades (selling NFTs)
+    pub buy_from_pair: bool,
+}
+
+#[cw_serde]
+pub struct HypotheticalQuotesResponse {
+    pub denom: String,
+    /// The amount received by the seller of an NFT to the hypothetical pair, None if not quotable
+    pub sell_to_pair_quote: Option<Uint128>,
+    /// The amount paid by the buyer of an NFT from the hypothetical pair, None if not quotable
+    pub buy_from_pair_quote: Option<Uint128>,
+}
+
+#[cw_serde]
+pub struct PreviewCloseResponse {
+    /// The tokens returned to the owner
+    pub tokens_to_return: Vec<Coin>,
+    /// The token ids of the NFTs returned to the owner
+    pub nft_ids_this_batch: Vec<TokenId>,
+    /// The number of NFTs left in the pair after the call
+    pub remaining_after: u64,
+}
+
+/// The query interface of the attributes oracle used by pairs with a trait filter
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum AttributesOracleQueryMsg {
+    #[returns(HasTraitResponse)]
+    HasTrait {
+        collection: String,
+        token_id: String,
+        trait_type: String,
+        value: String,
+    },
+}
+
+#[cw_serde]
+pub struct HasTraitResponse {
+    /// Whether or not the NFT has the trait
+    pub has_trait: bool,
+}
+
+/// The query interface used to probe a collection for a transfer freeze before accepting
+/// NFT deposits. Not all collections support it.
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum CollectionFreezeQueryMsg {
+    #[returns(TransfersFrozenResponse)]
+    TransfersFrozen {},
+}
+
+#[cw_serde]
+pub struct TransfersFrozenResponse {
+    /// Whether or not the collection has disabled NFT transfers
+    pub frozen: bool,
+}
+
+/// The query interface of the price oracle used by pairs with a price oracle
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum PriceOracleQueryMsg {
+    #[returns(PriceResponse)]
+    Price {
+        collection: String,
+        denom: String,
+    },
+}
+
+#[cw_serde]
+pub struct PriceResponse {
+    /// The reference price of an NFT in the collection
+    pub price: Uint128,
+}
@@ -1,14 +1,18 @@
+use crate::constants::MAX_PRICE_SNAPSHOTS;
 use crate::error::ContractError;
 use crate::helpers::PayoutContext;
 use crate::math;
 use crate::msg::TransactionType;
 use crate::state::{
-    BondingCurve, PairConfig, PairImmutable, PairInternal, PairType, QuoteSummary, PAIR_CONFIG,
-    PAIR_IMMUTABLE, PAIR_INTERNAL,
+    BondingCurve, LastSwap, PairConfig, PairImmutable, PairInternal, PairType, PriceSnapshot,
+    QuoteSummary, PAIR_CONFIG, PAIR_IMMUTABLE, PAIR_INTERNAL,
 };
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{attr, coin, to_binary, Addr, Attribute, Decimal, Storage, Uint128, WasmMsg};
+use cosmwasm_std::{
+    attr, coin, ensure, to_binary, Addr, Attribute, Decimal, Storage, Timestamp, Uint128, WasmMsg,
+};
+use infinity_global::GlobalConfig;
 use infinity_index::msg::ExecuteMsg as InfinityIndexExecuteMsg;
 use sg_marketplace_common::address::address_or;
 use sg_marketplace_common::coin::transfer_coins;
@@ -27,14 +31,34 @@ impl QuoteSummary {
         &self,
         denom: &String,
         seller_recipient: &Addr,
+        global_config: &GlobalConfig<Addr>,
         mut response: Response,
     ) -> Result<Response, ContractError> {
-        response = append_fair_burn_msg(
-            &self.fair_burn.recipient,
-            vec![coin(self.fair_burn.amount.u128(), denom)],
-            None,
-            response,
-        );
+        // When a trading fee recipient is set, the fair burn fee is split between
+        // the FairBurn contract and the recipient by the burn share percent
+        let mut burn_amount = self.fair_burn.amount;
+        if let Some(trading_fee_recipient) = &global_config.trading_fee_recipient {
+            let burn_share_percent = global_config.burn_share_percent.unwrap_or(Decimal::one());
+            burn_amount = self.fair_burn.amount.mul_ceil(burn_share_percent);
+
+            let trading_fee_amount = self.fair_burn.amount - burn_amount;
+            if !trading_fee_amount.is_zero() {
+                response = transfer_coins(
+                    vec![coin(trading_fee_amount.u128(), denom)],
+                    trading_fee_recipient,
+                    response,
+                );
+            }
+        }
+
+        if !burn_amount.is_zero() {
+            response = append_fair_burn_msg(
+                &self.fair_burn.recipient,
+                vec![coin(burn_amount.u128(), denom)],
+                None,
+                response,
+            );
+        }
 
         if let Some(royalty) = &self.royalty {
             response = transfer_coins(
@@ -72,6 +96,7 @@ impl Pair {
         storage: &mut dyn Storage,
         immutable: PairImmutable<Addr>,
         config: PairConfig<Addr>,
+        created_at: Timestamp,
     ) -> Result<Self, ContractError> {
         PAIR_IMMUTABLE.save(storage, &immutable)?;
 
@@ -82,6 +107,10 @@ impl Pair {
                 total_nfts: 0u64,
                 buy_from_pair_quote_summary: None,
                 sell_to_pair_quote_summary: None,
+                price_snapshots: vec![],
+                last_config_update: None,
+                last_swap: None,
+                created_at: Some(created_at),
             },
             Uint128::zero(),
         ))
@@ -107,21 +136,42 @@ impl Pair {
         payout_context: &PayoutContext,
         mut response: Response,
     ) -> Result<Response, ContractError> {
+        self.save(storage, payout_context)?;
+
+        response = self.update_index(&payout_context.global_config, response);
+
+        Ok(response)
+    }
+
+    /// Saves the pair without updating the infinity index
+    pub fn save(
+        &mut self,
+        storage: &mut dyn Storage,
+        payout_context: &PayoutContext,
+    ) -> Result<(), ContractError> {
         self.update_sell_to_pair_quote_summary(payout_context);
         self.update_buy_from_pair_quote_summary(payout_context);
 
         PAIR_CONFIG.save(storage, &self.config)?;
         PAIR_INTERNAL.save(storage, &self.internal)?;
 
-        response = self.update_index(&payout_context.global_config.infinity_index, response);
-
-        Ok(response)
+        Ok(())
     }
 
     pub fn asset_recipient(&self) -> Addr {
         address_or(self.config.asset_recipient.as_ref(), &self.immutable.owner)
     }
 
+    /// The recipient of tokens traded into the pair, falls back to the asset recipient
+    pub fn token_recipient(&self) -> Addr {
+        address_or(self.config.token_recipient.as_ref(), &self.asset_recipient())
+    }
+
+    /// The recipient of NFTs traded into the pair, falls back to the asset recipient
+    pub fn nft_recipient(&self) -> Addr {
+        address_or(self.config.nft_recipient.as_ref(), &self.asset_recipient())
+    }
+
     pub fn reinvest_nfts(&self) -> bool {
         match self.config.pair_type {
             PairType::Trade {
@@ -132,6 +182,26 @@ impl Pair {
         }
     }
 
+    /// Whether or not the pair holds as many reinvested NFTs as it is allowed to
+    pub fn reached_max_nfts(&self) -> bool {
+        match self.config.pair_type {
+            PairType::Trade {
+                reinvest_nfts: true,
+                max_nfts: Some(max_nfts),
+                ..
+            } => self.internal.total_nfts >= max_nfts,
+            _ => false,
+        }
+    }
+
+    /// Whether or not the pair reinvests NFTs while holding the global max NFTs per pair
+    pub fn reached_max_nfts_per_pair(&self, global_config: &GlobalConfig<Addr>) -> bool {
+        self.reinvest_nfts()
+            && global_config
+                .max_nfts_per_pair
+                .map_or(false, |max_nfts_per_pair| self.internal.total_nfts >= max_nfts_per_pair)
+    }
+
     pub fn reinvest_tokens(&self) -> bool {
         match self.config.pair_type {
             PairType::Trade {
@@ -152,6 +222,76 @@ impl Pair {
         }
     }
 
+    /// The spot price of the pair. For ConstantProduct pairs this is derived from the reserves.
+    pub fn spot_price(&self) -> Option<Uint128> {
+        match self.config.bonding_curve {
+            BondingCurve::Linear {
+                spot_price,
+                ..
+            }
+            | BondingCurve::Exponential {
+                spot_price,
+                ..
+            } => Some(spot_price),
+            BondingCurve::ConstantProduct
+            | BondingCurve::WeightedConstantProduct {
+                ..
+            } => self.config.bonding_curve.constant_product_weight_bps().and_then(|weight_bps| {
+                math::calc_cp_spot_price(self.total_tokens, self.internal.total_nfts, weight_bps)
+                    .ok()
+            }),
+        }
+    }
+
+    /// Whether or not the pair would be able to produce a quote if it were active
+    pub fn is_quotable(&self, payout_context: &PayoutContext) -> bool {
+        let mut pair = self.clone();
+        pair.config.is_active = true;
+        pair.update_sell_to_pair_quote_summary(payout_context);
+        pair.update_buy_from_pair_quote_summary(payout_context);
+
+        pair.internal.sell_to_pair_quote_summary.is_some()
+            || pair.internal.buy_from_pair_quote_summary.is_some()
+    }
+
+    /// Records the swap, rejecting an opposite direction swap within the same block
+    /// when the pair has anti sandwich protection enabled
+    pub fn record_swap(
+        &mut self,
+        height: u64,
+        transaction_type: TransactionType,
+    ) -> Result<(), ContractError> {
+        if let Some(last_swap) = &self.internal.last_swap {
+            ensure!(
+                !self.config.anti_sandwich
+                    || last_swap.height != height
+                    || last_swap.transaction_type == transaction_type,
+                ContractError::OppositeSwapsInSameBlock
+            );
+        }
+
+        self.internal.last_swap = Some(LastSwap {
+            height,
+            transaction_type,
+        });
+
+        Ok(())
+    }
+
+    pub fn record_price_snapshot(&mut self, timestamp: Timestamp) {
+        if let Some(spot_price) = self.spot_price() {
+            self.internal.price_snapshots.push(PriceSnapshot {
+                timestamp,
+                spot_price,
+            });
+        }
+
+        if self.internal.price_snapshots.len() > MAX_PRICE_SNAPSHOTS {
+            let overflow = self.internal.price_snapshots.len() - MAX_PRICE_SNAPSHOTS;
+            self.internal.price_snapshots.drain(..overflow);
+        }
+    }
+
     pub fn swap_nft_for_tokens(&mut self) {
         self.total_tokens -= self.internal.sell_to_pair_quote_summary.as_ref().unwrap().total();
 
@@ -235,12 +375,20 @@ impl Pair {
                     },
                 }
             },
-            BondingCurve::ConstantProduct => {},
+            BondingCurve::ConstantProduct
+            | BondingCurve::WeightedConstantProduct {
+                ..
+            } => {},
         };
     }
 
     pub fn update_sell_to_pair_quote_summary(&mut self, payout_context: &PayoutContext) {
-        if !self.config.is_active || self.config.pair_type == PairType::Nft {
+        if !self.config.is_active
+            || !self.config.allow_buy
+            || self.config.pair_type == PairType::Nft
+            || self.reached_max_nfts()
+            || self.reached_max_nfts_per_pair(&payout_context.global_config)
+        {
             self.internal.sell_to_pair_quote_summary = None;
             return;
         }
@@ -254,10 +402,17 @@ impl Pair {
                 spot_price,
                 ..
             } => Some(spot_price),
-            BondingCurve::ConstantProduct => {
-                math::calc_cp_trade_sell_to_pair_price(self.total_tokens, self.internal.total_nfts)
-                    .ok()
-            },
+            BondingCurve::ConstantProduct
+            | BondingCurve::WeightedConstantProduct {
+                ..
+            } => self.config.bonding_curve.constant_product_weight_bps().and_then(|weight_bps| {
+                math::calc_cp_trade_sell_to_pair_price(
+                    self.total_tokens,
+                    self.internal.total_nfts,
+                    weight_bps,
+                )
+                .ok()
+            }),
         };
 
         self.internal.sell_to_pair_quote_summary = match sale_amount_option {
@@ -270,6 +425,7 @@ impl Pair {
 
     pub fn update_buy_from_pair_quote_summary(&mut self, payout_context: &PayoutContext) {
         if !self.config.is_active
+            || !self.config.allow_sell
             || self.internal.total_nfts == 0u64
             || self.config.pair_type == PairType::Token
         {
@@ -311,11 +467,18 @@ impl Pair {
                 PairType::Trade {
                     ..
                 },
-                BondingCurve::ConstantProduct,
-            ) => {
-                math::calc_cp_trade_buy_from_pair_price(self.total_tokens, self.internal.total_nfts)
-                    .ok()
-            },
+                BondingCurve::ConstantProduct
+                | BondingCurve::WeightedConstantProduct {
+                    ..
+                },
+            ) => self.config.bonding_curve.constant_product_weight_bps().and_then(|weight_bps| {
+                math::calc_cp_trade_buy_from_pair_price(
+                    self.total_tokens,
+                    self.internal.total_nfts,
+                    weight_bps,
+                )
+                .ok()
+            }),
             _ => None,
         };
 
@@ -327,20 +490,85 @@ impl Pair {
         };
     }
 
-    fn update_index(&self, infinity_index: &Addr, response: Response) -> Response {
-        let sell_to_pair_quote =
-            self.internal.sell_to_pair_quote_summary.as_ref().map(|summary| summary.seller_amount);
+    /// The sell to pair and buy from pair quotes the index should hold for this pair
+    pub fn index_quotes(
+        &self,
+        global_config: &GlobalConfig<Addr>,
+    ) -> (Option<Uint128>, Option<Uint128>) {
+        // Pairs with a trait filter only accept some of the NFTs in the collection,
+        // so they are left out of the collection wide sell to pair index.
+        // Pairs holding less than the minimum deposit are also left out, but remain
+        // swappable directly and are indexed again once topped up.
+        let below_min_token_deposit =
+            global_config.min_token_deposit.map_or(false, |min| self.total_tokens < min);
+        let sell_to_pair_quote = match self.immutable.trait_filter {
+            Some(_) => None,
+            None if below_min_token_deposit => None,
+            None => self
+                .internal
+                .sell_to_pair_quote_summary
+                .as_ref()
+                .map(|summary| summary.seller_amount),
+        };
 
-        let buy_from_pair_quote =
-            self.internal.buy_from_pair_quote_summary.as_ref().map(|summary| summary.total());
+        let below_min_nft_count =
+            global_config.min_nft_count.map_or(false, |min| self.internal.total_nfts < min);
+        let buy_from_pair_quote = if below_min_nft_count {
+            None
+        } else {
+            self.internal.buy_from_pair_quote_summary.as_ref().map(|summary| summary.total())
+        };
+
+        (sell_to_pair_quote, buy_from_pair_quote)
+    }
+
+    fn update_index(&self, global_config: &GlobalConfig<Addr>, response: Response) -> Response {
+        let (sell_to_pair_quote, buy_from_pair_quote) = self.index_quotes(global_config);
 
         response.add_message(WasmMsg::Execute {
-            contract_addr: infinity_index.to_string(),
+            contract_addr: global_config.infinity_index.to_string(),
             msg: to_binary(&InfinityIndexExecuteMsg::UpdatePairIndices {
                 collection: self.immutable.collection.to_string(),
                 denom: self.immutable.denom.clone(),
                 sell_to_pair_quote,
                 buy_from_pair_quote,
+                total_tokens: Some(self.total_tokens),
+                total_nfts: Some(self.internal.total_nfts),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    }
+
+    /// Registers the creation of the pair with the infinity index
+    pub fn register_with_index(&self, infinity_index: &Addr, response: Response) -> Response {
+        match self.internal.created_at {
+            Some(created_at) => response.add_message(WasmMsg::Execute {
+                contract_addr: infinity_index.to_string(),
+                msg: to_binary(&InfinityIndexExecuteMsg::RegisterPair {
+                    collection: self.immutable.collection.to_string(),
+                    denom: self.immutable.denom.clone(),
+                    created_at,
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+            None => response,
+        }
+    }
+
+    pub fn update_collection_stats(
+        &self,
+        infinity_index: &Addr,
+        volume: Uint128,
+        response: Response,
+    ) -> Response {
+        response.add_message(WasmMsg::Execute {
+            contract_addr: infinity_index.to_string(),
+            msg: to_binary(&InfinityIndexExecuteMsg::UpdateCollectionStats {
+                collection: self.immutable.collection.to_string(),
+                denom: self.immutable.denom.clone(),
+                volume,
             })
             .unwrap(),
             funds: vec![],
@@ -383,6 +611,13 @@ impl Pair {
                         ..
                     } => Some(attr("reinvest_nfts", reinvest_nfts.to_string())),
                 },
+                "max_nfts" => match self.config.pair_type {
+                    PairType::Trade {
+                        max_nfts: Some(max_nfts),
+                        ..
+                    } => Some(attr("max_nfts", max_nfts.to_string())),
+                    _ => None,
+                },
                 "bonding_curve" => match self.config.bonding_curve {
                     BondingCurve::Linear {
                         ..
@@ -393,6 +628,9 @@ impl Pair {
                     BondingCurve::ConstantProduct {} => {
                         Some(attr("bonding_curve", "constant_product".to_string()))
                     },
+                    BondingCurve::WeightedConstantProduct {
+                        ..
+                    } => Some(attr("bonding_curve", "weighted_constant_product".to_string())),
                 },
                 "spot_price" => match self.config.bonding_curve {
                     BondingCurve::Linear {
@@ -405,6 +643,9 @@ impl Pair {
                     } => Some(attr("spot_price", spot_price.to_string())),
                     BondingCurve::ConstantProduct {
                         ..
+                    }
+                    | BondingCurve::WeightedConstantProduct {
+                        ..
                     } => None,
                 },
                 "delta" => match self.config.bonding_curve {
@@ -418,14 +659,39 @@ impl Pair {
                     } => Some(attr("delta", delta.to_string())),
                     BondingCurve::ConstantProduct {
                         ..
+                    }
+                    | BondingCurve::WeightedConstantProduct {
+                        ..
                     } => None,
                 },
                 "is_active" => Some(attr("is_active", self.config.is_active.to_string())),
+                "auto_activate" => {
+                    Some(attr("auto_activate", self.config.auto_activate.to_string()))
+                },
+                "anti_sandwich" => {
+                    Some(attr("anti_sandwich", self.config.anti_sandwich.to_string()))
+                },
+                "allow_buy" => Some(attr("allow_buy", self.config.allow_buy.to_string())),
+                "allow_sell" => Some(attr("allow_sell", self.config.allow_sell.to_string())),
                 "asset_recipient" => self
                     .config
                     .asset_recipient
                     .as_ref()
                     .map(|asset_recipient| attr("asset_recipient", asset_recipient.to_string())),
+                "token_recipient" => self
+                    .config
+                    .token_recipient
+                    .as_ref()
+                    .map(|token_recipient| attr("token_recipient", token_recipient.to_string())),
+                "nft_recipient" => self
+                    .config
+                    .nft_recipient
+                    .as_ref()
+                    .map(|nft_recipient| attr("nft_recipient", nft_recipient.to_string())),
+                "created_at" => self
+                    .internal
+                    .created_at
+                    .map(|created_at| attr("created_at", created_at.to_string())),
                 "total_tokens" => Some(attr("total_tokens", self.total_tokens.to_string())),
                 "total_nfts" => Some(attr("total_nfts", self.internal.total_nfts.to_string())),
                 "sell_to_pair_quote" => {
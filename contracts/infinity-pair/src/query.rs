@@ -1,11 +1,23 @@
 use crate::{
-    helpers::{load_pair, load_payout_context},
-    msg::{NftDepositsResponse, QueryMsg, QuotesResponse},
+    constants::{
+        CONSTANT_PRODUCT_WEIGHT_BPS, DEFAULT_WITHDRAW_NFTS_LIMIT, MAX_WITHDRAW_NFTS_LIMIT,
+    },
+    helpers::{
+        load_pair, load_payout_context, validate_bonding_curve, validate_pair_type_conversion,
+    },
+    math::calc_twap,
+    msg::{
+        AssetRecipientResponse, CapabilitiesResponse, ConstantProductInvariantResponse,
+        HypotheticalQuotesResponse, NftDepositsResponse, NftsForBudgetResponse,
+        PreviewCloseResponse, QueryMsg, QuotesResponse, TransactionType, TwapResponse,
+    },
     pair::Pair,
-    state::{INFINITY_GLOBAL, NFT_DEPOSITS, PAIR_IMMUTABLE},
+    state::{BondingCurve, PairType, INFINITY_GLOBAL, NFT_DEPOSITS, PAIR_IMMUTABLE, PAIR_INTERNAL},
 };
 
-use cosmwasm_std::{to_binary, Binary, Deps, Env, StdError, StdResult, Uint128};
+use cosmwasm_std::{
+    ensure, to_binary, Binary, Deps, Env, Order, StdError, StdResult, Timestamp, Uint128,
+};
 use sg_index_query::{QueryOptions, QueryOptionsInternal};
 
 #[cfg(not(feature = "library"))]
@@ -24,6 +36,43 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::SimBuyFromPairSwaps {
             limit,
         } => to_binary(&query_sim_buy_from_pair_swaps(deps, env, limit)?),
+        QueryMsg::BuyFromPairCurve {
+            count,
+        } => to_binary(&query_buy_from_pair_curve(deps, env, count)?),
+        QueryMsg::NftsForBudget {
+            budget,
+        } => to_binary(&query_nfts_for_budget(deps, env, budget)?),
+        QueryMsg::SpotPriceAfterSwaps {
+            transaction_type,
+            count,
+        } => to_binary(&query_spot_price_after_swaps(deps, env, transaction_type, count)?),
+        QueryMsg::Twap {
+            seconds,
+        } => to_binary(&query_twap(deps, env, seconds)?),
+        QueryMsg::PriceHistory {
+            limit,
+        } => to_binary(&query_price_history(deps, limit)?),
+        QueryMsg::Capabilities {} => to_binary(&query_capabilities(deps)?),
+        QueryMsg::ConstantProductInvariant {} => {
+            to_binary(&query_constant_product_invariant(deps, env)?)
+        },
+        QueryMsg::SimQuoteForHypotheticalPool {
+            pair_type,
+            bonding_curve,
+            total_tokens,
+            total_nfts,
+        } => to_binary(&query_sim_quote_for_hypothetical_pool(
+            deps,
+            env,
+            pair_type,
+            bonding_curve,
+            total_tokens,
+            total_nfts,
+        )?),
+        QueryMsg::PreviewClose {
+            nft_limit,
+        } => to_binary(&query_preview_close(deps, env, nft_limit)?),
+        QueryMsg::AssetRecipient {} => to_binary(&query_asset_recipient(deps, env)?),
     }
 }
 
@@ -149,3 +198,229 @@ pub fn query_sim_buy_from_pair_swaps(
         buy_from_pair_quotes,
     })
 }
+
+pub fn query_buy_from_pair_curve(deps: Deps, env: Env, count: u32) -> StdResult<Vec<Uint128>> {
+    Ok(query_sim_buy_from_pair_swaps(deps, env, count)?.buy_from_pair_quotes)
+}
+
+pub fn query_nfts_for_budget(
+    deps: Deps,
+    env: Env,
+    budget: Uint128,
+) -> StdResult<NftsForBudgetResponse> {
+    let mut pair = load_pair(&env.contract.address, deps.storage, &deps.querier)
+        .map_err(|_| StdError::generic_err("failed to load pair".to_string()))?;
+
+    let infinity_global = INFINITY_GLOBAL.load(deps.storage)?;
+    let payout_context = load_payout_context(
+        deps,
+        &infinity_global,
+        &pair.immutable.collection,
+        &pair.immutable.denom,
+    )
+    .map_err(|_| StdError::generic_err("failed to load payout context".to_string()))?;
+
+    pair.update_sell_to_pair_quote_summary(&payout_context);
+    pair.update_buy_from_pair_quote_summary(&payout_context);
+
+    let mut num_nfts = 0u32;
+    let mut total_cost = Uint128::zero();
+
+    while let Some(quote_summary) = &pair.internal.buy_from_pair_quote_summary {
+        let next_total_cost = total_cost + quote_summary.total();
+        if next_total_cost > budget {
+            break;
+        }
+
+        num_nfts += 1;
+        total_cost = next_total_cost;
+
+        pair.sim_swap_tokens_for_nft(&payout_context);
+    }
+
+    Ok(NftsForBudgetResponse {
+        num_nfts,
+        total_cost,
+    })
+}
+
+pub fn query_spot_price_after_swaps(
+    deps: Deps,
+    env: Env,
+    transaction_type: TransactionType,
+    count: u32,
+) -> StdResult<Option<Uint128>> {
+    let mut pair = load_pair(&env.contract.address, deps.storage, &deps.querier)
+        .map_err(|_| StdError::generic_err("failed to load pair".to_string()))?;
+
+    let infinity_global = INFINITY_GLOBAL.load(deps.storage)?;
+    let payout_context = load_payout_context(
+        deps,
+        &infinity_global,
+        &pair.immutable.collection,
+        &pair.immutable.denom,
+    )
+    .map_err(|_| StdError::generic_err("failed to load payout context".to_string()))?;
+
+    pair.update_sell_to_pair_quote_summary(&payout_context);
+    pair.update_buy_from_pair_quote_summary(&payout_context);
+
+    for _ in 0..count {
+        match transaction_type {
+            TransactionType::UserSubmitsNfts => {
+                if pair.internal.sell_to_pair_quote_summary.is_none() {
+                    return Ok(None);
+                }
+                pair.sim_swap_nft_for_tokens(&payout_context);
+            },
+            TransactionType::UserSubmitsTokens => {
+                if pair.internal.buy_from_pair_quote_summary.is_none() {
+                    return Ok(None);
+                }
+                pair.sim_swap_tokens_for_nft(&payout_context);
+            },
+        }
+    }
+
+    Ok(pair.spot_price())
+}
+
+pub fn query_twap(deps: Deps, env: Env, seconds: u64) -> StdResult<TwapResponse> {
+    let denom = PAIR_IMMUTABLE.load(deps.storage)?.denom;
+    let price_snapshots = PAIR_INTERNAL.load(deps.storage)?.price_snapshots;
+
+    let start = Timestamp::from_seconds(env.block.time.seconds().saturating_sub(seconds));
+    let twap = calc_twap(&price_snapshots, start, env.block.time)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+
+    Ok(TwapResponse {
+        denom,
+        twap,
+    })
+}
+
+pub fn query_price_history(deps: Deps, limit: u32) -> StdResult<Vec<(Timestamp, Uint128)>> {
+    let price_snapshots = PAIR_INTERNAL.load(deps.storage)?.price_snapshots;
+
+    let skip = price_snapshots.len().saturating_sub(limit as usize);
+    let price_history = price_snapshots
+        .into_iter()
+        .skip(skip)
+        .map(|snapshot| (snapshot.timestamp, snapshot.spot_price))
+        .collect();
+
+    Ok(price_history)
+}
+
+pub fn query_capabilities(deps: Deps) -> StdResult<CapabilitiesResponse> {
+    let internal = PAIR_INTERNAL.load(deps.storage)?;
+
+    Ok(CapabilitiesResponse {
+        sell_to_pair: internal.sell_to_pair_quote_summary.is_some(),
+        buy_from_pair: internal.buy_from_pair_quote_summary.is_some(),
+    })
+}
+
+pub fn query_constant_product_invariant(
+    deps: Deps,
+    env: Env,
+) -> StdResult<ConstantProductInvariantResponse> {
+    let pair = load_pair(&env.contract.address, deps.storage, &deps.querier)
+        .map_err(|_| StdError::generic_err("failed to load pair".to_string()))?;
+
+    let weight_bps = pair.config.bonding_curve.constant_product_weight_bps();
+    ensure!(
+        weight_bps.is_some(),
+        StdError::generic_err("pair does not use a constant product bonding curve")
+    );
+    // Weighted pairs preserve `total_tokens^weight * total_nfts^(1 - weight)` instead,
+    // which has no exact integer representation
+    ensure!(
+        weight_bps == Some(CONSTANT_PRODUCT_WEIGHT_BPS),
+        StdError::generic_err(
+            "the invariant of a weighted constant product pair is not the product of its reserves"
+        )
+    );
+
+    let k = pair.total_tokens.checked_mul(Uint128::from(pair.internal.total_nfts))?;
+
+    Ok(ConstantProductInvariantResponse {
+        total_tokens: pair.total_tokens,
+        total_nfts: pair.internal.total_nfts,
+        k,
+    })
+}
+
+pub fn query_sim_quote_for_hypothetical_pool(
+    deps: Deps,
+    env: Env,
+    pair_type: PairType,
+    bonding_curve: BondingCurve,
+    total_tokens: Uint128,
+    total_nfts: u64,
+) -> StdResult<HypotheticalQuotesResponse> {
+    validate_bonding_curve(&bonding_curve).map_err(|e| StdError::generic_err(e.to_string()))?;
+
+    let mut pair = load_pair(&env.contract.address, deps.storage, &deps.querier)
+        .map_err(|_| StdError::generic_err("failed to load pair".to_string()))?;
+
+    let infinity_global = INFINITY_GLOBAL.load(deps.storage)?;
+    let payout_context = load_payout_context(
+        deps,
+        &infinity_global,
+        &pair.immutable.collection,
+        &pair.immutable.denom,
+    )
+    .map_err(|_| StdError::generic_err("failed to load payout context".to_string()))?;
+
+    // The hypothetical pair only exists in memory, it is never saved
+    pair.config.pair_type = pair_type;
+    pair.config.bonding_curve = bonding_curve;
+    pair.config.is_active = true;
+    pair.internal.total_nfts = total_nfts;
+    pair.total_tokens = total_tokens;
+
+    // Pair types that cannot hold the given balances, or that the bonding curve does not
+    // support, are rejected rather than quoted
+    validate_pair_type_conversion(&pair, &pair.config.pair_type)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+
+    pair.update_sell_to_pair_quote_summary(&payout_context);
+    pair.update_buy_from_pair_quote_summary(&payout_context);
+
+    Ok(HypotheticalQuotesResponse {
+        denom: pair.immutable.denom,
+        sell_to_pair_quote: pair.internal.sell_to_pair_quote_summary.map(|qs| qs.seller_amount),
+        buy_from_pair_quote: pair.internal.buy_from_pair_quote_summary.map(|qs| qs.total()),
+    })
+}
+
+pub fn query_preview_close(
+    deps: Deps,
+    env: Env,
+    nft_limit: Option<u32>,
+) -> StdResult<PreviewCloseResponse> {
+    let total_nfts = PAIR_INTERNAL.load(deps.storage)?.total_nfts;
+
+    let nft_limit = nft_limit.unwrap_or(DEFAULT_WITHDRAW_NFTS_LIMIT).min(MAX_WITHDRAW_NFTS_LIMIT);
+    let nft_ids_this_batch = NFT_DEPOSITS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .take(nft_limit as usize)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(PreviewCloseResponse {
+        tokens_to_return: deps.querier.query_all_balances(&env.contract.address)?,
+        remaining_after: total_nfts.saturating_sub(nft_ids_this_batch.len() as u64),
+        nft_ids_this_batch,
+    })
+}
+
+pub fn query_asset_recipient(deps: Deps, env: Env) -> StdResult<AssetRecipientResponse> {
+    let pair = load_pair(&env.contract.address, deps.storage, &deps.querier)
+        .map_err(|_| StdError::generic_err("failed to load pair".to_string()))?;
+
+    Ok(AssetRecipientResponse {
+        token_recipient: pair.token_recipient(),
+        nft_recipient: pair.nft_recipient(),
+    })
+}
@@ -0,0 +1,105 @@
+use crate::events::{NftTransferEvent, PairInternalEvent};
+use crate::helpers::{has_pending_nft_withdrawals, load_pair, load_payout_context};
+use crate::state::{
+    INFINITY_GLOBAL, NFT_DEPOSITS, NFT_WITHDRAWAL, PAIR_INTERNAL, PENDING_NFT_WITHDRAWALS,
+};
+use crate::ContractError;
+
+use cosmwasm_std::{DepsMut, Env, Event, Reply, SubMsgResult};
+use infinity_shared::InfinityError;
+use sg_std::Response;
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+
+/// Handles the reply to each NFT transferred out of the pair by a withdrawal.
+/// NFTs that fail to transfer are restored to the pair, and the final reply
+/// settles the pair's activation, indices and events from the NFTs that transferred.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let pending_withdrawal = PENDING_NFT_WITHDRAWALS
+        .may_load(deps.storage, msg.id)?
+        .ok_or(InfinityError::InternalError(format!("unknown reply id: {}", msg.id)))?;
+    PENDING_NFT_WITHDRAWALS.remove(deps.storage, msg.id);
+
+    let mut nft_withdrawal = NFT_WITHDRAWAL.load(deps.storage)?;
+
+    match msg.result {
+        SubMsgResult::Ok(_) => {
+            nft_withdrawal.withdrawn_token_ids.push(pending_withdrawal.token_id);
+        },
+        SubMsgResult::Err(_) => {
+            if pending_withdrawal.is_deposit {
+                NFT_DEPOSITS.save(deps.storage, pending_withdrawal.token_id.clone(), &true)?;
+                let mut internal = PAIR_INTERNAL.load(deps.storage)?;
+                internal.total_nfts += 1u64;
+                PAIR_INTERNAL.save(deps.storage, &internal)?;
+            }
+            nft_withdrawal.failed_token_ids.push(pending_withdrawal.token_id);
+        },
+    }
+
+    if has_pending_nft_withdrawals(deps.storage) {
+        NFT_WITHDRAWAL.save(deps.storage, &nft_withdrawal)?;
+        return Ok(Response::new());
+    }
+    NFT_WITHDRAWAL.remove(deps.storage);
+
+    let mut pair = load_pair(&env.contract.address, deps.storage, &deps.querier)?;
+    let infinity_global = INFINITY_GLOBAL.load(deps.storage)?;
+    let payout_context = load_payout_context(
+        deps.as_ref(),
+        &infinity_global,
+        &pair.immutable.collection,
+        &pair.immutable.denom,
+    )?;
+
+    // NFTs restored by failed transfers act as a deposit, which may reactivate the pair
+    let is_restore = !nft_withdrawal.failed_token_ids.is_empty() && !pair.config.is_active;
+    if !nft_withdrawal.is_close
+        && (nft_withdrawal.keep_active || (pair.config.auto_activate && is_restore))
+    {
+        pair.config.is_active = pair.is_quotable(&payout_context);
+    }
+
+    let mut response =
+        pair.save_and_update_indices(deps.storage, &payout_context, Response::new())?;
+
+    let num_withdrawn = nft_withdrawal.withdrawn_token_ids.len();
+    response = response.add_attribute("num_withdrawn", num_withdrawn.to_string());
+
+    if nft_withdrawal.collection == pair.immutable.collection && num_withdrawn > 0 {
+        response = response.add_event(
+            NftTransferEvent {
+                ty: "withdraw-nfts",
+                pair: &pair,
+                token_ids: &nft_withdrawal.withdrawn_token_ids,
+            }
+            .into(),
+        );
+    }
+
+    if !nft_withdrawal.failed_token_ids.is_empty() {
+        response = response.add_event(
+            Event::new("withdraw-nfts-failed")
+                .add_attribute("failed_token_ids", nft_withdrawal.failed_token_ids.join(",")),
+        );
+    }
+
+    if nft_withdrawal.is_close {
+        response = response.add_event(
+            Event::new("close-and-withdraw")
+                .add_attribute("num_withdrawn", num_withdrawn.to_string())
+                .add_attribute("remaining_nft_count", pair.internal.total_nfts.to_string()),
+        );
+    }
+
+    response = response.add_event(
+        PairInternalEvent {
+            pair: &pair,
+        }
+        .into(),
+    );
+
+    Ok(response)
+}
@@ -1,7 +1,11 @@
-use crate::{constants::TopKey, ContractError};
+use crate::{
+    constants::{TopKey, CONSTANT_PRODUCT_WEIGHT_BPS},
+    msg::TransactionType,
+    ContractError,
+};
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Api, Decimal, Uint128};
+use cosmwasm_std::{Addr, Api, Decimal, Timestamp, Uint128};
 use cw_address_like::AddressLike;
 use cw_storage_plus::{Item, Map};
 use cw_utils::maybe_addr;
@@ -15,6 +19,34 @@ pub const INFINITY_GLOBAL: Item<Addr> = Item::new(TopKey::InfinityGlobal.as_str(
 // A map of all NFT token ids held by the pair
 pub const NFT_DEPOSITS: Map<TokenId, bool> = Map::new(TopKey::NftDeposits.as_str());
 
+/// PendingNftWithdrawal is an NFT transfer out of the pair that has not yet been replied to
+#[cw_serde]
+pub struct PendingNftWithdrawal {
+    pub collection: Addr,
+    pub token_id: TokenId,
+    /// Whether or not the NFT was removed from the pair's deposits
+    pub is_deposit: bool,
+}
+
+// A map of the NFT withdrawals of the current transaction, keyed by reply id
+pub const PENDING_NFT_WITHDRAWALS: Map<u64, PendingNftWithdrawal> =
+    Map::new(TopKey::PendingNftWithdrawals.as_str());
+
+/// NftWithdrawal is the outcome of the NFT withdrawal of the current transaction,
+/// which is settled by the final reply once every transfer has been replied to
+#[cw_serde]
+pub struct NftWithdrawal {
+    pub collection: Addr,
+    /// Whether or not the pair should remain active if it can still produce quotes
+    pub keep_active: bool,
+    /// Whether or not the withdrawal is part of closing the pair
+    pub is_close: bool,
+    pub withdrawn_token_ids: Vec<TokenId>,
+    pub failed_token_ids: Vec<TokenId>,
+}
+
+pub const NFT_WITHDRAWAL: Item<NftWithdrawal> = Item::new(TopKey::NftWithdrawal.as_str());
+
 /// PairType refers to the assets held by the pair
 /// * Token: A pair that holds fungible tokens
 /// * Nft: A pair that holds NFTs
@@ -31,6 +63,10 @@ pub enum PairType {
         reinvest_tokens: bool,
         /// Whether or not the NFTs sold into the pair will be reinvested
         reinvest_nfts: bool,
+        /// The max number of NFTs the pair holds before it stops accepting "sell to" trades,
+        /// only enforced when NFTs are reinvested
+        #[serde(default)]
+        max_nfts: Option<u64>,
     },
 }
 
@@ -38,6 +74,8 @@ pub enum PairType {
 /// * Linear: A linear curve that increments by a constant amount (delta)
 /// * Exponential: An exponential curve that increments by a percentage amount (delta)
 /// * ConstantProduct: A constant product curve that maintains a constant product of the two assets
+/// * WeightedConstantProduct: A constant product curve that prices NFTs so that a fixed share
+///   of the pair's value is held in tokens
 #[cw_serde]
 pub enum BondingCurve {
     Linear {
@@ -59,6 +97,65 @@ pub enum BondingCurve {
         delta: Decimal,
     },
     ConstantProduct,
+    WeightedConstantProduct {
+        /// The share of the pair's value held in tokens, in basis points, from 5000 to 9900.
+        /// 5000 bps is equivalent to the ConstantProduct curve.
+        weight_bps: u64,
+    },
+}
+
+impl BondingCurve {
+    /// The token weight of constant product curves in basis points, None for other curves
+    pub fn constant_product_weight_bps(&self) -> Option<u64> {
+        match self {
+            BondingCurve::ConstantProduct => Some(CONSTANT_PRODUCT_WEIGHT_BPS),
+            BondingCurve::WeightedConstantProduct {
+                weight_bps,
+            } => Some(*weight_bps),
+            _ => None,
+        }
+    }
+}
+
+/// TraitFilter restricts the NFTs accepted by a pair to those with a matching trait,
+/// as reported by an external attributes oracle
+#[cw_serde]
+pub struct TraitFilter<T: AddressLike> {
+    /// The address of the attributes oracle contract
+    pub oracle: T,
+    /// The trait type that NFTs must have, e.g. "Background"
+    pub trait_type: String,
+    /// The trait value that NFTs must have, e.g. "Gold"
+    pub value: String,
+}
+
+impl TraitFilter<String> {
+    pub fn str_to_addr(self, api: &dyn Api) -> Result<TraitFilter<Addr>, ContractError> {
+        Ok(TraitFilter {
+            oracle: api.addr_validate(&self.oracle)?,
+            trait_type: self.trait_type,
+            value: self.value,
+        })
+    }
+}
+
+/// PriceOracle anchors the spot price of a pair to the price reported by an external oracle,
+/// swaps are rejected while the spot price deviates from the oracle price by more than the band
+#[cw_serde]
+pub struct PriceOracle<T: AddressLike> {
+    /// The address of the price oracle contract
+    pub oracle: T,
+    /// The maximum deviation of the spot price from the oracle price, in basis points
+    pub max_deviation_bps: u64,
+}
+
+impl PriceOracle<String> {
+    pub fn str_to_addr(self, api: &dyn Api) -> Result<PriceOracle<Addr>, ContractError> {
+        Ok(PriceOracle {
+            oracle: api.addr_validate(&self.oracle)?,
+            max_deviation_bps: self.max_deviation_bps,
+        })
+    }
 }
 
 #[cw_serde]
@@ -69,6 +166,12 @@ pub struct PairImmutable<T: AddressLike> {
     pub owner: T,
     /// The denom of the tokens held by the pair
     pub denom: Denom,
+    /// Restricts the NFTs accepted by the pair to those matching a trait, all NFTs are accepted if None
+    #[serde(default)]
+    pub trait_filter: Option<TraitFilter<T>>,
+    /// Rejects swaps while the spot price is outside of a band around an oracle price
+    #[serde(default)]
+    pub price_oracle: Option<PriceOracle<T>>,
 }
 
 impl PairImmutable<String> {
@@ -77,6 +180,14 @@ impl PairImmutable<String> {
             collection: api.addr_validate(&self.collection)?,
             owner: api.addr_validate(&self.owner)?,
             denom: self.denom,
+            trait_filter: self
+                .trait_filter
+                .map(|trait_filter| trait_filter.str_to_addr(api))
+                .transpose()?,
+            price_oracle: self
+                .price_oracle
+                .map(|price_oracle| price_oracle.str_to_addr(api))
+                .transpose()?,
         })
     }
 }
@@ -94,6 +205,29 @@ pub struct PairConfig<T: AddressLike> {
     pub is_active: bool,
     /// The address of the recipient of assets traded into the pair
     pub asset_recipient: Option<T>,
+    /// Whether or not the pair is automatically activated when deposits make it quotable again,
+    /// and automatically deactivated when swaps leave it unable to produce a quote
+    #[serde(default)]
+    pub auto_activate: bool,
+    /// Whether or not swaps in opposite directions are rejected within the same block
+    #[serde(default)]
+    pub anti_sandwich: bool,
+    /// The address of the recipient of tokens traded into the pair, overrides the asset recipient
+    #[serde(default)]
+    pub token_recipient: Option<T>,
+    /// The address of the recipient of NFTs traded into the pair, overrides the asset recipient
+    #[serde(default)]
+    pub nft_recipient: Option<T>,
+    /// Whether or not the pair buys NFTs from users ("sell to" trades)
+    #[serde(default = "default_true")]
+    pub allow_buy: bool,
+    /// Whether or not the pair sells NFTs to users ("buy from" trades)
+    #[serde(default = "default_true")]
+    pub allow_sell: bool,
+}
+
+fn default_true() -> bool {
+    true
 }
 
 impl PairConfig<String> {
@@ -103,6 +237,12 @@ impl PairConfig<String> {
             bonding_curve: self.bonding_curve,
             is_active: self.is_active,
             asset_recipient: maybe_addr(api, self.asset_recipient)?,
+            auto_activate: self.auto_activate,
+            anti_sandwich: self.anti_sandwich,
+            token_recipient: maybe_addr(api, self.token_recipient)?,
+            nft_recipient: maybe_addr(api, self.nft_recipient)?,
+            allow_buy: self.allow_buy,
+            allow_sell: self.allow_sell,
         })
     }
 }
@@ -128,6 +268,24 @@ pub struct QuoteSummary {
     pub seller_amount: Uint128,
 }
 
+/// LastSwap records the block height and direction of the most recent swap
+#[cw_serde]
+pub struct LastSwap {
+    /// The block height of the swap
+    pub height: u64,
+    /// The direction of the swap
+    pub transaction_type: TransactionType,
+}
+
+/// PriceSnapshot records the spot price of the pair directly after a swap
+#[cw_serde]
+pub struct PriceSnapshot {
+    /// The block time at which the swap occurred
+    pub timestamp: Timestamp,
+    /// The spot price of the pair after the swap
+    pub spot_price: Uint128,
+}
+
 /// PairInternal represents the internal state of the pair, not directly set by the user
 #[cw_serde]
 pub struct PairInternal {
@@ -139,6 +297,19 @@ pub struct PairInternal {
     /// A breakdown of the fees to be paid out for the next "buy from" trade
     /// When set to `None`, the pair is not accepting "buy from" trades.
     pub buy_from_pair_quote_summary: Option<QuoteSummary>,
+    /// A ring buffer of the most recent spot prices, used to derive the TWAP of the pair.
+    /// The number of snapshots is capped by `MAX_PRICE_SNAPSHOTS`.
+    #[serde(default)]
+    pub price_snapshots: Vec<PriceSnapshot>,
+    /// The time of the most recent pair config update, used to enforce the update cooldown
+    #[serde(default)]
+    pub last_config_update: Option<Timestamp>,
+    /// The most recent swap against the pair, used to reject same block sandwiches
+    #[serde(default)]
+    pub last_swap: Option<LastSwap>,
+    /// The block time at which the pair was created, None for pairs created before it was recorded
+    #[serde(default)]
+    pub created_at: Option<Timestamp>,
 }
 
 pub const PAIR_INTERNAL: Item<PairInternal> = Item::new(TopKey::PairInternal.as_str());
@@ -1,10 +1,11 @@
 use crate::error::ContractError;
-use crate::helpers::approve_nft;
+use crate::helpers::{approve_nft, network_fee, split_proceeds};
 use crate::msg::{ExecuteMsg, SellOrder, SwapParams};
 use crate::nfts_for_tokens_iterators::{
     iter::NftsForTokens,
     types::{NftForTokensQuote, NftForTokensSource},
 };
+use crate::query::query_sim_swap_tokens_for_any_nfts;
 use crate::state::INFINITY_GLOBAL;
 use crate::tokens_for_nfts_iterators::{
     types::TokensForNftQuote,
@@ -13,7 +14,7 @@ use crate::tokens_for_nfts_iterators::{
 
 use cosmwasm_std::{
     attr, coin, ensure, ensure_eq, to_binary, Addr, CosmosMsg, DepsMut, Env, Event, MessageInfo,
-    Uint128, WasmMsg,
+    StdError, StdResult, Uint128, WasmMsg,
 };
 use cw_utils::{must_pay, nonpayable};
 use infinity_pair::msg::ExecuteMsg as PairExecuteMsg;
@@ -41,6 +42,7 @@ pub fn execute(
             collection,
             denom,
             sell_orders,
+            min_total_output,
             swap_params,
             filter_sources,
         } => execute_swap_nfts_for_tokens(
@@ -50,6 +52,7 @@ pub fn execute(
             api.addr_validate(&collection)?,
             denom,
             sell_orders,
+            min_total_output.unwrap_or_default(),
             swap_params.unwrap_or_default().str_to_addr(api)?,
             filter_sources.unwrap_or_default(),
         ),
@@ -57,6 +60,7 @@ pub fn execute(
             collection,
             denom,
             max_inputs,
+            min_nfts,
             swap_params,
             filter_sources,
         } => execute_swap_tokens_for_nfts(
@@ -66,9 +70,45 @@ pub fn execute(
             api.addr_validate(&collection)?,
             denom,
             max_inputs,
+            min_nfts.unwrap_or_default(),
             swap_params.unwrap_or_default().str_to_addr(api)?,
             filter_sources.unwrap_or_default(),
         ),
+        ExecuteMsg::SweepFloor {
+            collection,
+            denom,
+            count,
+            max_input,
+            per_unit_price_ceiling,
+            swap_params,
+            filter_sources,
+        } => execute_sweep_floor(
+            deps,
+            env,
+            info,
+            api.addr_validate(&collection)?,
+            denom,
+            count,
+            max_input,
+            per_unit_price_ceiling,
+            swap_params.unwrap_or_default().str_to_addr(api)?,
+            filter_sources.unwrap_or_default(),
+        ),
+        ExecuteMsg::SplitProceeds {
+            denom,
+            balance_before,
+            proceeds_splits,
+        } => execute_split_proceeds(
+            deps,
+            env,
+            info,
+            denom,
+            balance_before,
+            proceeds_splits
+                .into_iter()
+                .map(|(recipient, bps)| Ok((api.addr_validate(&recipient)?, bps)))
+                .collect::<StdResult<Vec<(Addr, u16)>>>()?,
+        ),
     }
 }
 
@@ -80,6 +120,7 @@ pub fn execute_swap_nfts_for_tokens(
     collection: Addr,
     denom: String,
     sell_orders: Vec<SellOrder>,
+    min_total_output: Uint128,
     swap_params: SwapParams<Addr>,
     filter_sources: Vec<NftForTokensSource>,
 ) -> Result<Response, ContractError> {
@@ -101,13 +142,28 @@ pub fn execute_swap_nfts_for_tokens(
 
     let asset_recipient = address_or(swap_params.asset_recipient.as_ref(), &info.sender);
 
+    // Split proceeds are collected by the router and distributed after the swaps
+    let proceeds_recipient = if swap_params.proceeds_splits.is_some() {
+        env.contract.address.clone()
+    } else {
+        asset_recipient.clone()
+    };
+    let balance_before = deps.querier.query_balance(&env.contract.address, &denom)?.amount;
+
     let mut num_swaps = 0u32;
     let mut volume = Uint128::zero();
+    let mut total_network_fee = Uint128::zero();
     for (sell_order, quote) in zip(sell_orders, quotes) {
         if quote.amount < sell_order.min_output {
             break;
         }
 
+        let quote_network_fee = network_fee(quote.quote_summary.as_ref());
+        if !swap_params.within_network_fee_cap(total_network_fee + quote_network_fee)? {
+            break;
+        }
+        total_network_fee += quote_network_fee;
+
         only_nft_owner(&deps.querier, &info, &collection, &sell_order.input_token_id)?;
         response =
             transfer_nft(&collection, &sell_order.input_token_id, &env.contract.address, response);
@@ -121,7 +177,7 @@ pub fn execute_swap_nfts_for_tokens(
                     msg: to_binary(&PairExecuteMsg::SwapNftForTokens {
                         token_id: sell_order.input_token_id,
                         min_output: coin(sell_order.min_output.u128(), &denom),
-                        asset_recipient: Some(asset_recipient.to_string()),
+                        asset_recipient: Some(proceeds_recipient.to_string()),
                     })?,
                     funds: vec![],
                 }))
@@ -134,13 +190,37 @@ pub fn execute_swap_nfts_for_tokens(
 
     ensure!(num_swaps > 0, ContractError::SwapError("no swaps were executed".to_string()));
 
-    if num_swaps < (requested_swaps as u32) && !swap_params.robust.unwrap_or(false) {
+    if num_swaps < (requested_swaps as u32) && !swap_params.allows_partial_fills() {
         return Err(ContractError::SwapError(format!(
             "unable to swap all nfts for tokens, requested swaps: {}, actual swaps: {}",
             requested_swaps, num_swaps
         )));
     }
 
+    if volume < min_total_output && !swap_params.robust.unwrap_or(false) {
+        return Err(ContractError::SwapError(format!(
+            "unable to receive the minimum total output, min total output: {}, actual output: {}",
+            min_total_output, volume
+        )));
+    }
+
+    // The proceeds are split by a final call to the router, so that the tokens actually
+    // received from the pairs are distributed rather than the quoted volume
+    if let Some(proceeds_splits) = &swap_params.proceeds_splits {
+        response = response.add_message(WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            msg: to_binary(&ExecuteMsg::SplitProceeds {
+                denom: denom.clone(),
+                balance_before,
+                proceeds_splits: proceeds_splits
+                    .iter()
+                    .map(|(recipient, bps)| (recipient.to_string(), *bps))
+                    .collect(),
+            })?,
+            funds: vec![],
+        });
+    }
+
     response = response.add_event(Event::new("router-swap-nfts-for-tokens").add_attributes(vec![
         attr("collection", collection),
         attr("denom", denom),
@@ -160,10 +240,18 @@ pub fn execute_swap_tokens_for_nfts(
     collection: Addr,
     denom: String,
     max_inputs: Vec<Uint128>,
+    min_nfts: u32,
     swap_params: SwapParams<Addr>,
     filter_sources: Vec<TokensForNftSource>,
 ) -> Result<Response, ContractError> {
     let received_amount = must_pay(&info, &denom)?;
+    ensure!(
+        swap_params.proceeds_splits.is_none(),
+        InfinityError::InvalidInput(
+            "proceeds splits are only supported when swapping nfts for tokens".to_string()
+        )
+    );
+
     let expected_amount = max_inputs.iter().sum::<Uint128>();
     ensure_eq!(
         received_amount,
@@ -191,11 +279,18 @@ pub fn execute_swap_tokens_for_nfts(
 
     let mut num_swaps = 0u32;
     let mut paid_amount = Uint128::zero();
+    let mut total_network_fee = Uint128::zero();
     for (max_input, quote) in zip(max_inputs, quotes) {
         if max_input < quote.amount {
             break;
         }
 
+        let quote_network_fee = network_fee(quote.quote_summary.as_ref());
+        if !swap_params.within_network_fee_cap(total_network_fee + quote_network_fee)? {
+            break;
+        }
+        total_network_fee += quote_network_fee;
+
         match quote.source {
             TokensForNftSource::Infinity => {
                 response = response.add_message(CosmosMsg::Wasm(WasmMsg::Execute {
@@ -214,13 +309,20 @@ pub fn execute_swap_tokens_for_nfts(
 
     ensure!(num_swaps > 0, ContractError::SwapError("no swaps were executed".to_string()));
 
-    if num_swaps < (requested_swaps as u32) && !swap_params.robust.unwrap_or(false) {
+    if num_swaps < (requested_swaps as u32) && !swap_params.allows_partial_fills() {
         return Err(ContractError::SwapError(format!(
             "unable to swap all tokens for nfts, requested swaps: {}, actual swaps: {}",
             requested_swaps, num_swaps
         )));
     }
 
+    if num_swaps < min_nfts && !swap_params.robust.unwrap_or(false) {
+        return Err(ContractError::SwapError(format!(
+            "unable to swap the minimum number of nfts, min nfts: {}, actual swaps: {}",
+            min_nfts, num_swaps
+        )));
+    }
+
     let refund_amount = received_amount.checked_sub(paid_amount).unwrap();
     if !refund_amount.is_zero() {
         response = transfer_coin(coin(refund_amount.u128(), &denom), &asset_recipient, response);
@@ -236,3 +338,148 @@ pub fn execute_swap_tokens_for_nfts(
 
     Ok(response)
 }
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute_sweep_floor(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: Addr,
+    denom: String,
+    count: u32,
+    max_input: Uint128,
+    per_unit_price_ceiling: Option<Uint128>,
+    swap_params: SwapParams<Addr>,
+    filter_sources: Vec<TokensForNftSource>,
+) -> Result<Response, ContractError> {
+    let received_amount = must_pay(&info, &denom)?;
+    ensure!(
+        swap_params.proceeds_splits.is_none(),
+        InfinityError::InvalidInput(
+            "proceeds splits are only supported when swapping nfts for tokens".to_string()
+        )
+    );
+
+    ensure_eq!(
+        received_amount,
+        max_input,
+        InfinityError::InsufficientFunds {
+            expected: coin(max_input.u128(), &denom),
+        }
+    );
+
+    // Quotes are sorted by ascending price, and are paired with the NFT each swap delivers
+    let quotes = query_sim_swap_tokens_for_any_nfts(
+        deps.as_ref(),
+        env,
+        collection.clone(),
+        denom.clone(),
+        count,
+        filter_sources,
+    )?;
+
+    let mut response = Response::new();
+
+    let asset_recipient = address_or(swap_params.asset_recipient.as_ref(), &info.sender);
+
+    let mut token_ids: Vec<String> = vec![];
+    let mut paid_amount = Uint128::zero();
+    let mut total_network_fee = Uint128::zero();
+    for quote in quotes {
+        if let Some(ceiling) = per_unit_price_ceiling {
+            if quote.amount > ceiling {
+                if swap_params.robust.unwrap_or(false) {
+                    continue;
+                }
+                return Err(ContractError::SwapError(format!(
+                    "quote {} exceeds the per unit price ceiling {}",
+                    quote.amount, ceiling
+                )));
+            }
+        }
+
+        if paid_amount + quote.amount > max_input {
+            break;
+        }
+
+        let quote_network_fee = network_fee(quote.quote_summary.as_ref());
+        if !swap_params.within_network_fee_cap(total_network_fee + quote_network_fee)? {
+            break;
+        }
+        total_network_fee += quote_network_fee;
+
+        match quote.source {
+            TokensForNftSource::Infinity => {
+                response = response.add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: quote.address.to_string(),
+                    msg: to_binary(&PairExecuteMsg::SwapTokensForSpecificNft {
+                        token_id: quote.token_id.clone(),
+                        asset_recipient: Some(asset_recipient.to_string()),
+                    })?,
+                    funds: vec![coin(quote.amount.u128(), &denom)],
+                }))
+            },
+        }
+
+        paid_amount += quote.amount;
+        token_ids.push(quote.token_id);
+    }
+
+    let num_swaps = token_ids.len() as u32;
+    ensure!(num_swaps > 0, ContractError::SwapError("no swaps were executed".to_string()));
+
+    if num_swaps < count && !swap_params.allows_partial_fills() {
+        return Err(ContractError::SwapError(format!(
+            "unable to sweep floor, requested swaps: {}, actual swaps: {}",
+            count, num_swaps
+        )));
+    }
+
+    let refund_amount = received_amount.checked_sub(paid_amount).unwrap();
+    if !refund_amount.is_zero() {
+        response = transfer_coin(coin(refund_amount.u128(), &denom), &asset_recipient, response);
+    }
+
+    response = response.add_event(
+        Event::new("router-sweep-floor")
+            .add_attributes(vec![
+                attr("collection", collection),
+                attr("denom", denom),
+                attr("sender_recipient", asset_recipient),
+                attr("num_swaps", num_swaps.to_string()),
+                attr("volume", paid_amount), // volume is the amount of tokens paid
+            ])
+            .add_attributes(token_ids.iter().map(|token_id| ("token_id", token_id))),
+    );
+
+    Ok(response)
+}
+
+pub fn execute_split_proceeds(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    denom: String,
+    balance_before: Uint128,
+    proceeds_splits: Vec<(Addr, u16)>,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    ensure_eq!(
+        info.sender,
+        env.contract.address,
+        InfinityError::Unauthorized("only the router can split proceeds".to_string())
+    );
+
+    let balance_after = deps.querier.query_balance(&env.contract.address, &denom)?.amount;
+    let proceeds = balance_after.checked_sub(balance_before).map_err(StdError::from)?;
+
+    let mut response = split_proceeds(proceeds, &denom, &proceeds_splits, Response::new());
+
+    response = response.add_event(
+        Event::new("router-split-proceeds")
+            .add_attribute("denom", denom)
+            .add_attribute("proceeds", proceeds),
+    );
+
+    Ok(response)
+}
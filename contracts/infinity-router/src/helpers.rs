@@ -1,5 +1,9 @@
-use cosmwasm_std::{to_binary, Addr, SubMsg, WasmMsg};
+use crate::msg::TOTAL_PROCEEDS_SPLIT_BPS;
+
+use cosmwasm_std::{coin, to_binary, Addr, SubMsg, Uint128, WasmMsg};
 use cw721::Cw721ExecuteMsg;
+use infinity_pair::state::QuoteSummary;
+use sg_marketplace_common::coin::transfer_coin;
 use sg_std::Response;
 
 pub fn approve_nft(
@@ -19,3 +23,30 @@ pub fn approve_nft(
         funds: vec![],
     }))
 }
+
+/// Distributes the proceeds between the split recipients. Rounding dust is sent to the last recipient.
+pub fn split_proceeds(
+    proceeds: Uint128,
+    denom: &str,
+    proceeds_splits: &[(Addr, u16)],
+    mut response: Response,
+) -> Response {
+    let mut remaining = proceeds;
+    for (idx, (recipient, bps)) in proceeds_splits.iter().enumerate() {
+        let amount = if idx == proceeds_splits.len() - 1 {
+            remaining
+        } else {
+            proceeds.multiply_ratio(*bps as u128, TOTAL_PROCEEDS_SPLIT_BPS as u128)
+        };
+        remaining -= amount;
+        if !amount.is_zero() {
+            response = transfer_coin(coin(amount.u128(), denom), recipient, response);
+        }
+    }
+    response
+}
+
+/// The network (fair burn) fee paid out of a swap, zero if the fee breakdown is unknown
+pub fn network_fee(quote_summary: Option<&QuoteSummary>) -> Uint128 {
+    quote_summary.map_or(Uint128::zero(), |quote_summary| quote_summary.fair_burn.amount)
+}
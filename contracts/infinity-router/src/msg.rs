@@ -1,14 +1,25 @@
 #[cfg_attr(not(debug_assertions), allow(unused_imports))]
 use crate::{
-    nfts_for_tokens_iterators::types::{NftForTokensQuote, NftForTokensSource},
-    tokens_for_nfts_iterators::types::{TokensForNftQuote, TokensForNftSource},
+    nfts_for_tokens_iterators::types::{
+        NftForTokensOrderQuote, NftForTokensQuote, NftForTokensSource,
+    },
+    tokens_for_nfts_iterators::types::{
+        TokensForAnyNftQuote, TokensForNftQuote, TokensForNftSource,
+    },
     ContractError,
 };
 
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Api, Uint128};
+use cosmwasm_std::{ensure_eq, Addr, Api, StdResult, Uint128};
 use cw_address_like::AddressLike;
 use cw_utils::maybe_addr;
+use infinity_index::msg::QuoteSide;
+use infinity_pair::msg::TransactionType;
+use infinity_pair::state::QuoteSummary;
+use infinity_shared::InfinityError;
+
+/// The total of the bps shares of a proceeds split, 10000 bps (100%)
+pub const TOTAL_PROCEEDS_SPLIT_BPS: u64 = 10000;
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -22,31 +33,101 @@ pub struct InstantiateMsg {
 pub struct SwapParams<T: AddressLike> {
     /// Whether or not to revert the entire trade if one of the swaps fails
     pub robust: Option<bool>,
+    /// Whether or not to commit the swaps that were filled when liquidity runs out before all
+    /// orders are filled. Invalid orders, such as NFTs not owned by the sender, still revert.
+    pub allow_partial: Option<bool>,
     /// The address to receive the assets from the swap, if not specified is set to sender
     pub asset_recipient: Option<T>,
+    /// Splits the proceeds of selling NFTs between recipients, as (address, bps) pairs
+    /// that sum to 10000 bps. Only supported by SwapNftsForTokens.
+    pub proceeds_splits: Option<Vec<(T, u16)>>,
+    /// The maximum sum of the network (fair burn) fees paid across all of the swaps.
+    /// In robust mode swaps stop filling at the cap, otherwise exceeding it reverts the trade.
+    pub max_total_network_fee: Option<Uint128>,
 }
 
 impl SwapParams<String> {
     pub fn str_to_addr(&self, api: &dyn Api) -> Result<SwapParams<Addr>, ContractError> {
+        let proceeds_splits = match &self.proceeds_splits {
+            Some(proceeds_splits) => {
+                let total_bps = proceeds_splits.iter().map(|(_, bps)| *bps as u64).sum::<u64>();
+                ensure_eq!(
+                    total_bps,
+                    TOTAL_PROCEEDS_SPLIT_BPS,
+                    InfinityError::InvalidInput(format!(
+                        "proceeds splits must sum to {} bps",
+                        TOTAL_PROCEEDS_SPLIT_BPS
+                    ))
+                );
+                Some(
+                    proceeds_splits
+                        .iter()
+                        .map(|(recipient, bps)| Ok((api.addr_validate(recipient)?, *bps)))
+                        .collect::<StdResult<Vec<(Addr, u16)>>>()?,
+                )
+            },
+            None => None,
+        };
+
         Ok(SwapParams {
             robust: self.robust,
+            allow_partial: self.allow_partial,
             asset_recipient: maybe_addr(api, self.asset_recipient.clone())?,
+            proceeds_splits,
+            max_total_network_fee: self.max_total_network_fee,
         })
     }
 }
 
+impl<T: AddressLike> SwapParams<T> {
+    /// Whether or not fewer swaps than requested may be committed
+    pub fn allows_partial_fills(&self) -> bool {
+        self.robust.unwrap_or(false) || self.allow_partial.unwrap_or(false)
+    }
+
+    /// Whether or not a swap that brings the network fees paid to `total_network_fee` may be
+    /// filled. Returns an error if the cap is exceeded outside of robust mode.
+    pub fn within_network_fee_cap(
+        &self,
+        total_network_fee: Uint128,
+    ) -> Result<bool, ContractError> {
+        match self.max_total_network_fee {
+            Some(max_total_network_fee) if total_network_fee > max_total_network_fee => {
+                if self.robust.unwrap_or(false) {
+                    return Ok(false);
+                }
+                Err(ContractError::SwapError(format!(
+                    "total network fee {} exceeds the maximum {}",
+                    total_network_fee, max_total_network_fee
+                )))
+            },
+            _ => Ok(true),
+        }
+    }
+}
+
 #[cw_serde]
 pub struct SellOrder {
     pub input_token_id: String,
     pub min_output: Uint128,
 }
 
+/// The specific NFTs to buy from a single pair
+#[cw_serde]
+pub struct PairNftSwap {
+    pub pair: String,
+    pub token_ids: Vec<String>,
+}
+
 #[cw_serde]
 pub enum ExecuteMsg {
     SwapNftsForTokens {
         collection: String,
         denom: String,
         sell_orders: Vec<SellOrder>,
+        /// The minimum total amount of tokens that must be received, otherwise the swap reverts.
+        /// Not enforced in robust mode.
+        min_total_output: Option<Uint128>,
         swap_params: Option<SwapParams<String>>,
         filter_sources: Option<Vec<NftForTokensSource>>,
     },
@@ -54,9 +135,31 @@ pub enum ExecuteMsg {
         collection: String,
         denom: String,
         max_inputs: Vec<Uint128>,
+        /// The minimum number of NFTs that must be delivered, otherwise the swap reverts.
+        /// Not enforced in robust mode.
+        min_nfts: Option<u32>,
         swap_params: Option<SwapParams<String>>,
         filter_sources: Option<Vec<TokensForNftSource>>,
     },
+    /// Buy the `count` cheapest NFTs available, spending at most `max_input` in total
+    SweepFloor {
+        collection: String,
+        denom: String,
+        count: u32,
+        max_input: Uint128,
+        /// The maximum price paid for any single NFT. In robust mode NFTs priced above the
+        /// ceiling are skipped, otherwise the sweep reverts.
+        per_unit_price_ceiling: Option<Uint128>,
+        swap_params: Option<SwapParams<String>>,
+        filter_sources: Option<Vec<TokensForNftSource>>,
+    },
+    /// Distributes the tokens received by the router since `balance_before` between the
+    /// split recipients. Only callable by the router, after the swaps of a SwapNftsForTokens.
+    SplitProceeds {
+        denom: String,
+        balance_before: Uint128,
+        proceeds_splits: Vec<(String, u16)>,
+    },
 }
 
 #[cw_serde]
@@ -76,4 +179,211 @@ pub enum QueryMsg {
         limit: u32,
         filter_sources: Option<Vec<TokensForNftSource>>,
     },
+    /// The sell orders that would be filled and left unfilled by a SwapNftsForTokens call
+    #[returns(SimSwapNftsForTokensResponse)]
+    SimSwapNftsForTokens {
+        collection: String,
+        denom: String,
+        sell_orders: Vec<SellOrder>,
+        filter_sources: Option<Vec<NftForTokensSource>>,
+    },
+    /// The sell orders that would be filled by a SwapNftsForTokens call limited to the first
+    /// `max_swaps` orders, and the orders that remain to be submitted in a later transaction
+    #[returns(SimSwapNftsForTokensBoundedResponse)]
+    SimSwapNftsForTokensBounded {
+        collection: String,
+        denom: String,
+        sell_orders: Vec<SellOrder>,
+        filter_sources: Option<Vec<NftForTokensSource>>,
+        /// The maximum number of swaps that fit in a single transaction
+        max_swaps: u32,
+    },
+    /// The sell orders that would be filled when each order is routed to the best bid among the
+    /// given trait filtered pairs that accept the NFT and the collection wide quotes.
+    /// Unlike SimSwapNftsForTokens, an unfilled order does not stop later orders from filling.
+    #[returns(SimSwapNftsForTokensRoutedResponse)]
+    SimSwapNftsForTokensRouted {
+        collection: String,
+        denom: String,
+        sell_orders: Vec<SellOrder>,
+        /// The trait filtered pairs to consider alongside the collection wide quotes
+        specific_pairs: Vec<String>,
+    },
+    /// The NFTs that would be delivered by a SwapTokensForNfts call, in swap order
+    #[returns(Vec<TokensForAnyNftQuote>)]
+    SimSwapTokensForAnyNfts {
+        collection: String,
+        denom: String,
+        limit: u32,
+        filter_sources: Option<Vec<TokensForNftSource>>,
+    },
+    /// The quotes for buying specific NFTs from each of the given pairs, in order, and the
+    /// token ids that could not be filled because the pair does not hold them or cannot quote
+    #[returns(SimSwapTokensForSpecificNftsResponse)]
+    SimSwapTokensForSpecificNfts {
+        collection: String,
+        denom: String,
+        pair_nfts_to_swap_for: Vec<PairNftSwap>,
+    },
+    /// The best sell quote for a single NFT, with the net proceeds to the seller
+    /// and the breakdown of fees, None if no pair can buy the NFT
+    #[returns(Option<SimSellProceedsResponse>)]
+    SimSellProceeds {
+        collection: String,
+        denom: String,
+        token_id: String,
+    },
+    /// The average price to fill `num_nfts` swaps across the best available quotes
+    #[returns(VolumeWeightedQuoteResponse)]
+    VolumeWeightedQuote {
+        collection: String,
+        denom: String,
+        num_nfts: u32,
+        transaction_type: TransactionType,
+    },
+    /// Cross checks the first `limit` entries of each quote index against the pairs they
+    /// reference, and returns the entries that no longer match the pair's current quote
+    #[returns(Vec<StaleQuote>)]
+    IndexHealth {
+        collection: String,
+        denom: String,
+        limit: u32,
+    },
+}
+
+/// Why a quote index entry is considered stale
+#[cw_serde]
+pub enum StaleQuoteReason {
+    /// The indexed address could not be queried as a pair
+    PairNotFound,
+    /// The pair has been deactivated
+    PairInactive,
+    /// The pair is active but can no longer quote on this side
+    NotQuotable,
+    /// The pair quotes a different amount than the index holds
+    QuoteMismatch,
+}
+
+#[cw_serde]
+pub struct StaleQuote {
+    pub pair: Addr,
+    pub side: QuoteSide,
+    /// The amount held by the index
+    pub indexed_amount: Uint128,
+    /// The amount the pair currently quotes, None if it cannot quote
+    pub expected_amount: Option<Uint128>,
+    pub reason: StaleQuoteReason,
+}
+
+#[cw_serde]
+pub struct VolumeWeightedQuoteResponse {
+    /// The number of swaps that can be filled, may be less than the requested amount
+    pub num_nfts: u32,
+    /// The sum of the quotes for all of the filled swaps
+    pub total: Uint128,
+    /// The average quote across all of the filled swaps, None if no swaps can be filled
+    pub average: Option<Uint128>,
+}
+
+#[cw_serde]
+pub struct SimSellProceedsResponse {
+    pub token_id: String,
+    /// The pair that would buy the NFT
+    pub pair: Addr,
+    /// The amount of tokens received by the seller, after all fees
+    pub net_to_seller: Uint128,
+    /// The breakdown of the fees paid out of the sale
+    pub quote_summary: QuoteSummary,
+}
+
+#[cw_serde]
+pub struct SimSwapNftsForTokensResponse {
+    /// The sell orders that would be filled, in swap order
+    pub filled: Vec<NftForTokensOrderQuote>,
+    /// The token ids of the sell orders that would not be filled
+    pub unfilled: Vec<String>,
+    /// The aggregate of the fees and proceeds of the filled sell orders
+    pub totals: SwapTotals,
+}
+
+#[cw_serde]
+pub struct SimSwapNftsForTokensBoundedResponse {
+    /// The sell orders that would be filled, in swap order
+    pub filled: Vec<NftForTokensOrderQuote>,
+    /// The sell orders that would not be filled, including those beyond the swap limit
+    pub remaining: Vec<SellOrder>,
+    /// The aggregate of the fees and proceeds of the filled sell orders
+    pub totals: SwapTotals,
+}
+
+/// The source of liquidity used to fill a sell order
+#[cw_serde]
+pub enum SellOrderRoute {
+    /// A trait filtered pair that accepts the NFT
+    SpecificPair,
+    /// The collection wide sell to pair quotes
+    CollectionQuote,
+}
+
+#[cw_serde]
+pub struct RoutedNftForTokensOrderQuote {
+    pub route: SellOrderRoute,
+    pub quote: NftForTokensOrderQuote,
+}
+
+#[cw_serde]
+pub struct SimSwapNftsForTokensRoutedResponse {
+    /// The sell orders that would be filled, in order
+    pub filled: Vec<RoutedNftForTokensOrderQuote>,
+    /// The token ids of the sell orders that would not be filled
+    pub unfilled: Vec<String>,
+    /// The aggregate of the fees and proceeds of the filled sell orders
+    pub totals: SwapTotals,
+}
+
+#[cw_serde]
+pub struct TokensForSpecificNftQuote {
+    pub pair: Addr,
+    pub token_id: String,
+    /// The amount of tokens paid by the buyer, including fees
+    pub amount: Uint128,
+    /// The breakdown of the fees paid out of the sale
+    pub quote_summary: QuoteSummary,
+}
+
+#[cw_serde]
+pub struct SimSwapTokensForSpecificNftsResponse {
+    /// The NFTs that would be bought, in swap order
+    pub filled: Vec<TokensForSpecificNftQuote>,
+    /// The token ids that would not be bought
+    pub unfilled: Vec<String>,
+    /// The aggregate of the fees and proceeds of the filled swaps
+    pub totals: SwapTotals,
+}
+
+/// SwapTotals is a rollup of the token payments across a batch of swaps
+#[cw_serde]
+#[derive(Default)]
+pub struct SwapTotals {
+    /// The sum of the sale prices, including fees
+    pub total_sale_price: Uint128,
+    /// The sum of the fair burn fees
+    pub total_fair_burn_fee: Uint128,
+    /// The sum of the royalty fees
+    pub total_royalty_fee: Uint128,
+    /// The sum of the swap fees paid to pair owners
+    pub total_swap_fee: Uint128,
+    /// The sum of the amounts received by the sellers
+    pub total_seller_proceeds: Uint128,
+}
+
+impl SwapTotals {
+    pub fn add_quote_summary(&mut self, quote_summary: &QuoteSummary) {
+        self.total_sale_price += quote_summary.total();
+        self.total_fair_burn_fee += quote_summary.fair_burn.amount;
+        self.total_royalty_fee +=
+            quote_summary.royalty.as_ref().map_or(Uint128::zero(), |r| r.amount);
+        self.total_swap_fee += quote_summary.swap.as_ref().map_or(Uint128::zero(), |s| s.amount);
+        self.total_seller_proceeds += quote_summary.seller_amount;
+    }
 }
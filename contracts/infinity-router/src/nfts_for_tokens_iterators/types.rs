@@ -1,6 +1,7 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Uint128};
 use infinity_pair::pair::Pair;
+use infinity_pair::state::QuoteSummary;
 use std::cmp::Ordering;
 
 #[cw_serde]
@@ -39,6 +40,8 @@ pub struct NftForTokensQuote {
     pub address: Addr,
     pub amount: Uint128,
     pub source: NftForTokensSource,
+    /// The breakdown of the fees paid out of the sale, if known
+    pub quote_summary: Option<QuoteSummary>,
 }
 
 impl From<&NftForTokensInternal> for NftForTokensQuote {
@@ -49,6 +52,20 @@ impl From<&NftForTokensInternal> for NftForTokensQuote {
             source: match &internal.source_data {
                 NftForTokensSourceData::Infinity(_) => NftForTokensSource::Infinity,
             },
+            quote_summary: match &internal.source_data {
+                NftForTokensSourceData::Infinity(pair) => {
+                    pair.internal.sell_to_pair_quote_summary.clone()
+                },
+            },
         }
     }
 }
+
+/// A quote paired with the sell order that it would fill
+#[cw_serde]
+pub struct NftForTokensOrderQuote {
+    pub token_id: String,
+    pub address: Addr,
+    pub amount: Uint128,
+    pub source: NftForTokensSource,
+}
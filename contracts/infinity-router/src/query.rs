@@ -1,15 +1,28 @@
-use crate::msg::QueryMsg;
+use crate::msg::{
+    QueryMsg, RoutedNftForTokensOrderQuote, SellOrder, SellOrderRoute, SimSellProceedsResponse,
+    SimSwapNftsForTokensBoundedResponse, SimSwapNftsForTokensResponse,
+    SimSwapNftsForTokensRoutedResponse, SimSwapTokensForSpecificNftsResponse, StaleQuote,
+    StaleQuoteReason, SwapTotals, TokensForSpecificNftQuote, VolumeWeightedQuoteResponse,
+};
 use crate::nfts_for_tokens_iterators::{
     iter::NftsForTokens,
-    types::{NftForTokensQuote, NftForTokensSource},
+    types::{NftForTokensOrderQuote, NftForTokensQuote, NftForTokensSource},
 };
 use crate::state::INFINITY_GLOBAL;
 use crate::tokens_for_nfts_iterators::{
     iter::TokensForNfts,
-    types::{TokensForNftQuote, TokensForNftSource},
+    types::{TokensForAnyNftQuote, TokensForNftQuote, TokensForNftSource},
 };
 
-use cosmwasm_std::{to_binary, Addr, Binary, Deps, Env, StdError, StdResult};
+use cosmwasm_std::{ensure, to_binary, Addr, Binary, Deps, Env, StdError, StdResult, Uint128};
+use infinity_index::msg::{QueryMsg as InfinityIndexQueryMsg, QuoteSide};
+use infinity_index::state::PairQuote;
+use infinity_pair::helpers::{load_payout_context, only_matching_trait};
+use infinity_pair::msg::{NftDepositsResponse, QueryMsg as PairQueryMsg, TransactionType};
+use infinity_pair::pair::Pair;
+use sg_index_query::{QueryBound, QueryOptions};
+use std::cmp::min;
+use std::collections::{BTreeMap, VecDeque};
 
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
@@ -45,6 +58,114 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             limit,
             filter_sources.unwrap_or_default(),
         )?),
+        QueryMsg::SimSwapNftsForTokens {
+            collection,
+            denom,
+            sell_orders,
+            filter_sources,
+        } => to_binary(&query_sim_swap_nfts_for_tokens(
+            deps,
+            env,
+            api.addr_validate(&collection)?,
+            denom,
+            sell_orders,
+            filter_sources.unwrap_or_default(),
+        )?),
+        QueryMsg::SimSwapNftsForTokensBounded {
+            collection,
+            denom,
+            sell_orders,
+            filter_sources,
+            max_swaps,
+        } => to_binary(&query_sim_swap_nfts_for_tokens_bounded(
+            deps,
+            env,
+            api.addr_validate(&collection)?,
+            denom,
+            sell_orders,
+            filter_sources.unwrap_or_default(),
+            max_swaps,
+        )?),
+        QueryMsg::SimSwapNftsForTokensRouted {
+            collection,
+            denom,
+            sell_orders,
+            specific_pairs,
+        } => to_binary(&query_sim_swap_nfts_for_tokens_routed(
+            deps,
+            env,
+            api.addr_validate(&collection)?,
+            denom,
+            sell_orders,
+            specific_pairs
+                .iter()
+                .map(|pair| api.addr_validate(pair))
+                .collect::<StdResult<Vec<Addr>>>()?,
+        )?),
+        QueryMsg::SimSwapTokensForAnyNfts {
+            collection,
+            denom,
+            limit,
+            filter_sources,
+        } => to_binary(&query_sim_swap_tokens_for_any_nfts(
+            deps,
+            env,
+            api.addr_validate(&collection)?,
+            denom,
+            limit,
+            filter_sources.unwrap_or_default(),
+        )?),
+        QueryMsg::SimSwapTokensForSpecificNfts {
+            collection,
+            denom,
+            pair_nfts_to_swap_for,
+        } => to_binary(&query_sim_swap_tokens_for_specific_nfts(
+            deps,
+            env,
+            api.addr_validate(&collection)?,
+            denom,
+            pair_nfts_to_swap_for
+                .into_iter()
+                .map(|pair_nft_swap| {
+                    Ok((api.addr_validate(&pair_nft_swap.pair)?, pair_nft_swap.token_ids))
+                })
+                .collect::<StdResult<Vec<(Addr, Vec<String>)>>>()?,
+        )?),
+        QueryMsg::SimSellProceeds {
+            collection,
+            denom,
+            token_id,
+        } => to_binary(&query_sim_sell_proceeds(
+            deps,
+            env,
+            api.addr_validate(&collection)?,
+            denom,
+            token_id,
+        )?),
+        QueryMsg::VolumeWeightedQuote {
+            collection,
+            denom,
+            num_nfts,
+            transaction_type,
+        } => to_binary(&query_volume_weighted_quote(
+            deps,
+            env,
+            api.addr_validate(&collection)?,
+            denom,
+            num_nfts,
+            transaction_type,
+        )?),
+        QueryMsg::IndexHealth {
+            collection,
+            denom,
+            limit,
+        } => to_binary(&query_index_health(
+            deps,
+            env,
+            api.addr_validate(&collection)?,
+            denom,
+            limit,
+        )?),
     }
 }
 
@@ -82,3 +203,458 @@ pub fn query_tokens_for_nfts(
 
     Ok(result)
 }
+
+pub fn query_sim_swap_nfts_for_tokens(
+    deps: Deps,
+    env: Env,
+    collection: Addr,
+    denom: String,
+    sell_orders: Vec<SellOrder>,
+    filter_sources: Vec<NftForTokensSource>,
+) -> StdResult<SimSwapNftsForTokensResponse> {
+    let quotes = query_nfts_for_tokens(
+        deps,
+        env,
+        collection,
+        denom,
+        sell_orders.len() as u32,
+        filter_sources,
+    )?;
+
+    // Mirrors SwapNftsForTokens, which stops filling at the first order that cannot be matched
+    let mut quotes = quotes.into_iter();
+    let mut filled: Vec<NftForTokensOrderQuote> = vec![];
+    let mut unfilled: Vec<String> = vec![];
+    let mut totals = SwapTotals::default();
+    for sell_order in sell_orders {
+        match quotes.next() {
+            Some(quote) if unfilled.is_empty() && quote.amount >= sell_order.min_output => {
+                if let Some(quote_summary) = &quote.quote_summary {
+                    totals.add_quote_summary(quote_summary);
+                }
+                filled.push(NftForTokensOrderQuote {
+                    token_id: sell_order.input_token_id,
+                    address: quote.address,
+                    amount: quote.amount,
+                    source: quote.source,
+                });
+            },
+            _ => unfilled.push(sell_order.input_token_id),
+        }
+    }
+
+    Ok(SimSwapNftsForTokensResponse {
+        filled,
+        unfilled,
+        totals,
+    })
+}
+
+pub fn query_sim_swap_nfts_for_tokens_bounded(
+    deps: Deps,
+    env: Env,
+    collection: Addr,
+    denom: String,
+    mut sell_orders: Vec<SellOrder>,
+    filter_sources: Vec<NftForTokensSource>,
+    max_swaps: u32,
+) -> StdResult<SimSwapNftsForTokensBoundedResponse> {
+    let overflow = sell_orders.split_off(min(max_swaps as usize, sell_orders.len()));
+
+    let SimSwapNftsForTokensResponse {
+        filled,
+        unfilled,
+        totals,
+    } = query_sim_swap_nfts_for_tokens(
+        deps,
+        env,
+        collection,
+        denom,
+        sell_orders.clone(),
+        filter_sources,
+    )?;
+
+    let remaining = sell_orders
+        .into_iter()
+        .filter(|sell_order| unfilled.contains(&sell_order.input_token_id))
+        .chain(overflow)
+        .collect::<Vec<SellOrder>>();
+
+    Ok(SimSwapNftsForTokensBoundedResponse {
+        filled,
+        remaining,
+        totals,
+    })
+}
+
+pub fn query_sim_swap_nfts_for_tokens_routed(
+    deps: Deps,
+    env: Env,
+    collection: Addr,
+    denom: String,
+    sell_orders: Vec<SellOrder>,
+    specific_pairs: Vec<Addr>,
+) -> StdResult<SimSwapNftsForTokensRoutedResponse> {
+    let infinity_global = INFINITY_GLOBAL.load(deps.storage)?;
+    let payout_context = load_payout_context(deps, &infinity_global, &collection, &denom)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+
+    // Pairs without a trait filter are already part of the collection wide quotes
+    let mut pairs = specific_pairs
+        .into_iter()
+        .map(|address| {
+            let pair = deps.querier.query_wasm_smart::<Pair>(&address, &PairQueryMsg::Pair {})?;
+            ensure!(
+                pair.immutable.collection == collection && pair.immutable.denom == denom,
+                StdError::generic_err(format!(
+                    "pair {} has a different collection or denom",
+                    address
+                ))
+            );
+            ensure!(
+                pair.immutable.trait_filter.is_some(),
+                StdError::generic_err(format!("pair {} does not have a trait filter", address))
+            );
+            Ok((address, pair))
+        })
+        .collect::<StdResult<Vec<(Addr, Pair)>>>()?;
+
+    let mut collection_quotes =
+        query_nfts_for_tokens(deps, env, collection, denom, sell_orders.len() as u32, vec![])?
+            .into_iter()
+            .peekable();
+
+    let mut filled: Vec<RoutedNftForTokensOrderQuote> = vec![];
+    let mut unfilled: Vec<String> = vec![];
+    let mut totals = SwapTotals::default();
+    for sell_order in sell_orders {
+        // The highest bid among the specific pairs that accept the NFT
+        let specific_bid = pairs
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, (_, pair))| {
+                pair.internal
+                    .sell_to_pair_quote_summary
+                    .as_ref()
+                    .filter(|_| {
+                        only_matching_trait(&deps.querier, pair, &sell_order.input_token_id).is_ok()
+                    })
+                    .map(|quote_summary| (idx, quote_summary.clone()))
+            })
+            .max_by_key(|(_, quote_summary)| quote_summary.seller_amount);
+        let collection_bid = collection_quotes.peek().map(|quote| quote.amount);
+
+        // The specific pair wins ties with the collection wide quote
+        match (specific_bid, collection_bid) {
+            (Some((idx, quote_summary)), _)
+                if quote_summary.seller_amount >= sell_order.min_output
+                    && collection_bid
+                        .map_or(true, |amount| quote_summary.seller_amount >= amount) =>
+            {
+                let (address, pair) = &mut pairs[idx];
+                totals.add_quote_summary(&quote_summary);
+                filled.push(RoutedNftForTokensOrderQuote {
+                    route: SellOrderRoute::SpecificPair,
+                    quote: NftForTokensOrderQuote {
+                        token_id: sell_order.input_token_id,
+                        address: address.clone(),
+                        amount: quote_summary.seller_amount,
+                        source: NftForTokensSource::Infinity,
+                    },
+                });
+                pair.sim_swap_nft_for_tokens(&payout_context);
+            },
+            (_, Some(amount)) if amount >= sell_order.min_output => {
+                let quote = collection_quotes.next().unwrap();
+                if let Some(quote_summary) = &quote.quote_summary {
+                    totals.add_quote_summary(quote_summary);
+                }
+                filled.push(RoutedNftForTokensOrderQuote {
+                    route: SellOrderRoute::CollectionQuote,
+                    quote: NftForTokensOrderQuote {
+                        token_id: sell_order.input_token_id,
+                        address: quote.address,
+                        amount: quote.amount,
+                        source: quote.source,
+                    },
+                });
+            },
+            _ => unfilled.push(sell_order.input_token_id),
+        }
+    }
+
+    Ok(SimSwapNftsForTokensRoutedResponse {
+        filled,
+        unfilled,
+        totals,
+    })
+}
+
+pub fn query_sim_sell_proceeds(
+    deps: Deps,
+    env: Env,
+    collection: Addr,
+    denom: String,
+    token_id: String,
+) -> StdResult<Option<SimSellProceedsResponse>> {
+    let best_quote = match query_nfts_for_tokens(deps, env, collection, denom, 1, vec![])?.pop() {
+        Some(quote) => quote,
+        None => return Ok(None),
+    };
+
+    let pair =
+        deps.querier.query_wasm_smart::<Pair>(&best_quote.address, &PairQueryMsg::Pair {})?;
+    let quote_summary = pair
+        .internal
+        .sell_to_pair_quote_summary
+        .ok_or_else(|| StdError::generic_err("pair cannot produce quote"))?;
+
+    Ok(Some(SimSellProceedsResponse {
+        token_id,
+        pair: best_quote.address,
+        net_to_seller: quote_summary.seller_amount,
+        quote_summary,
+    }))
+}
+
+pub fn query_sim_swap_tokens_for_any_nfts(
+    deps: Deps,
+    env: Env,
+    collection: Addr,
+    denom: String,
+    limit: u32,
+    filter_sources: Vec<TokensForNftSource>,
+) -> StdResult<Vec<TokensForAnyNftQuote>> {
+    let quotes = query_tokens_for_nfts(deps, env, collection, denom, limit, filter_sources)?;
+
+    let mut num_swaps_by_pair: BTreeMap<Addr, u32> = BTreeMap::new();
+    for quote in &quotes {
+        *num_swaps_by_pair.entry(quote.address.clone()).or_default() += 1;
+    }
+
+    // Pairs deliver their deposited NFTs in ascending token id order
+    let mut token_ids_by_pair: BTreeMap<Addr, VecDeque<String>> = BTreeMap::new();
+    for (pair, num_swaps) in num_swaps_by_pair {
+        let nft_deposits = deps.querier.query_wasm_smart::<NftDepositsResponse>(
+            &pair,
+            &PairQueryMsg::NftDeposits {
+                query_options: Some(QueryOptions {
+                    limit: Some(num_swaps),
+                    descending: Some(false),
+                    min: None,
+                    max: None,
+                }),
+            },
+        )?;
+        token_ids_by_pair.insert(pair, nft_deposits.token_ids.into());
+    }
+
+    quotes
+        .into_iter()
+        .map(|quote| {
+            let token_id = token_ids_by_pair
+                .get_mut(&quote.address)
+                .and_then(|token_ids| token_ids.pop_front())
+                .ok_or(StdError::generic_err("pair does not have enough nfts"))?;
+
+            Ok(TokensForAnyNftQuote {
+                address: quote.address,
+                amount: quote.amount,
+                source: quote.source,
+                token_id,
+                quote_summary: quote.quote_summary,
+            })
+        })
+        .collect::<StdResult<Vec<TokensForAnyNftQuote>>>()
+}
+
+pub fn query_sim_swap_tokens_for_specific_nfts(
+    deps: Deps,
+    _env: Env,
+    collection: Addr,
+    denom: String,
+    pair_nfts_to_swap_for: Vec<(Addr, Vec<String>)>,
+) -> StdResult<SimSwapTokensForSpecificNftsResponse> {
+    let infinity_global = INFINITY_GLOBAL.load(deps.storage)?;
+    let payout_context = load_payout_context(deps, &infinity_global, &collection, &denom)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+
+    let mut filled: Vec<TokensForSpecificNftQuote> = vec![];
+    let mut unfilled: Vec<String> = vec![];
+    let mut totals = SwapTotals::default();
+    for (address, token_ids) in pair_nfts_to_swap_for {
+        let mut pair = deps.querier.query_wasm_smart::<Pair>(&address, &PairQueryMsg::Pair {})?;
+        ensure!(
+            pair.immutable.collection == collection && pair.immutable.denom == denom,
+            StdError::generic_err(format!("pair {} has a different collection or denom", address))
+        );
+
+        for token_id in token_ids {
+            let quote_summary = match &pair.internal.buy_from_pair_quote_summary {
+                Some(quote_summary) if pair.config.is_active => quote_summary.clone(),
+                _ => {
+                    unfilled.push(token_id);
+                    continue;
+                },
+            };
+
+            // Mirrors SwapTokensForSpecificNft, which rejects NFTs that are not deposited
+            // in the pair, including those already bought earlier in the batch
+            let nft_deposits = deps.querier.query_wasm_smart::<NftDepositsResponse>(
+                &address,
+                &PairQueryMsg::NftDeposits {
+                    query_options: Some(QueryOptions {
+                        limit: Some(1),
+                        descending: None,
+                        min: Some(QueryBound::Inclusive(token_id.clone())),
+                        max: Some(QueryBound::Inclusive(token_id.clone())),
+                    }),
+                },
+            )?;
+            let already_filled =
+                filled.iter().any(|quote| quote.pair == address && quote.token_id == token_id);
+            if !nft_deposits.token_ids.contains(&token_id) || already_filled {
+                unfilled.push(token_id);
+                continue;
+            }
+
+            totals.add_quote_summary(&quote_summary);
+            filled.push(TokensForSpecificNftQuote {
+                pair: address.clone(),
+                token_id,
+                amount: quote_summary.total(),
+                quote_summary,
+            });
+            pair.sim_swap_tokens_for_nft(&payout_context);
+        }
+    }
+
+    Ok(SimSwapTokensForSpecificNftsResponse {
+        filled,
+        unfilled,
+        totals,
+    })
+}
+
+pub fn query_volume_weighted_quote(
+    deps: Deps,
+    env: Env,
+    collection: Addr,
+    denom: String,
+    num_nfts: u32,
+    transaction_type: TransactionType,
+) -> StdResult<VolumeWeightedQuoteResponse> {
+    let amounts: Vec<Uint128> = match transaction_type {
+        TransactionType::UserSubmitsNfts => {
+            query_nfts_for_tokens(deps, env, collection, denom, num_nfts, vec![])?
+                .into_iter()
+                .map(|quote| quote.amount)
+                .collect()
+        },
+        TransactionType::UserSubmitsTokens => {
+            query_tokens_for_nfts(deps, env, collection, denom, num_nfts, vec![])?
+                .into_iter()
+                .map(|quote| quote.amount)
+                .collect()
+        },
+    };
+
+    let num_filled = amounts.len() as u32;
+    let total = amounts.into_iter().sum::<Uint128>();
+    let average = if num_filled > 0 {
+        Some(total.checked_div(Uint128::from(num_filled))?)
+    } else {
+        None
+    };
+
+    Ok(VolumeWeightedQuoteResponse {
+        num_nfts: num_filled,
+        total,
+        average,
+    })
+}
+
+pub fn query_index_health(
+    deps: Deps,
+    _env: Env,
+    collection: Addr,
+    denom: String,
+    limit: u32,
+) -> StdResult<Vec<StaleQuote>> {
+    let infinity_global = INFINITY_GLOBAL.load(deps.storage)?;
+    let payout_context = load_payout_context(deps, &infinity_global, &collection, &denom)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+
+    let query_options = Some(QueryOptions {
+        limit: Some(limit),
+        descending: None,
+        min: None,
+        max: None,
+    });
+    let sell_to_pair_quotes = deps.querier.query_wasm_smart::<Vec<PairQuote>>(
+        &payout_context.global_config.infinity_index,
+        &InfinityIndexQueryMsg::SellToPairQuotes {
+            collection: collection.to_string(),
+            denom: denom.clone(),
+            query_options: query_options.clone(),
+        },
+    )?;
+    let buy_from_pair_quotes = deps.querier.query_wasm_smart::<Vec<PairQuote>>(
+        &payout_context.global_config.infinity_index,
+        &InfinityIndexQueryMsg::BuyFromPairQuotes {
+            collection: collection.to_string(),
+            denom,
+            query_options,
+        },
+    )?;
+
+    let entries = sell_to_pair_quotes
+        .into_iter()
+        .map(|pair_quote| (QuoteSide::SellToPair, pair_quote))
+        .chain(
+            buy_from_pair_quotes.into_iter().map(|pair_quote| (QuoteSide::BuyFromPair, pair_quote)),
+        );
+
+    let mut stale_quotes: Vec<StaleQuote> = vec![];
+    for (side, pair_quote) in entries {
+        let pair_result =
+            deps.querier.query_wasm_smart::<Pair>(&pair_quote.address, &PairQueryMsg::Pair {});
+
+        // Recompute the quotes from the current pair balances and payout context,
+        // rather than trusting the summaries stored at the pair's last update
+        let (expected_amount, reason) = match pair_result {
+            Err(_) => (None, Some(StaleQuoteReason::PairNotFound)),
+            Ok(pair) if !pair.config.is_active => (None, Some(StaleQuoteReason::PairInactive)),
+            Ok(mut pair) => {
+                pair.update_sell_to_pair_quote_summary(&payout_context);
+                pair.update_buy_from_pair_quote_summary(&payout_context);
+                let (sell_to_pair_quote, buy_from_pair_quote) =
+                    pair.index_quotes(&payout_context.global_config);
+                let expected_amount = match side {
+                    QuoteSide::SellToPair => sell_to_pair_quote,
+                    QuoteSide::BuyFromPair => buy_from_pair_quote,
+                };
+                let reason = match expected_amount {
+                    None => Some(StaleQuoteReason::NotQuotable),
+                    Some(amount) if amount != pair_quote.quote.amount => {
+                        Some(StaleQuoteReason::QuoteMismatch)
+                    },
+                    Some(_) => None,
+                };
+                (expected_amount, reason)
+            },
+        };
+
+        if let Some(reason) = reason {
+            stale_quotes.push(StaleQuote {
+                pair: pair_quote.address,
+                side,
+                indexed_amount: pair_quote.quote.amount,
+                expected_amount,
+                reason,
+            });
+        }
+    }
+
+    Ok(stale_quotes)
+}
@@ -46,45 +46,61 @@ impl<'a> TokensForNftsInfinity<'a> {
     }
 
     pub fn fetch_quote(&mut self) {
-        let pair_quote_option = self
-            .deps
-            .querier
-            .query_wasm_smart::<Vec<PairQuote>>(
-                &self.payout_context.global_config.infinity_index,
-                &InfinityIndexQueryMsg::BuyFromPairQuotes {
-                    collection: self.collection.to_string(),
-                    denom: self.payout_context.denom.to_string(),
-                    query_options: Some(QueryOptions {
-                        limit: Some(1),
-                        descending: Some(false),
-                        min: self.cursor.as_ref().map(|c| QueryBound::Exclusive(c.clone())),
-                        max: None,
-                    }),
+        loop {
+            let pair_quote_option = self
+                .deps
+                .querier
+                .query_wasm_smart::<Vec<PairQuote>>(
+                    &self.payout_context.global_config.infinity_index,
+                    &InfinityIndexQueryMsg::BuyFromPairQuotes {
+                        collection: self.collection.to_string(),
+                        denom: self.payout_context.denom.to_string(),
+                        query_options: Some(QueryOptions {
+                            limit: Some(1),
+                            descending: Some(false),
+                            min: self.cursor.as_ref().map(|c| QueryBound::Exclusive(c.clone())),
+                            max: None,
+                        }),
+                    },
+                )
+                .unwrap()
+                .pop();
+
+            let pair_quote = match pair_quote_option {
+                Some(pair_quote) => pair_quote,
+                None => {
+                    self.cursor = None;
+                    return;
                 },
-            )
-            .unwrap()
-            .pop();
+            };
 
-        if let Some(pair_quote) = pair_quote_option {
             self.cursor = Some(PairQuoteOffset {
                 pair: pair_quote.address.to_string(),
                 amount: pair_quote.quote.amount,
             });
 
-            let pair = self
+            // Skip pairs with a stale index entry that can no longer sell an NFT,
+            // such as pairs that no longer hold any NFTs
+            let pair = match self
                 .deps
                 .querier
                 .query_wasm_smart::<Pair>(&pair_quote.address, &PairQueryMsg::Pair {})
-                .map_err(|_| StdError::generic_err("pair not found"))
-                .unwrap();
+            {
+                Ok(pair)
+                    if pair.internal.total_nfts > 0
+                        && pair.internal.buy_from_pair_quote_summary.is_some() =>
+                {
+                    pair
+                },
+                _ => continue,
+            };
 
             self.quotes.insert(TokensForNftInternal {
                 address: pair_quote.address,
                 amount: pair_quote.quote.amount,
                 source_data: TokensForNftSourceData::Infinity(pair),
             });
-        } else {
-            self.cursor = None;
+            return;
         }
     }
 }
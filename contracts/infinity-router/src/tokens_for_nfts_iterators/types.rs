@@ -1,6 +1,7 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Uint128};
 use infinity_pair::pair::Pair;
+use infinity_pair::state::QuoteSummary;
 use std::cmp::Ordering;
 
 #[cw_serde]
@@ -39,6 +40,8 @@ pub struct TokensForNftQuote {
     pub address: Addr,
     pub amount: Uint128,
     pub source: TokensForNftSource,
+    /// The breakdown of the fees paid out of the sale, if known
+    pub quote_summary: Option<QuoteSummary>,
 }
 
 impl From<&TokensForNftInternal> for TokensForNftQuote {
@@ -49,6 +52,22 @@ impl From<&TokensForNftInternal> for TokensForNftQuote {
             source: match &internal.source_data {
                 TokensForNftSourceData::Infinity(_) => TokensForNftSource::Infinity,
             },
+            quote_summary: match &internal.source_data {
+                TokensForNftSourceData::Infinity(pair) => {
+                    pair.internal.buy_from_pair_quote_summary.clone()
+                },
+            },
         }
     }
 }
+
+/// A quote paired with the NFT that the swap would deliver
+#[cw_serde]
+pub struct TokensForAnyNftQuote {
+    pub address: Addr,
+    pub amount: Uint128,
+    pub source: TokensForNftSource,
+    pub token_id: String,
+    /// The breakdown of the fees paid out of the sale, if known
+    pub quote_summary: Option<QuoteSummary>,
+}
@@ -22,6 +22,7 @@ pub fn get_pool_fixtures(
             swap_fee_bps,
             reinvest_tokens: false,
             reinvest_nfts: false,
+            max_nfts: None,
         },
         PoolInfo {
             collection: collection.to_string(),
@@ -35,6 +36,7 @@ pub fn get_pool_fixtures(
             swap_fee_bps,
             reinvest_tokens: false,
             reinvest_nfts: false,
+            max_nfts: None,
         },
         PoolInfo {
             collection: collection.to_string(),
@@ -48,6 +50,7 @@ pub fn get_pool_fixtures(
             swap_fee_bps,
             reinvest_tokens: false,
             reinvest_nfts: false,
+            max_nfts: None,
         },
         PoolInfo {
             collection: collection.to_string(),
@@ -61,6 +64,7 @@ pub fn get_pool_fixtures(
             swap_fee_bps,
             reinvest_tokens: false,
             reinvest_nfts: false,
+            max_nfts: None,
         },
         PoolInfo {
             collection: collection.to_string(),
@@ -74,6 +78,7 @@ pub fn get_pool_fixtures(
             swap_fee_bps,
             reinvest_tokens: false,
             reinvest_nfts: false,
+            max_nfts: None,
         },
         PoolInfo {
             collection: collection.to_string(),
@@ -87,6 +92,7 @@ pub fn get_pool_fixtures(
             swap_fee_bps,
             reinvest_tokens: false,
             reinvest_nfts: false,
+            max_nfts: None,
         },
         PoolInfo {
             collection: collection.to_string(),
@@ -100,6 +106,7 @@ pub fn get_pool_fixtures(
             swap_fee_bps,
             reinvest_tokens: false,
             reinvest_nfts: false,
+            max_nfts: None,
         },
         PoolInfo {
             collection: collection.to_string(),
@@ -113,6 +120,7 @@ pub fn get_pool_fixtures(
             swap_fee_bps,
             reinvest_tokens: false,
             reinvest_nfts: false,
+            max_nfts: None,
         },
         PoolInfo {
             collection: collection.to_string(),
@@ -126,6 +134,7 @@ pub fn get_pool_fixtures(
             swap_fee_bps,
             reinvest_tokens: false,
             reinvest_nfts: false,
+            max_nfts: None,
         },
         PoolInfo {
             collection: collection.to_string(),
@@ -139,6 +148,7 @@ pub fn get_pool_fixtures(
             swap_fee_bps,
             reinvest_tokens: false,
             reinvest_nfts: false,
+            max_nfts: None,
         },
         PoolInfo {
             collection: collection.to_string(),
@@ -152,6 +162,7 @@ pub fn get_pool_fixtures(
             swap_fee_bps,
             reinvest_tokens: false,
             reinvest_nfts: false,
+            max_nfts: None,
         },
         PoolInfo {
             collection: collection.to_string(),
@@ -165,6 +176,7 @@ pub fn get_pool_fixtures(
             swap_fee_bps,
             reinvest_tokens: false,
             reinvest_nfts: false,
+            max_nfts: None,
         },
         PoolInfo {
             collection: collection.to_string(),
@@ -178,6 +190,7 @@ pub fn get_pool_fixtures(
             swap_fee_bps,
             reinvest_tokens: false,
             reinvest_nfts: false,
+            max_nfts: None,
         },
         PoolInfo {
             collection: collection.to_string(),
@@ -191,6 +204,7 @@ pub fn get_pool_fixtures(
             swap_fee_bps,
             reinvest_tokens: false,
             reinvest_nfts: false,
+            max_nfts: None,
         },
     ]
 }
@@ -20,6 +20,24 @@ pub fn create_pair(
     infinity_factory: &Addr,
     collection: &Addr,
     owner: &Addr,
+) -> (Addr, Pair) {
+    create_pair_with_type(
+        router,
+        infinity_global,
+        infinity_factory,
+        collection,
+        owner,
+        PairType::Token,
+    )
+}
+
+pub fn create_pair_with_type(
+    router: &mut StargazeApp,
+    infinity_global: &Addr,
+    infinity_factory: &Addr,
+    collection: &Addr,
+    owner: &Addr,
+    pair_type: PairType,
 ) -> (Addr, Pair) {
     let global_config = router
         .wrap()
@@ -33,16 +51,24 @@ pub fn create_pair(
         collection: collection.to_string(),
         owner: owner.to_string(),
         denom: NATIVE_DENOM.to_string(),
+        trait_filter: None,
+        price_oracle: None,
     };
 
     let pair_config = PairConfig {
-        pair_type: PairType::Token,
+        pair_type,
         bonding_curve: BondingCurve::Linear {
             spot_price: Uint128::from(10_000_000u128),
             delta: Uint128::from(1_000_000u128),
         },
         is_active: false,
         asset_recipient: None,
+        auto_activate: false,
+        anti_sandwich: false,
+        token_recipient: None,
+        nft_recipient: None,
+        allow_buy: true,
+        allow_sell: true,
     };
 
     let response = router.execute_contract(
@@ -87,14 +113,22 @@ pub fn create_pair_with_deposits(
     let (pair_addr, _pair) =
         create_pair(router, infinity_global, infinity_factory, collection, owner);
 
+    // The pair type is set before depositing, the rest of the config once the pair holds
+    // the assets that its bonding curve requires
     let response = router.execute_contract(
         owner.clone(),
         pair_addr.clone(),
         &InfinityPairExecuteMsg::UpdatePairConfig {
-            is_active: Some(pair_config.is_active),
-            pair_type: Some(pair_config.pair_type),
-            bonding_curve: Some(pair_config.bonding_curve),
-            asset_recipient: pair_config.asset_recipient,
+            is_active: None,
+            pair_type: Some(pair_config.pair_type.clone()),
+            bonding_curve: None,
+            asset_recipient: None,
+            auto_activate: None,
+            anti_sandwich: None,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: None,
+            allow_sell: None,
         },
         &[],
     );
@@ -130,6 +164,25 @@ pub fn create_pair_with_deposits(
         assert!(response.is_ok());
     }
 
+    let response = router.execute_contract(
+        owner.clone(),
+        pair_addr.clone(),
+        &InfinityPairExecuteMsg::UpdatePairConfig {
+            is_active: Some(pair_config.is_active),
+            pair_type: Some(pair_config.pair_type),
+            bonding_curve: Some(pair_config.bonding_curve),
+            asset_recipient: pair_config.asset_recipient,
+            auto_activate: Some(pair_config.auto_activate),
+            anti_sandwich: Some(pair_config.anti_sandwich),
+            token_recipient: pair_config.token_recipient,
+            nft_recipient: pair_config.nft_recipient,
+            allow_buy: Some(pair_config.allow_buy),
+            allow_sell: Some(pair_config.allow_sell),
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
     let pair = router
         .wrap()
         .query_wasm_smart::<Pair>(pair_addr.clone(), &InfinityPairQueryMsg::Pair {})
@@ -9,6 +9,13 @@ pub fn assert_error(response: Result<AppResponse, Error>, expected: String) {
     assert_eq!(response.unwrap_err().source().unwrap().to_string(), expected);
 }
 
+pub fn assert_error_variant<E>(response: Result<AppResponse, Error>, expected: E)
+where
+    E: std::error::Error + PartialEq + Send + Sync + 'static,
+{
+    assert_eq!(response.unwrap_err().downcast_ref::<E>(), Some(&expected));
+}
+
 pub fn _assert_event(response: Result<AppResponse, Error>, ty: &str) {
     assert!(response.unwrap().events.iter().any(|event| event.ty == ty));
 }
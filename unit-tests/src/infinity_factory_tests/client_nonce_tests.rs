@@ -0,0 +1,137 @@
+use crate::setup::setup_accounts::MarketAccounts;
+use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
+
+use cosmwasm_std::{Addr, Uint128};
+use cw_multi_test::Executor;
+use infinity_factory::msg::{
+    ExecuteMsg as InfinityFactoryExecuteMsg, NextPairResponse, QueryMsg as InfinityFactoryQueryMsg,
+};
+use infinity_global::{msg::QueryMsg as InfinityGlobalQueryMsg, GlobalConfig};
+use infinity_pair::msg::QueryMsg as InfinityPairQueryMsg;
+use infinity_pair::pair::Pair;
+use infinity_pair::state::{BondingCurve, PairConfig, PairImmutable, PairType};
+use sg_std::NATIVE_DENOM;
+use test_suite::common_setup::msg::MinterTemplateResponse;
+
+#[test]
+fn try_create_pair2_client_nonce() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        owner,
+                        ..
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        infinity_pair_code_id,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            &infinity_global,
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    let next_pair = router
+        .wrap()
+        .query_wasm_smart::<NextPairResponse>(
+            &infinity_factory,
+            &InfinityFactoryQueryMsg::NextPair {
+                sender: owner.to_string(),
+            },
+        )
+        .unwrap();
+
+    let create_pair2_msg = InfinityFactoryExecuteMsg::CreatePair2 {
+        pair_immutable: PairImmutable {
+            collection: collection.to_string(),
+            owner: owner.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            trait_filter: None,
+            price_oracle: None,
+        },
+        pair_config: PairConfig {
+            pair_type: PairType::Token,
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: false,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        client_nonce: Some("retry-1".to_string()),
+    };
+
+    let balance_before = router.wrap().query_balance(&owner, NATIVE_DENOM).unwrap().amount;
+
+    // The first request creates the pair
+    let response = router.execute_contract(
+        owner.clone(),
+        infinity_factory.clone(),
+        &create_pair2_msg,
+        &[global_config.pair_creation_fee.clone()],
+    );
+    assert!(response.is_ok());
+
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(next_pair.pair.clone(), &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert_eq!(pair.immutable.owner, owner);
+
+    // A retry with the same nonce returns the existing pair and refunds the fee
+    let response = router.execute_contract(
+        owner.clone(),
+        infinity_factory.clone(),
+        &create_pair2_msg,
+        &[global_config.pair_creation_fee.clone()],
+    );
+    let existing_pair = response
+        .unwrap()
+        .events
+        .iter()
+        .find(|e| e.ty == "wasm-factory-create-pair2-existing")
+        .unwrap()
+        .attributes
+        .iter()
+        .find(|a| a.key == "pair")
+        .unwrap()
+        .value
+        .clone();
+    assert_eq!(existing_pair, next_pair.pair.to_string());
+
+    let pairs = router
+        .wrap()
+        .query_wasm_smart::<Vec<(u64, Addr)>>(
+            &infinity_factory,
+            &InfinityFactoryQueryMsg::PairsByOwner {
+                owner: owner.to_string(),
+                code_id: infinity_pair_code_id,
+                query_options: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(pairs, vec![(0u64, next_pair.pair)]);
+
+    // The pair creation fee was only charged once
+    let balance_after = router.wrap().query_balance(&owner, NATIVE_DENOM).unwrap().amount;
+    assert_eq!(balance_before - balance_after, global_config.pair_creation_fee.amount);
+}
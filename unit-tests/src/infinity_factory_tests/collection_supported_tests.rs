@@ -0,0 +1,79 @@
+use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
+
+use infinity_factory::msg::{CollectionSupportedResponse, QueryMsg as InfinityFactoryQueryMsg};
+use infinity_shared::InfinityError;
+use sg_std::NATIVE_DENOM;
+use test_suite::common_setup::msg::MinterTemplateResponse;
+
+#[test]
+fn try_query_collection_supported() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                ..
+            },
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let collection = collection_resp.collection.clone().unwrap();
+
+    // Supported collection and denom
+    let response = router
+        .wrap()
+        .query_wasm_smart::<CollectionSupportedResponse>(
+            infinity_factory.clone(),
+            &InfinityFactoryQueryMsg::CollectionSupported {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        response,
+        CollectionSupportedResponse {
+            supported: true,
+            reason: None,
+        }
+    );
+
+    // Supported collection, but denom without a min price
+    let response = router
+        .wrap()
+        .query_wasm_smart::<CollectionSupportedResponse>(
+            infinity_factory.clone(),
+            &InfinityFactoryQueryMsg::CollectionSupported {
+                collection: collection.to_string(),
+                denom: "uatom".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        response,
+        CollectionSupportedResponse {
+            supported: false,
+            reason: Some(
+                InfinityError::InvalidInput("denom not supported".to_string()).to_string()
+            ),
+        }
+    );
+
+    // Collection that has not started trading
+    router.update_block(|block| block.time = block.time.minus_seconds(1));
+    let response = router
+        .wrap()
+        .query_wasm_smart::<CollectionSupportedResponse>(
+            infinity_factory,
+            &InfinityFactoryQueryMsg::CollectionSupported {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+            },
+        )
+        .unwrap();
+    assert!(!response.supported);
+    assert!(response.reason.is_some());
+}
@@ -0,0 +1,153 @@
+use crate::helpers::nft_functions::{approve_all, assert_nft_owner, mint_to};
+use crate::helpers::utils::assert_error;
+use crate::setup::setup_accounts::MarketAccounts;
+use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
+
+use cosmwasm_std::{coin, Addr, Decimal, Uint128};
+use cw_multi_test::Executor;
+use infinity_factory::msg::ExecuteMsg as InfinityFactoryExecuteMsg;
+use infinity_global::{msg::QueryMsg as InfinityGlobalQueryMsg, GlobalConfig};
+use infinity_pair::msg::QueryMsg as InfinityPairQueryMsg;
+use infinity_pair::pair::Pair;
+use infinity_pair::state::{BondingCurve, PairConfig, PairImmutable, PairType};
+use infinity_shared::InfinityError;
+use sg_std::NATIVE_DENOM;
+use test_suite::common_setup::msg::MinterTemplateResponse;
+
+#[test]
+fn try_create_and_fund_pair() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        ..
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            &infinity_global,
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    let mut token_ids: Vec<String> = vec![];
+    for _ in 0..3 {
+        token_ids.push(mint_to(&mut router, &creator, &owner, &minter));
+    }
+
+    let token_amount = Uint128::from(50_000_000u128);
+    let create_and_fund_pair_msg = InfinityFactoryExecuteMsg::CreateAndFundPair {
+        pair_immutable: PairImmutable {
+            collection: collection.to_string(),
+            owner: owner.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            trait_filter: None,
+            price_oracle: None,
+        },
+        pair_config: PairConfig {
+            pair_type: PairType::Trade {
+                swap_fee_percent: Decimal::percent(1),
+                reinvest_tokens: false,
+                reinvest_nfts: false,
+                max_nfts: None,
+            },
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        token_amount,
+        nft_token_ids: token_ids.clone(),
+    };
+    let funds =
+        coin(global_config.pair_creation_fee.amount.u128() + token_amount.u128(), NATIVE_DENOM);
+
+    // Funds must cover the token deposit in addition to the pair creation fee
+    let response = router.execute_contract(
+        owner.clone(),
+        infinity_factory.clone(),
+        &create_and_fund_pair_msg,
+        &[global_config.pair_creation_fee.clone()],
+    );
+    assert_error(
+        response,
+        InfinityError::InsufficientFunds {
+            expected: coin(token_amount.u128(), NATIVE_DENOM),
+        }
+        .to_string(),
+    );
+
+    // The whole message reverts when the NFTs are not approved for the factory
+    let response = router.execute_contract(
+        owner.clone(),
+        infinity_factory.clone(),
+        &create_and_fund_pair_msg,
+        &[funds.clone()],
+    );
+    assert!(response.is_err());
+    assert_nft_owner(&router, &collection, token_ids[0].clone(), &owner);
+
+    approve_all(&mut router, &owner, &collection, &infinity_factory);
+    let response = router.execute_contract(
+        owner.clone(),
+        infinity_factory.clone(),
+        &create_and_fund_pair_msg,
+        &[funds],
+    );
+    let pair_addr = response
+        .unwrap()
+        .events
+        .iter()
+        .find(|e| e.ty == "wasm-factory-fund-pair")
+        .unwrap()
+        .attributes
+        .iter()
+        .find(|a| a.key == "pair")
+        .unwrap()
+        .value
+        .clone();
+    let pair_addr = Addr::unchecked(pair_addr);
+
+    // The pair is funded with both assets and quotable immediately
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(pair_addr.clone(), &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert_eq!(pair.immutable.owner, owner);
+    assert!(pair.config.is_active);
+    assert_eq!(pair.internal.total_nfts, 3u64);
+    assert_eq!(pair.total_tokens, token_amount);
+    assert!(pair.internal.sell_to_pair_quote_summary.is_some());
+    assert!(pair.internal.buy_from_pair_quote_summary.is_some());
+
+    for token_id in token_ids {
+        assert_nft_owner(&router, &collection, token_id, &pair_addr);
+    }
+    let factory_balance =
+        router.wrap().query_balance(&infinity_factory, NATIVE_DENOM).unwrap().amount;
+    assert_eq!(factory_balance, Uint128::zero());
+}
@@ -1,4 +1,14 @@
 #[cfg(test)]
+mod client_nonce_tests;
+#[cfg(test)]
+mod collection_supported_tests;
+#[cfg(test)]
+mod create_and_fund_pair_tests;
+#[cfg(test)]
+mod quote_summaries_tests;
+#[cfg(test)]
 mod sim_pair_quotes_factory_tests;
 #[cfg(test)]
 mod sudo_tests;
+#[cfg(test)]
+mod validate_pair_config_tests;
@@ -0,0 +1,114 @@
+use crate::helpers::pair_functions::{create_pair_with_deposits, TestPair};
+use crate::setup::setup_accounts::MarketAccounts;
+use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
+
+use cosmwasm_std::{Decimal, Uint128};
+use infinity_factory::msg::{PairQuoteSummaries, QueryMsg as InfinityFactoryQueryMsg};
+use infinity_pair::msg::QueryMsg as InfinityPairQueryMsg;
+use infinity_pair::pair::Pair;
+use infinity_pair::state::{BondingCurve, PairConfig, PairType};
+use test_suite::common_setup::msg::MinterTemplateResponse;
+
+#[test]
+fn try_query_quote_summaries_by_pair() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        ..
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let pair_configs = vec![
+        (PairType::Token, 0u64, Uint128::from(100_000_000u128)),
+        (PairType::Nft, 3u64, Uint128::zero()),
+        (
+            PairType::Trade {
+                swap_fee_percent: Decimal::percent(1u64),
+                reinvest_tokens: false,
+                reinvest_nfts: false,
+                max_nfts: None,
+            },
+            3u64,
+            Uint128::from(100_000_000u128),
+        ),
+    ];
+
+    let test_pairs = pair_configs
+        .into_iter()
+        .map(|(pair_type, num_nfts, num_tokens)| {
+            create_pair_with_deposits(
+                &mut router,
+                &infinity_global,
+                &infinity_factory,
+                &minter,
+                &collection,
+                &creator,
+                &owner,
+                PairConfig {
+                    pair_type,
+                    bonding_curve: BondingCurve::Linear {
+                        spot_price: Uint128::from(10_000_000u128),
+                        delta: Uint128::from(1_000_000u128),
+                    },
+                    is_active: true,
+                    asset_recipient: None,
+                    auto_activate: false,
+                    anti_sandwich: false,
+                    token_recipient: None,
+                    nft_recipient: None,
+                    allow_buy: true,
+                    allow_sell: true,
+                },
+                num_nfts,
+                num_tokens,
+            )
+        })
+        .collect::<Vec<TestPair>>();
+
+    let quote_summaries = router
+        .wrap()
+        .query_wasm_smart::<Vec<PairQuoteSummaries>>(
+            infinity_factory.clone(),
+            &InfinityFactoryQueryMsg::QuoteSummariesByPair {
+                pairs: test_pairs.iter().map(|test_pair| test_pair.address.to_string()).collect(),
+            },
+        )
+        .unwrap();
+    assert_eq!(quote_summaries.len(), test_pairs.len());
+
+    for (test_pair, pair_quote_summaries) in test_pairs.iter().zip(quote_summaries) {
+        let pair = router
+            .wrap()
+            .query_wasm_smart::<Pair>(test_pair.address.clone(), &InfinityPairQueryMsg::Pair {})
+            .unwrap();
+
+        assert_eq!(pair_quote_summaries.pair, test_pair.address);
+        assert_eq!(
+            pair_quote_summaries.sell_to_pair_quote_summary,
+            pair.internal.sell_to_pair_quote_summary
+        );
+        assert_eq!(
+            pair_quote_summaries.buy_from_pair_quote_summary,
+            pair.internal.buy_from_pair_quote_summary
+        );
+    }
+
+    // Token pairs cannot sell NFTs and NFT pairs cannot buy them
+    assert!(test_pairs[0].pair.internal.buy_from_pair_quote_summary.is_none());
+    assert!(test_pairs[1].pair.internal.sell_to_pair_quote_summary.is_none());
+}
@@ -52,11 +52,18 @@ fn try_sim_sell_to_pair_quotes() {
             pair_type: PairType::Trade {
                 swap_fee_percent: Decimal::percent(2),
                 reinvest_nfts: true,
+                max_nfts: None,
                 reinvest_tokens: true,
             },
             bonding_curve: BondingCurve::ConstantProduct,
             is_active: false,
             asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
         },
         20u64,
         Uint128::from(100_000_000u128),
@@ -129,11 +136,18 @@ fn try_sim_buy_from_pair_quotes() {
             pair_type: PairType::Trade {
                 swap_fee_percent: Decimal::percent(2),
                 reinvest_nfts: true,
+                max_nfts: None,
                 reinvest_tokens: true,
             },
             bonding_curve: BondingCurve::ConstantProduct,
             is_active: false,
             asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
         },
         20u64,
         Uint128::from(100_000_000u128),
@@ -162,3 +176,101 @@ fn try_sim_buy_from_pair_quotes() {
 
     assert_eq!(pair_quotes_response, factory_quotes_response);
 }
+
+#[test]
+fn try_sim_pair_quotes_truncate_on_liquidity() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        ..
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let mut pair_config = PairConfig {
+        pair_type: PairType::Token,
+        bonding_curve: BondingCurve::Linear {
+            spot_price: Uint128::from(10_000_000u128),
+            delta: Uint128::from(1_000_000u128),
+        },
+        is_active: true,
+        asset_recipient: None,
+        auto_activate: false,
+        anti_sandwich: false,
+        token_recipient: None,
+        nft_recipient: None,
+        allow_buy: true,
+        allow_sell: true,
+    };
+
+    // The token pair can only afford the swaps at 10, 9 and 8 STARS
+    let token_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        pair_config.clone(),
+        0u64,
+        Uint128::from(30_000_000u128),
+    );
+
+    let quotes_response = router
+        .wrap()
+        .query_wasm_smart::<QuotesResponse>(
+            infinity_factory.clone(),
+            &InfinityFactoryQueryMsg::SimSellToPairSwaps {
+                pair: token_pair.pair,
+                limit: 10,
+            },
+        )
+        .unwrap();
+    assert_eq!(quotes_response.sell_to_pair_quotes.len(), 3);
+    assert!(quotes_response.sell_to_pair_quotes.windows(2).all(|w| w[0] > w[1]));
+    assert!(quotes_response.buy_from_pair_quotes.is_empty());
+
+    // The NFT pair can only sell the NFTs it holds
+    pair_config.pair_type = PairType::Nft;
+    let nft_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        pair_config,
+        3u64,
+        Uint128::zero(),
+    );
+
+    let quotes_response = router
+        .wrap()
+        .query_wasm_smart::<QuotesResponse>(
+            infinity_factory,
+            &InfinityFactoryQueryMsg::SimBuyFromPairSwaps {
+                pair: nft_pair.pair,
+                limit: 10,
+            },
+        )
+        .unwrap();
+    assert_eq!(quotes_response.buy_from_pair_quotes.len(), 3);
+    assert!(quotes_response.buy_from_pair_quotes.windows(2).all(|w| w[0] < w[1]));
+    assert!(quotes_response.sell_to_pair_quotes.is_empty());
+}
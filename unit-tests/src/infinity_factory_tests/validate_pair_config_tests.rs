@@ -0,0 +1,141 @@
+use crate::setup::setup_accounts::MarketAccounts;
+use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
+
+use cosmwasm_std::Uint128;
+use infinity_factory::msg::{QueryMsg as InfinityFactoryQueryMsg, ValidatePairConfigResponse};
+use infinity_pair::state::{BondingCurve, PairConfig, PairImmutable, PairType};
+use infinity_shared::InfinityError;
+use sg_multi_test::StargazeApp;
+use sg_std::NATIVE_DENOM;
+use test_suite::common_setup::msg::MinterTemplateResponse;
+
+#[test]
+fn try_query_validate_pair_config() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        owner,
+                        ..
+                    },
+            },
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let pair_immutable = PairImmutable {
+        collection: collection.to_string(),
+        owner: owner.to_string(),
+        denom: NATIVE_DENOM.to_string(),
+        trait_filter: None,
+        price_oracle: None,
+    };
+    let pair_config = PairConfig {
+        pair_type: PairType::Token,
+        bonding_curve: BondingCurve::Linear {
+            spot_price: Uint128::from(10_000_000u128),
+            delta: Uint128::from(1_000_000u128),
+        },
+        is_active: true,
+        asset_recipient: None,
+        auto_activate: false,
+        anti_sandwich: false,
+        token_recipient: None,
+        nft_recipient: None,
+        allow_buy: true,
+        allow_sell: true,
+    };
+
+    let query_validate = |router: &StargazeApp,
+                          pair_immutable: &PairImmutable<String>,
+                          pair_config: &PairConfig<String>| {
+        router
+            .wrap()
+            .query_wasm_smart::<ValidatePairConfigResponse>(
+                infinity_factory.clone(),
+                &InfinityFactoryQueryMsg::ValidatePairConfig {
+                    pair_immutable: pair_immutable.clone(),
+                    pair_config: pair_config.clone(),
+                },
+            )
+            .unwrap()
+    };
+
+    // Valid pair parameters
+    let response = query_validate(&router, &pair_immutable, &pair_config);
+    assert_eq!(
+        response,
+        ValidatePairConfigResponse {
+            valid: true,
+            reason: None,
+        }
+    );
+
+    // Invalid collection address
+    let response = query_validate(
+        &router,
+        &PairImmutable {
+            collection: "INVALID".to_string(),
+            ..pair_immutable.clone()
+        },
+        &pair_config,
+    );
+    assert!(!response.valid);
+    assert!(response.reason.is_some());
+
+    // Invalid owner address
+    let response = query_validate(
+        &router,
+        &PairImmutable {
+            owner: "INVALID".to_string(),
+            ..pair_immutable.clone()
+        },
+        &pair_config,
+    );
+    assert!(!response.valid);
+    assert!(response.reason.is_some());
+
+    // Invalid asset recipient address
+    let response = query_validate(
+        &router,
+        &pair_immutable,
+        &PairConfig {
+            asset_recipient: Some("INVALID".to_string()),
+            ..pair_config.clone()
+        },
+    );
+    assert!(!response.valid);
+    assert!(response.reason.is_some());
+
+    // Denom without a min price
+    let response = query_validate(
+        &router,
+        &PairImmutable {
+            denom: "uatom".to_string(),
+            ..pair_immutable.clone()
+        },
+        &pair_config,
+    );
+    assert_eq!(
+        response,
+        ValidatePairConfigResponse {
+            valid: false,
+            reason: Some(
+                InfinityError::InvalidInput("denom not supported".to_string()).to_string()
+            ),
+        }
+    );
+
+    // Collection that has not started trading
+    router.update_block(|block| block.time = block.time.minus_seconds(1));
+    let response = query_validate(&router, &pair_immutable, &pair_config);
+    assert!(!response.valid);
+    assert!(response.reason.is_some());
+}
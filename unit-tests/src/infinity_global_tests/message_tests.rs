@@ -1,9 +1,9 @@
 use crate::setup::setup_infinity_contracts::{contract_infinity_global, UOSMO};
 
-use cosmwasm_std::{coin, Addr, Coin, Decimal};
+use cosmwasm_std::{coin, Addr, Coin, Decimal, StdError, Uint128};
 use cw_multi_test::Executor;
 use infinity_global::{
-    msg::{InstantiateMsg, QueryMsg, SudoMsg},
+    msg::{InstantiateMsg, OptionalConfigField, QueryMsg, SudoMsg},
     GlobalConfig,
 };
 use sg_multi_test::mock_deps;
@@ -37,6 +37,16 @@ fn try_infinity_global_init() {
         default_royalty_fee_percent: Decimal::percent(10u64),
         max_royalty_fee_percent: Decimal::percent(15u64),
         max_swap_fee_percent: Decimal::percent(10u64),
+        max_nfts_per_pair: Some(100u64),
+        pair_config_update_cooldown_seconds: None,
+        trading_fee_recipient: None,
+        burn_share_percent: None,
+        min_token_deposit: None,
+        min_nft_count: None,
+        pair_creation_fee_discount_percent: None,
+        min_spot_price: None,
+        max_spot_price: None,
+        probe_collection_freeze: None,
     };
 
     let min_prices = vec![coin(1_000_000u128, NATIVE_DENOM)];
@@ -76,6 +86,56 @@ fn try_infinity_global_init() {
     assert_eq!(min_prices[0], min_price_response.unwrap());
 }
 
+#[test]
+fn try_infinity_global_init_invalid_max_nfts_per_pair() {
+    let creator = Addr::unchecked("creator");
+
+    let mut router = custom_mock_app();
+    let infinity_global_code_id = router.store_code(contract_infinity_global());
+
+    let global_config = GlobalConfig {
+        fair_burn: "fair_burn".to_string(),
+        royalty_registry: "royalty_registry".to_string(),
+        marketplace: "marketplace".to_string(),
+        infinity_factory: "infinity_factory".to_string(),
+        infinity_index: "infinity_index".to_string(),
+        infinity_router: "infinity_router".to_string(),
+        infinity_pair_code_id: 1u64,
+        pair_creation_fee: coin(1_000_000u128, NATIVE_DENOM),
+        fair_burn_fee_percent: Decimal::percent(1u64),
+        default_royalty_fee_percent: Decimal::percent(10u64),
+        max_royalty_fee_percent: Decimal::percent(15u64),
+        max_swap_fee_percent: Decimal::percent(10u64),
+        max_nfts_per_pair: Some(0u64),
+        pair_config_update_cooldown_seconds: None,
+        trading_fee_recipient: None,
+        burn_share_percent: None,
+        min_token_deposit: None,
+        min_nft_count: None,
+        pair_creation_fee_discount_percent: None,
+        min_spot_price: None,
+        max_spot_price: None,
+        probe_collection_freeze: None,
+    };
+
+    let msg = InstantiateMsg {
+        global_config,
+        min_prices: vec![],
+    };
+    let response = router.instantiate_contract(
+        infinity_global_code_id,
+        creator,
+        &msg,
+        &[],
+        "Infinity Global",
+        None,
+    );
+    assert_eq!(
+        response.unwrap_err().root_cause().to_string(),
+        StdError::generic_err("max_nfts_per_pair must be greater than zero").to_string()
+    );
+}
+
 #[test]
 fn try_infinity_global_update_config() {
     let creator = Addr::unchecked("creator");
@@ -103,6 +163,16 @@ fn try_infinity_global_update_config() {
         default_royalty_fee_percent: Decimal::percent(10u64),
         max_royalty_fee_percent: Decimal::percent(15u64),
         max_swap_fee_percent: Decimal::percent(10u64),
+        max_nfts_per_pair: Some(100u64),
+        pair_config_update_cooldown_seconds: None,
+        trading_fee_recipient: None,
+        burn_share_percent: None,
+        min_token_deposit: None,
+        min_nft_count: None,
+        pair_creation_fee_discount_percent: None,
+        min_spot_price: None,
+        max_spot_price: None,
+        probe_collection_freeze: None,
     };
 
     let min_prices = vec![coin(1_000_000u128, NATIVE_DENOM)];
@@ -135,6 +205,16 @@ fn try_infinity_global_update_config() {
         default_royalty_fee_percent: Some(Decimal::percent(1u64)),
         max_royalty_fee_percent: Some(Decimal::percent(20u64)),
         max_swap_fee_percent: Some(Decimal::percent(20u64)),
+        max_nfts_per_pair: Some(200u64),
+        pair_config_update_cooldown_seconds: Some(60u64),
+        trading_fee_recipient: Some("trading_fee_recipient_new".to_string()),
+        burn_share_percent: Some(Decimal::percent(50u64)),
+        min_token_deposit: Some(Uint128::from(1_000_000u128)),
+        min_nft_count: Some(2u64),
+        pair_creation_fee_discount_percent: Some(Decimal::percent(10u64)),
+        min_spot_price: Some(Uint128::from(1_000u128)),
+        max_spot_price: Some(Uint128::from(1_000_000_000_000u128)),
+        probe_collection_freeze: Some(true),
     };
     let response = router.wasm_sudo(infinity_global.clone(), &update_config_msg);
     assert!(response.is_ok());
@@ -157,6 +237,16 @@ fn try_infinity_global_update_config() {
         default_royalty_fee_percent,
         max_royalty_fee_percent,
         max_swap_fee_percent,
+        max_nfts_per_pair,
+        pair_config_update_cooldown_seconds,
+        trading_fee_recipient,
+        burn_share_percent,
+        min_token_deposit,
+        min_nft_count,
+        pair_creation_fee_discount_percent,
+        min_spot_price,
+        max_spot_price,
+        probe_collection_freeze,
     } = update_config_msg
     {
         assert_eq!(fair_burn.unwrap(), global_config_response.fair_burn);
@@ -177,7 +267,98 @@ fn try_infinity_global_update_config() {
             global_config_response.max_royalty_fee_percent
         );
         assert_eq!(max_swap_fee_percent.unwrap(), global_config_response.max_swap_fee_percent);
+        assert_eq!(max_nfts_per_pair, global_config_response.max_nfts_per_pair);
+        assert_eq!(
+            pair_config_update_cooldown_seconds,
+            global_config_response.pair_config_update_cooldown_seconds
+        );
+        assert_eq!(
+            trading_fee_recipient.unwrap(),
+            global_config_response.trading_fee_recipient.unwrap()
+        );
+        assert_eq!(burn_share_percent, global_config_response.burn_share_percent);
+        assert_eq!(min_token_deposit, global_config_response.min_token_deposit);
+        assert_eq!(min_nft_count, global_config_response.min_nft_count);
+        assert_eq!(
+            pair_creation_fee_discount_percent,
+            global_config_response.pair_creation_fee_discount_percent
+        );
+        assert_eq!(min_spot_price, global_config_response.min_spot_price);
+        assert_eq!(max_spot_price, global_config_response.max_spot_price);
+        assert_eq!(probe_collection_freeze, global_config_response.probe_collection_freeze);
     }
+
+    // The spot price bounds cannot exclude every price
+    let update_config_msg = SudoMsg::UpdateConfig {
+        fair_burn: None,
+        royalty_registry: None,
+        marketplace: None,
+        infinity_factory: None,
+        infinity_index: None,
+        infinity_router: None,
+        infinity_pair_code_id: None,
+        pair_creation_fee: None,
+        fair_burn_fee_percent: None,
+        default_royalty_fee_percent: None,
+        max_royalty_fee_percent: None,
+        max_swap_fee_percent: None,
+        max_nfts_per_pair: None,
+        pair_config_update_cooldown_seconds: None,
+        trading_fee_recipient: None,
+        burn_share_percent: None,
+        min_token_deposit: None,
+        min_nft_count: None,
+        pair_creation_fee_discount_percent: None,
+        min_spot_price: Some(Uint128::from(2_000_000_000_000u128)),
+        max_spot_price: None,
+        probe_collection_freeze: None,
+    };
+    let response = router.wasm_sudo(infinity_global.clone(), &update_config_msg);
+    assert_eq!(
+        response.unwrap_err().root_cause().to_string(),
+        StdError::generic_err("min_spot_price cannot exceed max_spot_price").to_string()
+    );
+
+    // Optional settings can be cleared
+    let response = router.wasm_sudo(
+        infinity_global.clone(),
+        &SudoMsg::ClearConfig {
+            fields: vec![
+                OptionalConfigField::MaxNftsPerPair,
+                OptionalConfigField::PairConfigUpdateCooldownSeconds,
+                OptionalConfigField::TradingFeeRecipient,
+                OptionalConfigField::BurnSharePercent,
+                OptionalConfigField::MinTokenDeposit,
+                OptionalConfigField::MinNftCount,
+                OptionalConfigField::PairCreationFeeDiscountPercent,
+                OptionalConfigField::MinSpotPrice,
+                OptionalConfigField::MaxSpotPrice,
+                OptionalConfigField::ProbeCollectionFreeze,
+            ],
+        },
+    );
+    assert!(response.is_ok());
+
+    let cleared_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(infinity_global, &QueryMsg::GlobalConfig {})
+        .unwrap();
+    assert_eq!(
+        cleared_config,
+        GlobalConfig {
+            max_nfts_per_pair: None,
+            pair_config_update_cooldown_seconds: None,
+            trading_fee_recipient: None,
+            burn_share_percent: None,
+            min_token_deposit: None,
+            min_nft_count: None,
+            pair_creation_fee_discount_percent: None,
+            min_spot_price: None,
+            max_spot_price: None,
+            probe_collection_freeze: None,
+            ..global_config_response
+        }
+    );
 }
 
 #[test]
@@ -207,6 +388,16 @@ fn try_infinity_global_add_remove_min_prices() {
         default_royalty_fee_percent: Decimal::percent(10u64),
         max_royalty_fee_percent: Decimal::percent(15u64),
         max_swap_fee_percent: Decimal::percent(10u64),
+        max_nfts_per_pair: Some(100u64),
+        pair_config_update_cooldown_seconds: None,
+        trading_fee_recipient: None,
+        burn_share_percent: None,
+        min_token_deposit: None,
+        min_nft_count: None,
+        pair_creation_fee_discount_percent: None,
+        min_spot_price: None,
+        max_spot_price: None,
+        probe_collection_freeze: None,
     };
 
     let min_prices = vec![coin(1_000_000u128, NATIVE_DENOM)];
@@ -0,0 +1,128 @@
+use crate::helpers::nft_functions::{approve, mint_to};
+use crate::helpers::pair_functions::create_pair_with_deposits;
+use crate::setup::setup_accounts::MarketAccounts;
+use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
+
+use cosmwasm_std::{Addr, Uint128};
+use cw_multi_test::Executor;
+use infinity_global::{msg::QueryMsg as InfinityGlobalQueryMsg, GlobalConfig};
+use infinity_index::msg::QueryMsg as InfinityIndexQueryMsg;
+use infinity_index::state::CollectionStats;
+use infinity_pair::state::{BondingCurve, PairConfig, PairType};
+use infinity_router::msg::{ExecuteMsg as InfinityRouterExecuteMsg, SellOrder};
+use sg_std::NATIVE_DENOM;
+use test_suite::common_setup::msg::MinterTemplateResponse;
+
+#[test]
+fn try_collection_stats_across_pairs() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_index,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    for spot_price in [10_000_000u128, 9_500_000u128] {
+        create_pair_with_deposits(
+            &mut router,
+            &infinity_global,
+            &infinity_factory,
+            &minter,
+            &collection,
+            &creator,
+            &owner,
+            PairConfig {
+                pair_type: PairType::Token,
+                bonding_curve: BondingCurve::Linear {
+                    spot_price: Uint128::from(spot_price),
+                    delta: Uint128::from(1_000_000u128),
+                },
+                is_active: true,
+                asset_recipient: None,
+                auto_activate: false,
+                anti_sandwich: false,
+                token_recipient: None,
+                nft_recipient: None,
+                allow_buy: true,
+                allow_sell: true,
+            },
+            0u64,
+            Uint128::from(100_000_000u128),
+        );
+    }
+
+    let mut sell_orders: Vec<SellOrder> = vec![];
+    for _ in 0..3 {
+        let token_id = mint_to(&mut router, &creator.clone(), &bidder.clone(), &minter);
+        approve(
+            &mut router,
+            &bidder,
+            &collection,
+            &global_config.infinity_router,
+            token_id.clone(),
+        );
+        sell_orders.push(SellOrder {
+            input_token_id: token_id,
+            min_output: Uint128::one(),
+        });
+    }
+
+    // Swaps are routed to the first pair at 10_000_000 and 9_000_000,
+    // and to the second pair at 9_500_000
+    let response = router.execute_contract(
+        bidder.clone(),
+        global_config.infinity_router.clone(),
+        &InfinityRouterExecuteMsg::SwapNftsForTokens {
+            collection: collection.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            sell_orders,
+            min_total_output: None,
+            swap_params: None,
+            filter_sources: None,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let collection_stats = router
+        .wrap()
+        .query_wasm_smart::<CollectionStats>(
+            infinity_index,
+            &InfinityIndexQueryMsg::CollectionStats {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        collection_stats,
+        CollectionStats {
+            total_volume: Uint128::from(28_500_000u128),
+            total_swaps: 3u64,
+        }
+    );
+}
@@ -0,0 +1,92 @@
+use crate::helpers::pair_functions::create_pair_with_deposits;
+use crate::setup::setup_accounts::MarketAccounts;
+use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
+
+use cosmwasm_std::Uint128;
+use infinity_index::msg::{QueryMsg as InfinityIndexQueryMsg, QuoteSide};
+use infinity_pair::state::{BondingCurve, PairConfig, PairType};
+use sg_std::NATIVE_DENOM;
+use test_suite::common_setup::msg::MinterTemplateResponse;
+
+#[test]
+fn try_query_max_swaps_for_collection() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        ..
+                    },
+            },
+        infinity_global,
+        infinity_index,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let num_active_pairs = 3u64;
+    for idx in 0..(num_active_pairs + 1) {
+        create_pair_with_deposits(
+            &mut router,
+            &infinity_global,
+            &infinity_factory,
+            &minter,
+            &collection,
+            &creator,
+            &owner,
+            PairConfig {
+                pair_type: PairType::Token,
+                bonding_curve: BondingCurve::Linear {
+                    spot_price: Uint128::from(10_000_000u128),
+                    delta: Uint128::from(1_000_000u128),
+                },
+                // The last pair is inactive and should not be counted
+                is_active: idx < num_active_pairs,
+                asset_recipient: None,
+                auto_activate: false,
+                anti_sandwich: false,
+                token_recipient: None,
+                nft_recipient: None,
+                allow_buy: true,
+                allow_sell: true,
+            },
+            0u64,
+            Uint128::from(100_000_000u128),
+        );
+    }
+
+    let max_swaps = router
+        .wrap()
+        .query_wasm_smart::<u64>(
+            infinity_index.clone(),
+            &InfinityIndexQueryMsg::MaxSwapsForCollection {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                side: QuoteSide::SellToPair,
+            },
+        )
+        .unwrap();
+    assert_eq!(max_swaps, num_active_pairs);
+
+    let max_swaps = router
+        .wrap()
+        .query_wasm_smart::<u64>(
+            infinity_index,
+            &InfinityIndexQueryMsg::MaxSwapsForCollection {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                side: QuoteSide::BuyFromPair,
+            },
+        )
+        .unwrap();
+    assert_eq!(max_swaps, 0u64);
+}
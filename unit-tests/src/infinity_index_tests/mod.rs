@@ -0,0 +1,10 @@
+#[cfg(test)]
+mod collection_stats_tests;
+#[cfg(test)]
+mod max_swaps_tests;
+#[cfg(test)]
+mod pair_created_tests;
+#[cfg(test)]
+mod pair_quotes_tests;
+#[cfg(test)]
+mod total_value_locked_tests;
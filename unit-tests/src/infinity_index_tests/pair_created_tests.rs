@@ -0,0 +1,119 @@
+use crate::helpers::pair_functions::create_pair;
+use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
+
+use infinity_index::msg::{PairCreatedOffset, QueryMsg as InfinityIndexQueryMsg};
+use infinity_index::state::PairCreated;
+use infinity_pair::msg::QueryMsg as InfinityPairQueryMsg;
+use infinity_pair::pair::Pair;
+use sg_index_query::{QueryBound, QueryOptions};
+use sg_std::NATIVE_DENOM;
+use test_suite::common_setup::msg::MinterTemplateResponse;
+
+#[test]
+fn try_query_pairs_by_created_at() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts,
+            },
+        infinity_global,
+        infinity_index,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let first_created_at = router.block_info().time;
+    let (first_pair, _) =
+        create_pair(&mut router, &infinity_global, &infinity_factory, &collection, &accts.owner);
+
+    router.update_block(|block| block.time = block.time.plus_seconds(100));
+
+    let second_created_at = router.block_info().time;
+    let (second_pair, _) =
+        create_pair(&mut router, &infinity_global, &infinity_factory, &collection, &accts.owner);
+
+    // The creation time is stored on the pair
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(second_pair.clone(), &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert_eq!(pair.internal.created_at, Some(second_created_at));
+
+    // Pairs are ordered by creation time
+    let pairs_created = router
+        .wrap()
+        .query_wasm_smart::<Vec<PairCreated>>(
+            infinity_index.clone(),
+            &InfinityIndexQueryMsg::PairsByCreatedAt {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                query_options: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        pairs_created,
+        vec![
+            PairCreated {
+                address: first_pair.clone(),
+                collection: collection.clone(),
+                denom: NATIVE_DENOM.to_string(),
+                created_at: first_created_at,
+            },
+            PairCreated {
+                address: second_pair.clone(),
+                collection: collection.clone(),
+                denom: NATIVE_DENOM.to_string(),
+                created_at: second_created_at,
+            },
+        ]
+    );
+
+    // Newest pairs first, paginated
+    let pairs_created = router
+        .wrap()
+        .query_wasm_smart::<Vec<PairCreated>>(
+            infinity_index.clone(),
+            &InfinityIndexQueryMsg::PairsByCreatedAt {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                query_options: Some(QueryOptions {
+                    limit: Some(1),
+                    descending: Some(true),
+                    min: None,
+                    max: None,
+                }),
+            },
+        )
+        .unwrap();
+    assert_eq!(pairs_created.len(), 1);
+    assert_eq!(pairs_created[0].address, second_pair);
+
+    let pairs_created = router
+        .wrap()
+        .query_wasm_smart::<Vec<PairCreated>>(
+            infinity_index,
+            &InfinityIndexQueryMsg::PairsByCreatedAt {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                query_options: Some(QueryOptions {
+                    limit: Some(1),
+                    descending: Some(true),
+                    min: None,
+                    max: Some(QueryBound::Exclusive(PairCreatedOffset {
+                        pair: second_pair.to_string(),
+                        created_at: second_created_at,
+                    })),
+                }),
+            },
+        )
+        .unwrap();
+    assert_eq!(pairs_created.len(), 1);
+    assert_eq!(pairs_created[0].address, first_pair);
+}
@@ -0,0 +1,794 @@
+use crate::helpers::nft_functions::{approve, mint_to};
+use crate::helpers::pair_functions::create_pair_with_deposits;
+use crate::helpers::utils::assert_error;
+use crate::setup::setup_accounts::MarketAccounts;
+use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
+
+use cosmwasm_std::{coin, Addr, Decimal, Uint128};
+use cw_multi_test::Executor;
+use infinity_global::{
+    msg::{QueryMsg as InfinityGlobalQueryMsg, SudoMsg as InfinityGlobalSudoMsg},
+    GlobalConfig,
+};
+use infinity_index::msg::{PairQuoteOffset, QueryMsg as InfinityIndexQueryMsg, QuoteSide};
+use infinity_index::state::PairQuote;
+use infinity_pair::msg::ExecuteMsg as InfinityPairExecuteMsg;
+use infinity_pair::state::{BondingCurve, PairConfig, PairType};
+use infinity_router::msg::{
+    ExecuteMsg as InfinityRouterExecuteMsg, QueryMsg as InfinityRouterQueryMsg, SellOrder,
+};
+use infinity_router::nfts_for_tokens_iterators::types::NftForTokensQuote;
+use infinity_router::tokens_for_nfts_iterators::types::TokensForNftQuote;
+use infinity_shared::InfinityError;
+use sg_index_query::{QueryBound, QueryOptions};
+use sg_multi_test::StargazeApp;
+use sg_std::NATIVE_DENOM;
+use test_suite::common_setup::msg::MinterTemplateResponse;
+
+#[test]
+fn try_query_sell_to_pair_quotes_order() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        ..
+                    },
+            },
+        infinity_global,
+        infinity_index,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let mut expected_quotes: Vec<(Uint128, Addr)> = vec![];
+    for spot_price in [20_000_000u128, 10_000_000u128, 40_000_000u128, 30_000_000u128] {
+        let test_pair = create_pair_with_deposits(
+            &mut router,
+            &infinity_global,
+            &infinity_factory,
+            &minter,
+            &collection,
+            &creator,
+            &owner,
+            PairConfig {
+                pair_type: PairType::Token,
+                bonding_curve: BondingCurve::Linear {
+                    spot_price: Uint128::from(spot_price),
+                    delta: Uint128::from(1_000_000u128),
+                },
+                is_active: true,
+                asset_recipient: None,
+                auto_activate: false,
+                anti_sandwich: false,
+                token_recipient: None,
+                nft_recipient: None,
+                allow_buy: true,
+                allow_sell: true,
+            },
+            0u64,
+            Uint128::from(100_000_000u128),
+        );
+        let seller_amount =
+            test_pair.pair.internal.sell_to_pair_quote_summary.unwrap().seller_amount;
+        expected_quotes.push((seller_amount, test_pair.address));
+    }
+    // The best sell quotes are the highest, so the sell side is read in descending order
+    expected_quotes.sort();
+    expected_quotes.reverse();
+
+    let query_page = |cursor: Option<PairQuoteOffset>| {
+        router
+            .wrap()
+            .query_wasm_smart::<Vec<PairQuote>>(
+                infinity_index.clone(),
+                &InfinityIndexQueryMsg::SellToPairQuotes {
+                    collection: collection.to_string(),
+                    denom: NATIVE_DENOM.to_string(),
+                    query_options: Some(QueryOptions {
+                        limit: Some(3),
+                        descending: Some(true),
+                        min: None,
+                        max: cursor.map(QueryBound::Exclusive),
+                    }),
+                },
+            )
+            .unwrap()
+    };
+
+    // Pages follow the index order, do not overlap, and cover every quoting pair
+    let mut paged_quotes: Vec<(Uint128, Addr)> = vec![];
+    let mut cursor: Option<PairQuoteOffset> = None;
+    loop {
+        let page = query_page(cursor.clone());
+        if page.is_empty() {
+            break;
+        }
+        let last = page.last().unwrap();
+        cursor = Some(PairQuoteOffset {
+            pair: last.address.to_string(),
+            amount: last.quote.amount,
+        });
+        for pair_quote in page {
+            assert_eq!(pair_quote.collection, collection);
+            assert_eq!(pair_quote.quote.denom, NATIVE_DENOM);
+            paged_quotes.push((pair_quote.quote.amount, pair_quote.address));
+        }
+    }
+    assert_eq!(paged_quotes, expected_quotes);
+}
+
+#[test]
+fn try_pair_quotes_removed_when_unquotable_within_batch() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_index,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    let mut pairs = vec![];
+    for (pair_type, spot_price, num_nfts, num_tokens) in [
+        (PairType::Nft, 10_000_000u128, 2u64, 0u128),
+        (PairType::Nft, 20_000_000u128, 5u64, 0u128),
+        (PairType::Token, 20_000_000u128, 0u64, 25_000_000u128),
+        (PairType::Token, 10_000_000u128, 0u64, 100_000_000u128),
+    ] {
+        pairs.push(create_pair_with_deposits(
+            &mut router,
+            &infinity_global,
+            &infinity_factory,
+            &minter,
+            &collection,
+            &creator,
+            &owner,
+            PairConfig {
+                pair_type,
+                bonding_curve: BondingCurve::Linear {
+                    spot_price: Uint128::from(spot_price),
+                    delta: Uint128::from(1_000_000u128),
+                },
+                is_active: true,
+                asset_recipient: None,
+                auto_activate: false,
+                anti_sandwich: false,
+                token_recipient: None,
+                nft_recipient: None,
+                allow_buy: true,
+                allow_sell: true,
+            },
+            num_nfts,
+            Uint128::from(num_tokens),
+        ));
+    }
+
+    // Buy every NFT from the first pair, and one from the second, in a single batch
+    let buy_quotes = router
+        .wrap()
+        .query_wasm_smart::<Vec<TokensForNftQuote>>(
+            &global_config.infinity_router,
+            &InfinityRouterQueryMsg::TokensForNfts {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                limit: 3,
+                filter_sources: None,
+            },
+        )
+        .unwrap();
+    let max_inputs = buy_quotes.iter().map(|quote| quote.amount).collect::<Vec<Uint128>>();
+    let total_tokens = max_inputs.iter().sum::<Uint128>();
+    let response = router.execute_contract(
+        bidder.clone(),
+        global_config.infinity_router.clone(),
+        &InfinityRouterExecuteMsg::SwapTokensForNfts {
+            collection: collection.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            max_inputs,
+            min_nfts: None,
+            swap_params: None,
+            filter_sources: None,
+        },
+        &[coin(total_tokens.u128(), NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+
+    let buy_from_pair_quotes = router
+        .wrap()
+        .query_wasm_smart::<Vec<PairQuote>>(
+            infinity_index.clone(),
+            &InfinityIndexQueryMsg::BuyFromPairQuotes {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                query_options: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        buy_from_pair_quotes.iter().map(|pq| pq.address.clone()).collect::<Vec<Addr>>(),
+        vec![pairs[1].address.clone()]
+    );
+
+    // Sell two NFTs in a single batch, the first pair can only afford one
+    let sell_quotes = router
+        .wrap()
+        .query_wasm_smart::<Vec<NftForTokensQuote>>(
+            &global_config.infinity_router,
+            &InfinityRouterQueryMsg::NftsForTokens {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                limit: 2,
+                filter_sources: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(sell_quotes[0].address, pairs[2].address);
+
+    let mut sell_orders = vec![];
+    for quote in sell_quotes {
+        let token_id = mint_to(&mut router, &creator, &bidder, &minter);
+        approve(
+            &mut router,
+            &bidder,
+            &collection,
+            &global_config.infinity_router,
+            token_id.clone(),
+        );
+        sell_orders.push(SellOrder {
+            input_token_id: token_id,
+            min_output: quote.amount,
+        });
+    }
+    let response = router.execute_contract(
+        bidder,
+        global_config.infinity_router,
+        &InfinityRouterExecuteMsg::SwapNftsForTokens {
+            collection: collection.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            sell_orders,
+            min_total_output: None,
+            swap_params: None,
+            filter_sources: None,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let sell_to_pair_quotes = router
+        .wrap()
+        .query_wasm_smart::<Vec<PairQuote>>(
+            infinity_index,
+            &InfinityIndexQueryMsg::SellToPairQuotes {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                query_options: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        sell_to_pair_quotes.iter().map(|pq| pq.address.clone()).collect::<Vec<Addr>>(),
+        vec![pairs[3].address.clone()]
+    );
+}
+
+#[test]
+fn try_pair_quotes_min_deposit() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        ..
+                    },
+            },
+        infinity_global,
+        infinity_index,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let response = router.wasm_sudo(
+        infinity_global.clone(),
+        &InfinityGlobalSudoMsg::UpdateConfig {
+            fair_burn: None,
+            royalty_registry: None,
+            marketplace: None,
+            infinity_factory: None,
+            infinity_index: None,
+            infinity_router: None,
+            infinity_pair_code_id: None,
+            pair_creation_fee: None,
+            fair_burn_fee_percent: None,
+            default_royalty_fee_percent: None,
+            max_royalty_fee_percent: None,
+            max_swap_fee_percent: None,
+            max_nfts_per_pair: None,
+            pair_config_update_cooldown_seconds: None,
+            trading_fee_recipient: None,
+            burn_share_percent: None,
+            min_token_deposit: Some(Uint128::from(50_000_000u128)),
+            min_nft_count: Some(3u64),
+            pair_creation_fee_discount_percent: None,
+            min_spot_price: None,
+            max_spot_price: None,
+            probe_collection_freeze: None,
+        },
+    );
+    assert!(response.is_ok());
+
+    let mut pairs = vec![];
+    for (pair_type, num_nfts, num_tokens) in
+        [(PairType::Token, 0u64, 25_000_000u128), (PairType::Nft, 2u64, 0u128)]
+    {
+        pairs.push(create_pair_with_deposits(
+            &mut router,
+            &infinity_global,
+            &infinity_factory,
+            &minter,
+            &collection,
+            &creator,
+            &owner,
+            PairConfig {
+                pair_type,
+                bonding_curve: BondingCurve::Linear {
+                    spot_price: Uint128::from(10_000_000u128),
+                    delta: Uint128::from(1_000_000u128),
+                },
+                is_active: true,
+                asset_recipient: None,
+                auto_activate: false,
+                anti_sandwich: false,
+                token_recipient: None,
+                nft_recipient: None,
+                allow_buy: true,
+                allow_sell: true,
+            },
+            num_nfts,
+            Uint128::from(num_tokens),
+        ));
+    }
+
+    // Both pairs are quotable, but hold less than the minimum deposits
+    assert!(pairs[0].pair.internal.sell_to_pair_quote_summary.is_some());
+    assert!(pairs[1].pair.internal.buy_from_pair_quote_summary.is_some());
+
+    let sell_to_pair_quotes = router
+        .wrap()
+        .query_wasm_smart::<Vec<PairQuote>>(
+            infinity_index.clone(),
+            &InfinityIndexQueryMsg::SellToPairQuotes {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                query_options: None,
+            },
+        )
+        .unwrap();
+    assert!(sell_to_pair_quotes.is_empty());
+
+    let buy_from_pair_quotes = router
+        .wrap()
+        .query_wasm_smart::<Vec<PairQuote>>(
+            infinity_index.clone(),
+            &InfinityIndexQueryMsg::BuyFromPairQuotes {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                query_options: None,
+            },
+        )
+        .unwrap();
+    assert!(buy_from_pair_quotes.is_empty());
+
+    // Topping the pairs up to the minimum deposits adds them to the indices
+    let response = router.execute_contract(
+        owner.clone(),
+        pairs[0].address.clone(),
+        &InfinityPairExecuteMsg::DepositTokens {},
+        &[coin(25_000_000u128, NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+
+    let token_id = mint_to(&mut router, &creator, &owner, &minter);
+    approve(&mut router, &owner, &collection, &pairs[1].address, token_id.clone());
+    let response = router.execute_contract(
+        owner,
+        pairs[1].address.clone(),
+        &InfinityPairExecuteMsg::DepositNfts {
+            collection: collection.to_string(),
+            token_ids: vec![token_id],
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let sell_to_pair_quotes = router
+        .wrap()
+        .query_wasm_smart::<Vec<PairQuote>>(
+            infinity_index.clone(),
+            &InfinityIndexQueryMsg::SellToPairQuotes {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                query_options: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        sell_to_pair_quotes.iter().map(|pq| pq.address.clone()).collect::<Vec<Addr>>(),
+        vec![pairs[0].address.clone()]
+    );
+
+    let buy_from_pair_quotes = router
+        .wrap()
+        .query_wasm_smart::<Vec<PairQuote>>(
+            infinity_index,
+            &InfinityIndexQueryMsg::BuyFromPairQuotes {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                query_options: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        buy_from_pair_quotes.iter().map(|pq| pq.address.clone()).collect::<Vec<Addr>>(),
+        vec![pairs[1].address.clone()]
+    );
+}
+
+#[test]
+fn try_query_best_quotes() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        ..
+                    },
+            },
+        infinity_global,
+        infinity_index,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    // Token pairs quote the sell side, NFT pairs quote the buy side
+    let mut sell_quotes: Vec<(Uint128, Addr)> = vec![];
+    let mut buy_quotes: Vec<(Uint128, Addr)> = vec![];
+    for spot_price in [20_000_000u128, 10_000_000u128, 40_000_000u128, 30_000_000u128] {
+        for (pair_type, num_nfts, num_tokens) in [
+            (PairType::Token, 0u64, Uint128::from(100_000_000u128)),
+            (PairType::Nft, 1u64, Uint128::zero()),
+        ] {
+            let test_pair = create_pair_with_deposits(
+                &mut router,
+                &infinity_global,
+                &infinity_factory,
+                &minter,
+                &collection,
+                &creator,
+                &owner,
+                PairConfig {
+                    pair_type,
+                    bonding_curve: BondingCurve::Linear {
+                        spot_price: Uint128::from(spot_price),
+                        delta: Uint128::from(1_000_000u128),
+                    },
+                    is_active: true,
+                    asset_recipient: None,
+                    auto_activate: false,
+                    anti_sandwich: false,
+                    token_recipient: None,
+                    nft_recipient: None,
+                    allow_buy: true,
+                    allow_sell: true,
+                },
+                num_nfts,
+                num_tokens,
+            );
+            if let Some(quote_summary) = test_pair.pair.internal.sell_to_pair_quote_summary {
+                sell_quotes.push((quote_summary.seller_amount, test_pair.address.clone()));
+            }
+            if let Some(quote_summary) = test_pair.pair.internal.buy_from_pair_quote_summary {
+                buy_quotes.push((quote_summary.total(), test_pair.address));
+            }
+        }
+    }
+    assert_eq!(sell_quotes.len(), 4);
+    assert_eq!(buy_quotes.len(), 4);
+
+    // The highest sell quotes and the lowest buy quotes are the best
+    sell_quotes.sort();
+    sell_quotes.reverse();
+    buy_quotes.sort();
+
+    let query_best_quotes = |side: QuoteSide| {
+        router
+            .wrap()
+            .query_wasm_smart::<Vec<PairQuote>>(
+                infinity_index.clone(),
+                &InfinityIndexQueryMsg::BestQuotes {
+                    collection: collection.to_string(),
+                    denom: NATIVE_DENOM.to_string(),
+                    side,
+                    limit: 3,
+                },
+            )
+            .unwrap()
+            .into_iter()
+            .map(|pair_quote| (pair_quote.quote.amount, pair_quote.address))
+            .collect::<Vec<(Uint128, Addr)>>()
+    };
+
+    assert_eq!(query_best_quotes(QuoteSide::SellToPair), sell_quotes[..3].to_vec());
+    assert_eq!(query_best_quotes(QuoteSide::BuyFromPair), buy_quotes[..3].to_vec());
+}
+
+#[test]
+fn try_query_best_quotes_inactive_pair() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        ..
+                    },
+            },
+        infinity_global,
+        infinity_index,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: PairType::Trade {
+                swap_fee_percent: Decimal::zero(),
+                reinvest_tokens: false,
+                reinvest_nfts: false,
+                max_nfts: None,
+            },
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        3u64,
+        Uint128::from(100_000_000u128),
+    );
+
+    let query_best_quotes = |router: &StargazeApp, side: QuoteSide| {
+        router
+            .wrap()
+            .query_wasm_smart::<Vec<PairQuote>>(
+                infinity_index.clone(),
+                &InfinityIndexQueryMsg::BestQuotes {
+                    collection: collection.to_string(),
+                    denom: NATIVE_DENOM.to_string(),
+                    side,
+                    limit: 10,
+                },
+            )
+            .unwrap()
+            .into_iter()
+            .map(|pair_quote| pair_quote.address)
+            .collect::<Vec<Addr>>()
+    };
+
+    assert_eq!(query_best_quotes(&router, QuoteSide::SellToPair), vec![test_pair.address.clone()]);
+    assert_eq!(query_best_quotes(&router, QuoteSide::BuyFromPair), vec![test_pair.address.clone()]);
+
+    // Deactivating the pair removes both of its quotes from the index
+    let response = router.execute_contract(
+        owner,
+        test_pair.address,
+        &InfinityPairExecuteMsg::UpdatePairConfig {
+            is_active: Some(false),
+            pair_type: None,
+            bonding_curve: None,
+            asset_recipient: None,
+            auto_activate: None,
+            anti_sandwich: None,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: None,
+            allow_sell: None,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    assert!(query_best_quotes(&router, QuoteSide::SellToPair).is_empty());
+    assert!(query_best_quotes(&router, QuoteSide::BuyFromPair).is_empty());
+}
+
+#[test]
+fn try_query_best_quotes_buy_only_pair() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        ..
+                    },
+            },
+        infinity_global,
+        infinity_index,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    // The trade pair holds both assets, but only buys NFTs from users
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: PairType::Trade {
+                swap_fee_percent: Decimal::zero(),
+                reinvest_tokens: false,
+                reinvest_nfts: false,
+                max_nfts: None,
+            },
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: false,
+        },
+        3u64,
+        Uint128::from(100_000_000u128),
+    );
+    assert!(test_pair.pair.internal.sell_to_pair_quote_summary.is_some());
+    assert!(test_pair.pair.internal.buy_from_pair_quote_summary.is_none());
+
+    let query_best_quotes = |router: &StargazeApp, side: QuoteSide| {
+        router
+            .wrap()
+            .query_wasm_smart::<Vec<PairQuote>>(
+                infinity_index.clone(),
+                &InfinityIndexQueryMsg::BestQuotes {
+                    collection: collection.to_string(),
+                    denom: NATIVE_DENOM.to_string(),
+                    side,
+                    limit: 10,
+                },
+            )
+            .unwrap()
+            .into_iter()
+            .map(|pair_quote| pair_quote.address)
+            .collect::<Vec<Addr>>()
+    };
+
+    assert_eq!(query_best_quotes(&router, QuoteSide::SellToPair), vec![test_pair.address.clone()]);
+    assert!(query_best_quotes(&router, QuoteSide::BuyFromPair).is_empty());
+
+    // A pair must trade in at least one direction
+    let update_directions_msg =
+        |allow_buy: bool, allow_sell: bool| InfinityPairExecuteMsg::UpdatePairConfig {
+            is_active: None,
+            pair_type: None,
+            bonding_curve: None,
+            asset_recipient: None,
+            auto_activate: None,
+            anti_sandwich: None,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: Some(allow_buy),
+            allow_sell: Some(allow_sell),
+        };
+    let response = router.execute_contract(
+        owner.clone(),
+        test_pair.address.clone(),
+        &update_directions_msg(false, false),
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput("pair must allow buying or selling NFTs".to_string())
+            .to_string(),
+    );
+
+    // Flipping the pair to sell only moves it to the other side of the index
+    let response = router.execute_contract(
+        owner,
+        test_pair.address.clone(),
+        &update_directions_msg(false, true),
+        &[],
+    );
+    assert!(response.is_ok());
+
+    assert!(query_best_quotes(&router, QuoteSide::SellToPair).is_empty());
+    assert_eq!(query_best_quotes(&router, QuoteSide::BuyFromPair), vec![test_pair.address]);
+}
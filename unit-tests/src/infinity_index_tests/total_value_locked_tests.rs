@@ -0,0 +1,165 @@
+use crate::helpers::pair_functions::create_pair_with_deposits;
+use crate::setup::setup_accounts::MarketAccounts;
+use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
+
+use cosmwasm_std::{coin, Decimal, Uint128};
+use cw_multi_test::Executor;
+use infinity_index::msg::{QueryMsg as InfinityIndexQueryMsg, TotalValueLockedResponse};
+use infinity_pair::msg::ExecuteMsg as InfinityPairExecuteMsg;
+use infinity_pair::state::{BondingCurve, PairConfig, PairType};
+use sg_multi_test::StargazeApp;
+use sg_std::NATIVE_DENOM;
+use test_suite::common_setup::msg::MinterTemplateResponse;
+
+#[test]
+fn try_query_total_value_locked() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        ..
+                    },
+            },
+        infinity_global,
+        infinity_index,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let query_total_value_locked = |router: &StargazeApp| {
+        router
+            .wrap()
+            .query_wasm_smart::<TotalValueLockedResponse>(
+                infinity_index.clone(),
+                &InfinityIndexQueryMsg::TotalValueLocked {},
+            )
+            .unwrap()
+    };
+
+    let total_value_locked = query_total_value_locked(&router);
+    assert_eq!(
+        total_value_locked,
+        TotalValueLockedResponse {
+            total_tokens: vec![],
+            total_nfts: 0u64,
+        }
+    );
+
+    let mut test_pairs = vec![];
+    for (pair_type, num_nfts, num_tokens) in [
+        (PairType::Token, 0u64, 10_000_000u128),
+        (PairType::Nft, 3u64, 0u128),
+        (
+            PairType::Trade {
+                swap_fee_percent: Decimal::zero(),
+                reinvest_tokens: false,
+                reinvest_nfts: false,
+                max_nfts: None,
+            },
+            2u64,
+            5_000_000u128,
+        ),
+    ] {
+        test_pairs.push(create_pair_with_deposits(
+            &mut router,
+            &infinity_global,
+            &infinity_factory,
+            &minter,
+            &collection,
+            &creator,
+            &owner,
+            PairConfig {
+                pair_type,
+                bonding_curve: BondingCurve::Linear {
+                    spot_price: Uint128::from(1_000_000u128),
+                    delta: Uint128::from(100_000u128),
+                },
+                is_active: true,
+                asset_recipient: None,
+                auto_activate: false,
+                anti_sandwich: false,
+                token_recipient: None,
+                nft_recipient: None,
+                allow_buy: true,
+                allow_sell: true,
+            },
+            num_nfts,
+            Uint128::from(num_tokens),
+        ));
+    }
+
+    // The total is the sum of the assets deposited into every pair
+    let total_value_locked = query_total_value_locked(&router);
+    assert_eq!(
+        total_value_locked,
+        TotalValueLockedResponse {
+            total_tokens: vec![coin(15_000_000u128, NATIVE_DENOM)],
+            total_nfts: 5u64,
+        }
+    );
+
+    // Withdrawals decrement the total
+    let response = router.execute_contract(
+        owner.clone(),
+        test_pairs[0].address.clone(),
+        &InfinityPairExecuteMsg::WithdrawTokens {
+            funds: vec![coin(4_000_000u128, NATIVE_DENOM)],
+            asset_recipient: None,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let response = router.execute_contract(
+        owner.clone(),
+        test_pairs[1].address.clone(),
+        &InfinityPairExecuteMsg::WithdrawNfts {
+            collection: collection.to_string(),
+            token_ids: vec![test_pairs[1].token_ids[0].clone()],
+            asset_recipient: None,
+            keep_active: None,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let total_value_locked = query_total_value_locked(&router);
+    assert_eq!(
+        total_value_locked,
+        TotalValueLockedResponse {
+            total_tokens: vec![coin(11_000_000u128, NATIVE_DENOM)],
+            total_nfts: 4u64,
+        }
+    );
+
+    // Closing a pair removes all of its assets from the total
+    let response = router.execute_contract(
+        owner,
+        test_pairs[2].address.clone(),
+        &InfinityPairExecuteMsg::CloseAndWithdraw {
+            nft_limit: None,
+            asset_recipient: None,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let total_value_locked = query_total_value_locked(&router);
+    assert_eq!(
+        total_value_locked,
+        TotalValueLockedResponse {
+            total_tokens: vec![coin(6_000_000u128, NATIVE_DENOM)],
+            total_nfts: 2u64,
+        }
+    );
+}
@@ -0,0 +1,137 @@
+use crate::helpers::nft_functions::{approve, mint_to};
+use crate::helpers::pair_functions::create_pair_with_deposits;
+use crate::setup::setup_accounts::MarketAccounts;
+use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
+
+use cosmwasm_std::{coin, Addr, Uint128};
+use cw_multi_test::Executor;
+use infinity_global::{msg::QueryMsg as InfinityGlobalQueryMsg, GlobalConfig};
+use infinity_pair::msg::{ExecuteMsg as InfinityPairExecuteMsg, QueryMsg as InfinityPairQueryMsg};
+use infinity_pair::pair::Pair;
+use infinity_pair::state::{BondingCurve, PairConfig, PairType};
+use infinity_router::msg::QueryMsg as InfinityRouterQueryMsg;
+use infinity_router::nfts_for_tokens_iterators::types::NftForTokensQuote;
+use sg_std::NATIVE_DENOM;
+use test_suite::common_setup::msg::MinterTemplateResponse;
+
+#[test]
+fn try_auto_activate_token_pair() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: PairType::Token,
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: true,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        0u64,
+        Uint128::from(25_000_000u128),
+    );
+
+    let query_sell_quotes = |router: &sg_multi_test::StargazeApp| {
+        router
+            .wrap()
+            .query_wasm_smart::<Vec<NftForTokensQuote>>(
+                &global_config.infinity_router,
+                &InfinityRouterQueryMsg::NftsForTokens {
+                    collection: collection.to_string(),
+                    denom: NATIVE_DENOM.to_string(),
+                    limit: 10,
+                    filter_sources: None,
+                },
+            )
+            .unwrap()
+    };
+
+    // Swapping until the pair can no longer afford the spot price deactivates the pair
+    for _ in 0..2 {
+        let token_id = mint_to(&mut router, &creator.clone(), &bidder.clone(), &minter);
+        approve(&mut router, &bidder, &collection, &test_pair.address, token_id.clone());
+
+        let response = router.execute_contract(
+            bidder.clone(),
+            test_pair.address.clone(),
+            &InfinityPairExecuteMsg::SwapNftForTokens {
+                token_id,
+                min_output: coin(1u128, NATIVE_DENOM),
+                asset_recipient: None,
+            },
+            &[],
+        );
+        assert!(response.is_ok());
+    }
+
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(test_pair.address.clone(), &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert!(!pair.config.is_active);
+    assert_eq!(pair.total_tokens, Uint128::from(6_000_000u128));
+    assert!(pair.internal.sell_to_pair_quote_summary.is_none());
+    assert!(query_sell_quotes(&router).is_empty());
+
+    // Depositing tokens so that the pair can afford the spot price reactivates the pair
+    let response = router.execute_contract(
+        owner.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::DepositTokens {},
+        &[coin(10_000_000u128, NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(test_pair.address.clone(), &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert!(pair.config.is_active);
+    assert!(pair.internal.sell_to_pair_quote_summary.is_some());
+
+    let sell_quotes = query_sell_quotes(&router);
+    assert_eq!(sell_quotes.len(), 1);
+    assert_eq!(sell_quotes[0].address, test_pair.address);
+}
@@ -1,30 +1,1339 @@
 use crate::helpers::nft_functions::{approve_all, assert_nft_owner, mint_to, transfer};
-use crate::helpers::pair_functions::{create_pair, create_pair_with_deposits};
+use crate::helpers::pair_functions::{
+    create_pair, create_pair_with_deposits, create_pair_with_type,
+};
 use crate::helpers::utils::assert_error;
 use crate::setup::setup_accounts::MarketAccounts;
+use crate::setup::setup_contracts::{setup_mock_collection, MockCollectionExecuteMsg};
 use crate::setup::setup_infinity_contracts::UOSMO;
 use crate::setup::templates::{
     minter_two_collections, setup_infinity_test, standard_minter_template, InfinityTestSetup,
 };
 
 use cosmwasm_std::{coin, Addr, Decimal, Uint128};
-use cw_multi_test::Executor;
-use infinity_pair::msg::{ExecuteMsg as InfinityPairExecuteMsg, QueryMsg as InfinityPairQueryMsg};
+use cw721::{Cw721QueryMsg, TokensResponse};
+use cw_multi_test::{AppResponse, Executor};
+use infinity_global::msg::SudoMsg as InfinityGlobalSudoMsg;
+use infinity_index::msg::{QueryMsg as InfinityIndexQueryMsg, TotalValueLockedResponse};
+use infinity_index::state::PairQuote;
+use infinity_pair::msg::{
+    ExecuteMsg as InfinityPairExecuteMsg, NftDepositsResponse, PreviewCloseResponse,
+    QueryMsg as InfinityPairQueryMsg,
+};
 use infinity_pair::pair::Pair;
 use infinity_pair::state::{BondingCurve, PairConfig, PairType};
 use infinity_shared::InfinityError;
+use sg_index_query::{QueryBound, QueryOptions};
+use sg_multi_test::StargazeApp;
 use sg_std::NATIVE_DENOM;
 use test_suite::common_setup::msg::MinterTemplateResponse;
 
 #[test]
-fn try_deposit_nft() {
+fn try_deposit_nft() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts,
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let (pair_addr, _pair) = create_pair_with_type(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &collection,
+        &accts.owner,
+        PairType::Nft,
+    );
+
+    let token_id = mint_to(&mut router, &accts.creator, &accts.owner, &minter);
+
+    approve_all(&mut router, &accts.owner, &collection, &pair_addr);
+    let response = router.execute_contract(
+        accts.owner,
+        pair_addr.clone(),
+        &InfinityPairExecuteMsg::DepositNfts {
+            collection: collection.to_string(),
+            token_ids: vec![token_id.clone()],
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    assert_nft_owner(&router, &collection, token_id, &pair_addr);
+
+    let pair =
+        router.wrap().query_wasm_smart::<Pair>(pair_addr, &InfinityPairQueryMsg::Pair {}).unwrap();
+    assert!(pair.internal.total_nfts == 1);
+}
+
+#[test]
+fn try_deposit_nfts_max_nfts_per_pair() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts,
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let max_nfts_per_pair = 5u64;
+    let response = router.wasm_sudo(
+        infinity_global.clone(),
+        &InfinityGlobalSudoMsg::UpdateConfig {
+            fair_burn: None,
+            royalty_registry: None,
+            marketplace: None,
+            infinity_factory: None,
+            infinity_index: None,
+            infinity_router: None,
+            infinity_pair_code_id: None,
+            pair_creation_fee: None,
+            fair_burn_fee_percent: None,
+            default_royalty_fee_percent: None,
+            max_royalty_fee_percent: None,
+            max_swap_fee_percent: None,
+            max_nfts_per_pair: Some(max_nfts_per_pair),
+            pair_config_update_cooldown_seconds: None,
+            trading_fee_recipient: None,
+            burn_share_percent: None,
+            min_token_deposit: None,
+            min_nft_count: None,
+            pair_creation_fee_discount_percent: None,
+            min_spot_price: None,
+            max_spot_price: None,
+            probe_collection_freeze: None,
+        },
+    );
+    assert!(response.is_ok());
+
+    let (pair_addr, _pair) = create_pair_with_type(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &collection,
+        &accts.owner,
+        PairType::Nft,
+    );
+
+    let mut token_ids: Vec<String> = vec![];
+    for _ in 0..(max_nfts_per_pair + 1) {
+        let token_id = mint_to(&mut router, &accts.creator.clone(), &accts.owner.clone(), &minter);
+        token_ids.push(token_id);
+    }
+
+    approve_all(&mut router, &accts.owner.clone(), &collection, &pair_addr);
+
+    // Owner can deposit up to the cap
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        pair_addr.clone(),
+        &InfinityPairExecuteMsg::DepositNfts {
+            collection: collection.to_string(),
+            token_ids: token_ids[0..max_nfts_per_pair as usize].to_vec(),
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    // Owner cannot deposit past the cap
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        pair_addr.clone(),
+        &InfinityPairExecuteMsg::DepositNfts {
+            collection: collection.to_string(),
+            token_ids: vec![token_ids[max_nfts_per_pair as usize].clone()],
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput(format!(
+            "pair cannot hold more than {} nfts",
+            max_nfts_per_pair
+        ))
+        .to_string(),
+    );
+
+    let pair =
+        router.wrap().query_wasm_smart::<Pair>(pair_addr, &InfinityPairQueryMsg::Pair {}).unwrap();
+    assert_eq!(pair.internal.total_nfts, max_nfts_per_pair);
+}
+
+#[test]
+fn try_deposit_nfts_batch_validation() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts,
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let (pair_addr, _pair) = create_pair_with_type(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &collection,
+        &accts.owner,
+        PairType::Nft,
+    );
+
+    let owned_token_id = mint_to(&mut router, &accts.creator, &accts.owner, &minter);
+    let other_token_id = mint_to(&mut router, &accts.creator, &accts.bidder, &minter);
+
+    approve_all(&mut router, &accts.owner, &collection, &pair_addr);
+    approve_all(&mut router, &accts.bidder, &collection, &pair_addr);
+
+    // A batch with an NFT not owned by the sender reverts entirely
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        pair_addr.clone(),
+        &InfinityPairExecuteMsg::DepositNfts {
+            collection: collection.to_string(),
+            token_ids: vec![owned_token_id.clone(), other_token_id.clone()],
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::Unauthorized("sender is not the owner of the nft".to_string()).to_string(),
+    );
+    assert_nft_owner(&router, &collection, owned_token_id.clone(), &accts.owner);
+    assert_nft_owner(&router, &collection, other_token_id, &accts.bidder);
+
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(pair_addr.clone(), &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert_eq!(pair.internal.total_nfts, 0);
+
+    // A batch with a duplicate token id is rejected
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        pair_addr.clone(),
+        &InfinityPairExecuteMsg::DepositNfts {
+            collection: collection.to_string(),
+            token_ids: vec![owned_token_id.clone(), owned_token_id.clone()],
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput(format!("duplicate token_ids: {}", owned_token_id)).to_string(),
+    );
+
+    // A batch larger than the deposit limit is rejected
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        pair_addr.clone(),
+        &InfinityPairExecuteMsg::DepositNfts {
+            collection: collection.to_string(),
+            token_ids: (0..101).map(|idx| idx.to_string()).collect(),
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput("cannot deposit more than 100 nfts at once".to_string())
+            .to_string(),
+    );
+
+    // A valid batch is deposited and the count is emitted
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        pair_addr.clone(),
+        &InfinityPairExecuteMsg::DepositNfts {
+            collection: collection.to_string(),
+            token_ids: vec![owned_token_id.clone()],
+        },
+        &[],
+    );
+    let deposit_event =
+        response.unwrap().events.into_iter().find(|event| event.ty == "wasm-deposit-nfts").unwrap();
+    assert!(deposit_event.attributes.iter().any(|attr| attr.key == "count" && attr.value == "1"));
+    assert_nft_owner(&router, &collection, owned_token_id, &pair_addr);
+
+    let pair =
+        router.wrap().query_wasm_smart::<Pair>(pair_addr, &InfinityPairQueryMsg::Pair {}).unwrap();
+    assert_eq!(pair.internal.total_nfts, 1);
+}
+
+#[test]
+fn try_deposit_nfts_duplicate_and_deposited_ids() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts,
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let (pair_addr, _pair) = create_pair_with_type(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &collection,
+        &accts.owner,
+        PairType::Nft,
+    );
+
+    let mut token_ids: Vec<String> = vec![];
+    for _ in 0..4 {
+        let token_id = mint_to(&mut router, &accts.creator, &accts.owner, &minter);
+        token_ids.push(token_id);
+    }
+    approve_all(&mut router, &accts.owner, &collection, &pair_addr);
+
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        pair_addr.clone(),
+        &InfinityPairExecuteMsg::DepositNfts {
+            collection: collection.to_string(),
+            token_ids: token_ids[0..2].to_vec(),
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    // Every duplicated token id is listed
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        pair_addr.clone(),
+        &InfinityPairExecuteMsg::DepositNfts {
+            collection: collection.to_string(),
+            token_ids: vec![
+                token_ids[2].clone(),
+                token_ids[3].clone(),
+                token_ids[2].clone(),
+                token_ids[3].clone(),
+                token_ids[2].clone(),
+            ],
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput(format!(
+            "duplicate token_ids: {}, {}",
+            token_ids[2], token_ids[3]
+        ))
+        .to_string(),
+    );
+
+    // Every token id already held by the pair is listed
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        pair_addr.clone(),
+        &InfinityPairExecuteMsg::DepositNfts {
+            collection: collection.to_string(),
+            token_ids: vec![token_ids[0].clone(), token_ids[2].clone(), token_ids[1].clone()],
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput(format!(
+            "nfts already deposited: {}, {}",
+            token_ids[0], token_ids[1]
+        ))
+        .to_string(),
+    );
+    assert_nft_owner(&router, &collection, token_ids[2].clone(), &accts.owner);
+
+    let pair =
+        router.wrap().query_wasm_smart::<Pair>(pair_addr, &InfinityPairQueryMsg::Pair {}).unwrap();
+    assert_eq!(pair.internal.total_nfts, 2);
+}
+
+#[test]
+fn try_withdraw_nfts() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts,
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let (pair_addr, _pair) = create_pair_with_type(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &collection,
+        &accts.owner,
+        PairType::Nft,
+    );
+
+    let num_nfts: usize = 10;
+    let mut token_ids: Vec<String> = vec![];
+    for _ in 0..num_nfts {
+        let token_id = mint_to(&mut router, &accts.creator.clone(), &accts.owner.clone(), &minter);
+        token_ids.push(token_id);
+    }
+
+    approve_all(&mut router, &accts.owner.clone(), &collection, &pair_addr);
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        pair_addr.clone(),
+        &InfinityPairExecuteMsg::DepositNfts {
+            collection: collection.to_string(),
+            token_ids: token_ids.clone(),
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(pair_addr.clone(), &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert!(pair.internal.total_nfts == num_nfts as u64);
+
+    let withdraw_nfts = token_ids[0..num_nfts / 2].to_vec();
+
+    // Non owner cannot withdraw
+    let response = router.execute_contract(
+        accts.creator.clone(),
+        pair_addr.clone(),
+        &InfinityPairExecuteMsg::WithdrawNfts {
+            collection: collection.to_string(),
+            token_ids: withdraw_nfts.clone(),
+            asset_recipient: None,
+            keep_active: None,
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::Unauthorized("sender is not the owner of the pair".to_string()).to_string(),
+    );
+
+    // Withdraw half of the token ids
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        pair_addr.clone(),
+        &InfinityPairExecuteMsg::WithdrawNfts {
+            collection: collection.to_string(),
+            token_ids: withdraw_nfts.clone(),
+            asset_recipient: None,
+            keep_active: None,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    for withdraw_token_id in withdraw_nfts {
+        assert_nft_owner(&router, &collection, withdraw_token_id, &accts.owner.clone());
+    }
+
+    // Non owner cannot withdraw any
+    let response = router.execute_contract(
+        accts.creator.clone(),
+        pair_addr.clone(),
+        &InfinityPairExecuteMsg::WithdrawAnyNfts {
+            collection: collection.to_string(),
+            limit: Some(100u32),
+            asset_recipient: None,
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::Unauthorized("sender is not the owner of the pair".to_string()).to_string(),
+    );
+
+    // Withdraw rest of the token ids to asset recipient
+    let asset_recipient = Addr::unchecked("asset_recipient");
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        pair_addr.clone(),
+        &InfinityPairExecuteMsg::WithdrawAnyNfts {
+            collection: collection.to_string(),
+            limit: Some(100u32),
+            asset_recipient: Some(asset_recipient.to_string()),
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let withdraw_nfts = token_ids[num_nfts / 2..].to_vec();
+    for withdraw_token_id in withdraw_nfts {
+        assert_nft_owner(&router, &collection, withdraw_token_id, &asset_recipient.clone());
+    }
+
+    let pair =
+        router.wrap().query_wasm_smart::<Pair>(pair_addr, &InfinityPairQueryMsg::Pair {}).unwrap();
+    assert!(pair.internal.total_nfts == 0);
+}
+
+#[test]
+fn try_withdraw_nfts_keep_active() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        ..
+                    },
+            },
+        infinity_global,
+        infinity_index,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: PairType::Nft,
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: false,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        4u64,
+        Uint128::zero(),
+    );
+
+    let query_buy_from_pair_quotes = |router: &StargazeApp| {
+        router
+            .wrap()
+            .query_wasm_smart::<Vec<PairQuote>>(
+                infinity_index.clone(),
+                &InfinityIndexQueryMsg::BuyFromPairQuotes {
+                    collection: collection.to_string(),
+                    denom: NATIVE_DENOM.to_string(),
+                    query_options: None,
+                },
+            )
+            .unwrap()
+    };
+    assert!(query_buy_from_pair_quotes(&router).is_empty());
+
+    // By default withdrawing leaves the pair inactive
+    let response = router.execute_contract(
+        owner.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::WithdrawNfts {
+            collection: collection.to_string(),
+            token_ids: vec![test_pair.token_ids[0].clone()],
+            asset_recipient: None,
+            keep_active: None,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(test_pair.address.clone(), &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert!(!pair.config.is_active);
+    assert!(query_buy_from_pair_quotes(&router).is_empty());
+
+    // Keeping the pair active with NFTs remaining puts it in the buy from pair index
+    let response = router.execute_contract(
+        owner.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::WithdrawNfts {
+            collection: collection.to_string(),
+            token_ids: vec![test_pair.token_ids[1].clone()],
+            asset_recipient: None,
+            keep_active: Some(true),
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(test_pair.address.clone(), &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert!(pair.config.is_active);
+    assert_eq!(pair.internal.total_nfts, 2u64);
+    let buy_from_pair_quotes = query_buy_from_pair_quotes(&router);
+    assert_eq!(buy_from_pair_quotes.len(), 1);
+    assert_eq!(buy_from_pair_quotes[0].address, test_pair.address);
+
+    // Once the last NFTs are withdrawn the pair can no longer quote, so it is deactivated
+    let response = router.execute_contract(
+        owner.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::WithdrawNfts {
+            collection: collection.to_string(),
+            token_ids: test_pair.token_ids[2..].to_vec(),
+            asset_recipient: None,
+            keep_active: Some(true),
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(test_pair.address, &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert!(!pair.config.is_active);
+    assert!(query_buy_from_pair_quotes(&router).is_empty());
+}
+
+#[test]
+fn try_withdraw_any_nfts_with_limit() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts,
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let (pair_addr, _pair) = create_pair_with_type(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &collection,
+        &accts.owner,
+        PairType::Nft,
+    );
+
+    let num_nfts: usize = 7;
+    let mut token_ids: Vec<String> = vec![];
+    for _ in 0..num_nfts {
+        let token_id = mint_to(&mut router, &accts.creator.clone(), &accts.owner.clone(), &minter);
+        token_ids.push(token_id);
+    }
+
+    approve_all(&mut router, &accts.owner.clone(), &collection, &pair_addr);
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        pair_addr.clone(),
+        &InfinityPairExecuteMsg::DepositNfts {
+            collection: collection.to_string(),
+            token_ids: token_ids.clone(),
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    // Withdraw a batch of 3 NFTs
+    let response = router
+        .execute_contract(
+            accts.owner.clone(),
+            pair_addr.clone(),
+            &InfinityPairExecuteMsg::WithdrawAnyNfts {
+                collection: collection.to_string(),
+                limit: Some(3u32),
+                asset_recipient: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    let num_withdrawn = response
+        .events
+        .iter()
+        .flat_map(|e| e.attributes.iter())
+        .find(|a| a.key == "num_withdrawn")
+        .unwrap();
+    assert_eq!(num_withdrawn.value, "3");
+
+    let owner_tokens = router
+        .wrap()
+        .query_wasm_smart::<TokensResponse>(
+            collection.clone(),
+            &Cw721QueryMsg::Tokens {
+                owner: accts.owner.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(owner_tokens.tokens.len(), 3);
+
+    let pair_tokens = router
+        .wrap()
+        .query_wasm_smart::<TokensResponse>(
+            collection,
+            &Cw721QueryMsg::Tokens {
+                owner: pair_addr.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(pair_tokens.tokens.len(), 4);
+
+    let pair =
+        router.wrap().query_wasm_smart::<Pair>(pair_addr, &InfinityPairQueryMsg::Pair {}).unwrap();
+    assert_eq!(pair.internal.total_nfts, 4u64);
+}
+
+#[test]
+fn try_query_nft_deposits_pagination() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts,
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let (pair_addr, _pair) = create_pair_with_type(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &collection,
+        &accts.owner,
+        PairType::Nft,
+    );
+
+    let mut token_ids: Vec<String> = vec![];
+    for _ in 0..7 {
+        let token_id = mint_to(&mut router, &accts.creator.clone(), &accts.owner.clone(), &minter);
+        token_ids.push(token_id);
+    }
+    let mut sorted_token_ids = token_ids.clone();
+    sorted_token_ids.sort();
+
+    // Deposit in an order that differs from token id order
+    let mut deposit_token_ids = sorted_token_ids.clone();
+    deposit_token_ids.reverse();
+    deposit_token_ids.swap(0, 3);
+
+    approve_all(&mut router, &accts.owner.clone(), &collection, &pair_addr);
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        pair_addr.clone(),
+        &InfinityPairExecuteMsg::DepositNfts {
+            collection: collection.to_string(),
+            token_ids: deposit_token_ids,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let query_page = |descending: bool, cursor: Option<String>| {
+        let (min, max) = match descending {
+            false => (cursor.map(QueryBound::Exclusive), None),
+            true => (None, cursor.map(QueryBound::Exclusive)),
+        };
+        router
+            .wrap()
+            .query_wasm_smart::<NftDepositsResponse>(
+                pair_addr.clone(),
+                &InfinityPairQueryMsg::NftDeposits {
+                    query_options: Some(QueryOptions {
+                        limit: Some(3),
+                        descending: Some(descending),
+                        min,
+                        max,
+                    }),
+                },
+            )
+            .unwrap()
+            .token_ids
+    };
+
+    for descending in [false, true] {
+        let mut expected_token_ids = sorted_token_ids.clone();
+        if descending {
+            expected_token_ids.reverse();
+        }
+
+        // Pages are ordered, do not overlap, and cover every deposited token id
+        let mut paged_token_ids: Vec<String> = vec![];
+        let mut cursor: Option<String> = None;
+        loop {
+            let page = query_page(descending, cursor.clone());
+            if page.is_empty() {
+                break;
+            }
+            assert!(page.len() <= 3);
+            cursor = page.last().cloned();
+            paged_token_ids.extend(page);
+        }
+        assert_eq!(paged_token_ids, expected_token_ids);
+
+        // Repeated queries return the same page
+        assert_eq!(query_page(descending, None), query_page(descending, None));
+    }
+}
+
+#[test]
+fn try_withdraw_other_collection_nfts() {
+    let vt = minter_two_collections(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        ..
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: PairType::Trade {
+                swap_fee_percent: Decimal::zero(),
+                reinvest_tokens: false,
+                reinvest_nfts: false,
+                max_nfts: None,
+            },
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        10u64,
+        Uint128::from(100_000_000u128),
+    );
+    assert!(test_pair.pair.internal.sell_to_pair_quote_summary.is_some());
+    assert!(test_pair.pair.internal.buy_from_pair_quote_summary.is_some(),);
+
+    // Send NFT from other collection to pair
+    let other_collection = &collection_response_vec[1].collection.clone().unwrap();
+    let other_minter = &collection_response_vec[1].minter.clone().unwrap();
+    let token_id = mint_to(&mut router, &creator.clone(), &owner.clone(), other_minter);
+    transfer(&mut router, &owner, &test_pair.address, other_collection, &token_id);
+    assert_nft_owner(&router, other_collection, token_id.clone(), &test_pair.address);
+
+    // Non owner cannot withdraw other collection nfts
+    let response = router.execute_contract(
+        creator.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::WithdrawNfts {
+            collection: other_collection.to_string(),
+            token_ids: vec![token_id.clone()],
+            asset_recipient: None,
+            keep_active: None,
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::Unauthorized("sender is not the owner of the pair".to_string()).to_string(),
+    );
+
+    // Owner can withdraw other collection nfts
+    let response = router.execute_contract(
+        owner.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::WithdrawNfts {
+            collection: other_collection.to_string(),
+            token_ids: vec![token_id.clone()],
+            asset_recipient: None,
+            keep_active: None,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+    assert_nft_owner(&router, other_collection, token_id, &owner);
+
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(test_pair.address.clone(), &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert_eq!(test_pair.pair, pair);
+}
+
+#[test]
+fn try_withdraw_nfts_skips_failed_transfers() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        ..
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: PairType::Trade {
+                swap_fee_percent: Decimal::zero(),
+                reinvest_tokens: false,
+                reinvest_nfts: false,
+                max_nfts: None,
+            },
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        3u64,
+        Uint128::from(100_000_000u128),
+    );
+
+    // The pair holds NFTs of a collection that refuses to transfer one of them
+    let frozen_token_id = "2".to_string();
+    let mock_collection =
+        setup_mock_collection(&mut router, &creator, vec![frozen_token_id.clone()]);
+    let token_ids: Vec<String> = vec!["1".to_string(), frozen_token_id.clone(), "3".to_string()];
+    for token_id in &token_ids {
+        let response = router.execute_contract(
+            creator.clone(),
+            mock_collection.clone(),
+            &MockCollectionExecuteMsg::Mint {
+                token_id: token_id.clone(),
+                owner: test_pair.address.to_string(),
+            },
+            &[],
+        );
+        assert!(response.is_ok());
+    }
+
+    let response = router.execute_contract(
+        owner.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::WithdrawNfts {
+            collection: mock_collection.to_string(),
+            token_ids: token_ids.clone(),
+            asset_recipient: None,
+            keep_active: None,
+        },
+        &[],
+    );
+    let failed_event = response
+        .unwrap()
+        .events
+        .into_iter()
+        .find(|event| event.ty == "wasm-withdraw-nfts-failed")
+        .unwrap();
+    assert!(failed_event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "failed_token_ids" && attr.value == frozen_token_id));
+
+    // The other NFTs are withdrawn, the frozen NFT stays in the pair
+    for token_id in token_ids {
+        let expected_owner = if token_id == frozen_token_id {
+            &test_pair.address
+        } else {
+            &owner
+        };
+        assert_nft_owner(&router, &mock_collection, token_id, expected_owner);
+    }
+
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(test_pair.address.clone(), &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert_eq!(test_pair.pair, pair);
+}
+
+#[test]
+fn try_withdraw_nfts_settles_from_transferred_nfts() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        ..
+                    },
+                ..
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let mock_collection = setup_mock_collection(&mut router, &creator, vec![]);
+    let (pair_addr, _pair) =
+        create_pair(&mut router, &infinity_global, &infinity_factory, &mock_collection, &owner);
+
+    let update_pair_config_msg = |pair_type: Option<PairType>, is_active: Option<bool>| {
+        InfinityPairExecuteMsg::UpdatePairConfig {
+            is_active,
+            pair_type,
+            bonding_curve: None,
+            asset_recipient: None,
+            auto_activate: None,
+            anti_sandwich: None,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: None,
+            allow_sell: None,
+        }
+    };
+    let response = router.execute_contract(
+        owner.clone(),
+        pair_addr.clone(),
+        &update_pair_config_msg(
+            Some(PairType::Trade {
+                swap_fee_percent: Decimal::zero(),
+                reinvest_tokens: false,
+                reinvest_nfts: false,
+                max_nfts: None,
+            }),
+            None,
+        ),
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let token_ids: Vec<String> = vec!["1".to_string(), "2".to_string(), "3".to_string()];
+    for token_id in &token_ids {
+        let response = router.execute_contract(
+            creator.clone(),
+            mock_collection.clone(),
+            &MockCollectionExecuteMsg::Mint {
+                token_id: token_id.clone(),
+                owner: owner.to_string(),
+            },
+            &[],
+        );
+        assert!(response.is_ok());
+    }
+    let response = router.execute_contract(
+        owner.clone(),
+        mock_collection.clone(),
+        &MockCollectionExecuteMsg::ApproveAll {
+            operator: pair_addr.to_string(),
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let response = router.execute_contract(
+        owner.clone(),
+        pair_addr.clone(),
+        &InfinityPairExecuteMsg::DepositNfts {
+            collection: mock_collection.to_string(),
+            token_ids: token_ids.clone(),
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let response = router.execute_contract(
+        owner.clone(),
+        pair_addr.clone(),
+        &update_pair_config_msg(None, Some(true)),
+        &[],
+    );
+    assert!(response.is_ok());
+
+    // One of the deposited NFTs can no longer be transferred
+    let frozen_token_id = "2".to_string();
+    let response = router.execute_contract(
+        creator,
+        mock_collection.clone(),
+        &MockCollectionExecuteMsg::FreezeToken {
+            token_id: frozen_token_id.clone(),
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let response = router
+        .execute_contract(
+            owner.clone(),
+            pair_addr.clone(),
+            &InfinityPairExecuteMsg::WithdrawNfts {
+                collection: mock_collection.to_string(),
+                token_ids: token_ids.clone(),
+                asset_recipient: None,
+                keep_active: Some(true),
+            },
+            &[],
+        )
+        .unwrap();
+
+    // Counts and events only report the NFTs that were transferred
+    let num_withdrawn = response
+        .events
+        .iter()
+        .flat_map(|e| e.attributes.iter())
+        .find(|a| a.key == "num_withdrawn")
+        .unwrap();
+    assert_eq!(num_withdrawn.value, "2");
+
+    let withdraw_event =
+        response.events.iter().find(|event| event.ty == "wasm-withdraw-nfts").unwrap();
+    let withdrawn_token_ids = withdraw_event
+        .attributes
+        .iter()
+        .filter(|attr| attr.key == "token_id")
+        .map(|attr| attr.value.clone())
+        .collect::<Vec<String>>();
+    assert_eq!(withdrawn_token_ids, vec!["1".to_string(), "3".to_string()]);
+    assert!(withdraw_event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "total_nfts" && attr.value == "1"));
+
+    for token_id in token_ids {
+        let expected_owner = if token_id == frozen_token_id {
+            &pair_addr
+        } else {
+            &owner
+        };
+        assert_nft_owner(&router, &mock_collection, token_id, expected_owner);
+    }
+
+    // The restored NFT keeps the pair quotable, so it remains active
+    let pair =
+        router.wrap().query_wasm_smart::<Pair>(pair_addr, &InfinityPairQueryMsg::Pair {}).unwrap();
+    assert!(pair.config.is_active);
+    assert_eq!(pair.internal.total_nfts, 1);
+    assert!(pair.internal.buy_from_pair_quote_summary.is_some());
+}
+
+#[test]
+fn try_deposit_nfts_frozen_collection() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        ..
+                    },
+                ..
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let response = router.wasm_sudo(
+        infinity_global.clone(),
+        &InfinityGlobalSudoMsg::UpdateConfig {
+            fair_burn: None,
+            royalty_registry: None,
+            marketplace: None,
+            infinity_factory: None,
+            infinity_index: None,
+            infinity_router: None,
+            infinity_pair_code_id: None,
+            pair_creation_fee: None,
+            fair_burn_fee_percent: None,
+            default_royalty_fee_percent: None,
+            max_royalty_fee_percent: None,
+            max_swap_fee_percent: None,
+            max_nfts_per_pair: None,
+            pair_config_update_cooldown_seconds: None,
+            trading_fee_recipient: None,
+            burn_share_percent: None,
+            min_token_deposit: None,
+            min_nft_count: None,
+            pair_creation_fee_discount_percent: None,
+            min_spot_price: None,
+            max_spot_price: None,
+            probe_collection_freeze: Some(true),
+        },
+    );
+    assert!(response.is_ok());
+
+    let mock_collection = setup_mock_collection(&mut router, &creator, vec![]);
+    let token_id = "1".to_string();
+    let response = router.execute_contract(
+        creator.clone(),
+        mock_collection.clone(),
+        &MockCollectionExecuteMsg::Mint {
+            token_id: token_id.clone(),
+            owner: owner.to_string(),
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let (pair_addr, _pair) = create_pair_with_type(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &mock_collection,
+        &owner,
+        PairType::Nft,
+    );
+
+    // The collection disables transfers after the pair is created
+    let response = router.execute_contract(
+        creator,
+        mock_collection.clone(),
+        &MockCollectionExecuteMsg::Freeze {},
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let response = router.execute_contract(
+        owner.clone(),
+        pair_addr,
+        &InfinityPairExecuteMsg::DepositNfts {
+            collection: mock_collection.to_string(),
+            token_ids: vec![token_id.clone()],
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput(format!("collection {} transfers are frozen", mock_collection))
+            .to_string(),
+    );
+    assert_nft_owner(&router, &mock_collection, token_id, &owner);
+}
+
+#[test]
+fn try_withdraw_stray_nft() {
     let vt = standard_minter_template(1000u32);
     let InfinityTestSetup {
         vending_template:
             MinterTemplateResponse {
                 collection_response_vec,
                 mut router,
-                accts,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
             },
         infinity_global,
         infinity_factory,
@@ -35,32 +1344,93 @@ fn try_deposit_nft() {
     let minter = collection_resp.minter.clone().unwrap();
     let collection = collection_resp.collection.clone().unwrap();
 
-    let (pair_addr, _pair) =
-        create_pair(&mut router, &infinity_global, &infinity_factory, &collection, &accts.owner);
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: PairType::Nft,
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        2u64,
+        Uint128::zero(),
+    );
 
-    let token_id = mint_to(&mut router, &accts.creator, &accts.owner, &minter);
+    // An NFT of the pair's collection transferred without DepositNfts is not tracked by the pair
+    let stray_token_id = mint_to(&mut router, &creator, &bidder, &minter);
+    transfer(&mut router, &bidder, &test_pair.address, &collection, &stray_token_id);
+    assert_nft_owner(&router, &collection, stray_token_id.clone(), &test_pair.address);
 
-    approve_all(&mut router, &accts.owner, &collection, &pair_addr);
+    let nft_deposits = router
+        .wrap()
+        .query_wasm_smart::<NftDepositsResponse>(
+            test_pair.address.clone(),
+            &InfinityPairQueryMsg::NftDeposits {
+                query_options: None,
+            },
+        )
+        .unwrap();
+    assert!(!nft_deposits.token_ids.contains(&stray_token_id));
+
+    // Non owner cannot recover the stray NFT
     let response = router.execute_contract(
-        accts.owner,
-        pair_addr.clone(),
-        &InfinityPairExecuteMsg::DepositNfts {
+        bidder.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::WithdrawNfts {
             collection: collection.to_string(),
-            token_ids: vec![token_id.clone()],
+            token_ids: vec![stray_token_id.clone()],
+            asset_recipient: Some(bidder.to_string()),
+            keep_active: None,
         },
         &[],
     );
-    assert!(response.is_ok());
+    assert_error(
+        response,
+        InfinityError::Unauthorized("sender is not the owner of the pair".to_string()).to_string(),
+    );
 
-    assert_nft_owner(&router, &collection, token_id, &pair_addr);
+    // Owner can recover the stray NFT, leaving the deposited NFTs untouched
+    let response = router.execute_contract(
+        owner,
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::WithdrawNfts {
+            collection: collection.to_string(),
+            token_ids: vec![stray_token_id.clone()],
+            asset_recipient: Some(bidder.to_string()),
+            keep_active: None,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+    assert_nft_owner(&router, &collection, stray_token_id, &bidder);
 
-    let pair =
-        router.wrap().query_wasm_smart::<Pair>(pair_addr, &InfinityPairQueryMsg::Pair {}).unwrap();
-    assert!(pair.internal.total_nfts == 1);
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(test_pair.address.clone(), &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert_eq!(test_pair.pair, pair);
+    for token_id in test_pair.token_ids {
+        assert_nft_owner(&router, &collection, token_id, &test_pair.address);
+    }
 }
 
 #[test]
-fn try_withdraw_nfts() {
+fn try_close_and_withdraw() {
     let vt = standard_minter_template(1000u32);
     let InfinityTestSetup {
         vending_template:
@@ -70,6 +1440,7 @@ fn try_withdraw_nfts() {
                 accts,
             },
         infinity_global,
+        infinity_index,
         infinity_factory,
         ..
     } = setup_infinity_test(vt).unwrap();
@@ -78,123 +1449,135 @@ fn try_withdraw_nfts() {
     let minter = collection_resp.minter.clone().unwrap();
     let collection = collection_resp.collection.clone().unwrap();
 
-    let (pair_addr, _pair) =
-        create_pair(&mut router, &infinity_global, &infinity_factory, &collection, &accts.owner);
-
-    let num_nfts: usize = 10;
-    let mut token_ids: Vec<String> = vec![];
-    for _ in 0..num_nfts {
-        let token_id = mint_to(&mut router, &accts.creator.clone(), &accts.owner.clone(), &minter);
-        token_ids.push(token_id);
-    }
-
-    approve_all(&mut router, &accts.owner.clone(), &collection, &pair_addr);
-    let response = router.execute_contract(
-        accts.owner.clone(),
-        pair_addr.clone(),
-        &InfinityPairExecuteMsg::DepositNfts {
-            collection: collection.to_string(),
-            token_ids: token_ids.clone(),
+    let num_nfts = 5u64;
+    let num_tokens = Uint128::from(100_000_000u128);
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &accts.creator,
+        &accts.owner,
+        PairConfig {
+            pair_type: PairType::Trade {
+                swap_fee_percent: Decimal::zero(),
+                reinvest_tokens: false,
+                reinvest_nfts: false,
+                max_nfts: None,
+            },
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
         },
-        &[],
+        num_nfts,
+        num_tokens,
     );
-    assert!(response.is_ok());
-
-    let pair = router
-        .wrap()
-        .query_wasm_smart::<Pair>(pair_addr.clone(), &InfinityPairQueryMsg::Pair {})
-        .unwrap();
-    assert!(pair.internal.total_nfts == num_nfts as u64);
-
-    let withdraw_nfts = token_ids[0..num_nfts / 2].to_vec();
 
-    // Non owner cannot withdraw
+    // Only the owner can close the pair
     let response = router.execute_contract(
-        accts.creator.clone(),
-        pair_addr.clone(),
-        &InfinityPairExecuteMsg::WithdrawNfts {
-            collection: collection.to_string(),
-            token_ids: withdraw_nfts.clone(),
+        accts.bidder.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::CloseAndWithdraw {
+            nft_limit: None,
             asset_recipient: None,
         },
         &[],
     );
-    assert_error(
-        response,
-        InfinityError::Unauthorized("sender is not the owner of the pair".to_string()).to_string(),
-    );
+    assert!(response.is_err());
 
-    // Withdraw half of the token ids
+    let owner_balance_before =
+        router.wrap().query_balance(&accts.owner, NATIVE_DENOM).unwrap().amount;
+
+    let close_and_withdraw_msg = InfinityPairExecuteMsg::CloseAndWithdraw {
+        nft_limit: Some(3),
+        asset_recipient: None,
+    };
     let response = router.execute_contract(
         accts.owner.clone(),
-        pair_addr.clone(),
-        &InfinityPairExecuteMsg::WithdrawNfts {
-            collection: collection.to_string(),
-            token_ids: withdraw_nfts.clone(),
-            asset_recipient: None,
-        },
+        test_pair.address.clone(),
+        &close_and_withdraw_msg,
         &[],
     );
-    assert!(response.is_ok());
+    let close_event = response
+        .unwrap()
+        .events
+        .into_iter()
+        .find(|event| event.ty == "wasm-close-and-withdraw")
+        .unwrap();
+    assert!(close_event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "remaining_nft_count" && attr.value == "2"));
 
-    for withdraw_token_id in withdraw_nfts {
-        assert_nft_owner(&router, &collection, withdraw_token_id, &accts.owner.clone());
-    }
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(test_pair.address.clone(), &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert!(!pair.config.is_active);
+    assert_eq!(pair.internal.total_nfts, 2);
+    assert_eq!(pair.total_tokens, Uint128::zero());
+    assert!(pair.internal.sell_to_pair_quote_summary.is_none());
+    assert!(pair.internal.buy_from_pair_quote_summary.is_none());
 
-    // Non owner cannot withdraw any
-    let response = router.execute_contract(
-        accts.creator.clone(),
-        pair_addr.clone(),
-        &InfinityPairExecuteMsg::WithdrawAnyNfts {
-            collection: collection.to_string(),
-            limit: 100u32,
-            asset_recipient: None,
-        },
-        &[],
-    );
-    assert_error(
-        response,
-        InfinityError::Unauthorized("sender is not the owner of the pair".to_string()).to_string(),
+    // The index is updated once the tokens have left the pair
+    let total_value_locked = router
+        .wrap()
+        .query_wasm_smart::<TotalValueLockedResponse>(
+            infinity_index,
+            &InfinityIndexQueryMsg::TotalValueLocked {},
+        )
+        .unwrap();
+    assert_eq!(
+        total_value_locked,
+        TotalValueLockedResponse {
+            total_tokens: vec![],
+            total_nfts: 2u64,
+        }
     );
 
-    // Withdraw rest of the token ids to asset recipient
-    let asset_recipient = Addr::unchecked("asset_recipient");
+    let owner_balance_after =
+        router.wrap().query_balance(&accts.owner, NATIVE_DENOM).unwrap().amount;
+    assert_eq!(owner_balance_after - owner_balance_before, num_tokens);
+
+    // Repeated calls drain the remaining NFTs
     let response = router.execute_contract(
         accts.owner.clone(),
-        pair_addr.clone(),
-        &InfinityPairExecuteMsg::WithdrawAnyNfts {
-            collection: collection.to_string(),
-            limit: 100u32,
-            asset_recipient: Some(asset_recipient.to_string()),
-        },
+        test_pair.address.clone(),
+        &close_and_withdraw_msg,
         &[],
     );
     assert!(response.is_ok());
 
-    let withdraw_nfts = token_ids[num_nfts / 2..].to_vec();
-    for withdraw_token_id in withdraw_nfts {
-        assert_nft_owner(&router, &collection, withdraw_token_id, &asset_recipient.clone());
-    }
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(test_pair.address, &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert_eq!(pair.internal.total_nfts, 0);
 
-    let pair =
-        router.wrap().query_wasm_smart::<Pair>(pair_addr, &InfinityPairQueryMsg::Pair {}).unwrap();
-    assert!(pair.internal.total_nfts == 0);
+    for token_id in test_pair.token_ids {
+        assert_nft_owner(&router, &collection, token_id, &accts.owner);
+    }
 }
 
 #[test]
-fn try_withdraw_other_collection_nfts() {
-    let vt = minter_two_collections(1000u32);
+fn try_preview_close() {
+    let vt = standard_minter_template(1000u32);
     let InfinityTestSetup {
         vending_template:
             MinterTemplateResponse {
                 collection_response_vec,
                 mut router,
-                accts:
-                    MarketAccounts {
-                        creator,
-                        owner,
-                        ..
-                    },
+                accts,
             },
         infinity_global,
         infinity_factory,
@@ -205,19 +1588,21 @@ fn try_withdraw_other_collection_nfts() {
     let minter = collection_resp.minter.clone().unwrap();
     let collection = collection_resp.collection.clone().unwrap();
 
+    let num_tokens = Uint128::from(100_000_000u128);
     let test_pair = create_pair_with_deposits(
         &mut router,
         &infinity_global,
         &infinity_factory,
         &minter,
         &collection,
-        &creator,
-        &owner,
+        &accts.creator,
+        &accts.owner,
         PairConfig {
             pair_type: PairType::Trade {
                 swap_fee_percent: Decimal::zero(),
                 reinvest_tokens: false,
                 reinvest_nfts: false,
+                max_nfts: None,
             },
             bonding_curve: BondingCurve::Linear {
                 spot_price: Uint128::from(10_000_000u128),
@@ -225,55 +1610,71 @@ fn try_withdraw_other_collection_nfts() {
             },
             is_active: true,
             asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
         },
-        10u64,
-        Uint128::from(100_000_000u128),
+        5u64,
+        num_tokens,
     );
-    assert!(test_pair.pair.internal.sell_to_pair_quote_summary.is_some());
-    assert!(test_pair.pair.internal.buy_from_pair_quote_summary.is_some(),);
 
-    // Send NFT from other collection to pair
-    let other_collection = &collection_response_vec[1].collection.clone().unwrap();
-    let other_minter = &collection_response_vec[1].minter.clone().unwrap();
-    let token_id = mint_to(&mut router, &creator.clone(), &owner.clone(), other_minter);
-    transfer(&mut router, &owner, &test_pair.address, other_collection, &token_id);
-    assert_nft_owner(&router, other_collection, token_id.clone(), &test_pair.address);
+    let preview = router
+        .wrap()
+        .query_wasm_smart::<PreviewCloseResponse>(
+            test_pair.address.clone(),
+            &InfinityPairQueryMsg::PreviewClose {
+                nft_limit: Some(3),
+            },
+        )
+        .unwrap();
+    assert_eq!(preview.tokens_to_return, vec![coin(num_tokens.u128(), NATIVE_DENOM)]);
+    assert_eq!(preview.nft_ids_this_batch.len(), 3);
+    assert_eq!(preview.remaining_after, 2);
 
-    // Non owner cannot withdraw other collection nfts
-    let response = router.execute_contract(
-        creator.clone(),
-        test_pair.address.clone(),
-        &InfinityPairExecuteMsg::WithdrawNfts {
-            collection: other_collection.to_string(),
-            token_ids: vec![token_id.clone()],
-            asset_recipient: None,
-        },
-        &[],
-    );
-    assert_error(
-        response,
-        InfinityError::Unauthorized("sender is not the owner of the pair".to_string()).to_string(),
-    );
+    let owner_balance_before =
+        router.wrap().query_balance(&accts.owner, NATIVE_DENOM).unwrap().amount;
 
-    // Owner can withdraw other collection nfts
     let response = router.execute_contract(
-        owner.clone(),
+        accts.owner.clone(),
         test_pair.address.clone(),
-        &InfinityPairExecuteMsg::WithdrawNfts {
-            collection: other_collection.to_string(),
-            token_ids: vec![token_id.clone()],
+        &InfinityPairExecuteMsg::CloseAndWithdraw {
+            nft_limit: Some(3),
             asset_recipient: None,
         },
         &[],
     );
     assert!(response.is_ok());
-    assert_nft_owner(&router, other_collection, token_id, &owner);
+
+    // The withdrawal matches the preview
+    let owner_balance_after =
+        router.wrap().query_balance(&accts.owner, NATIVE_DENOM).unwrap().amount;
+    assert_eq!(owner_balance_after - owner_balance_before, preview.tokens_to_return[0].amount);
+    for token_id in &preview.nft_ids_this_batch {
+        assert_nft_owner(&router, &collection, token_id.to_string(), &accts.owner);
+    }
 
     let pair = router
         .wrap()
         .query_wasm_smart::<Pair>(test_pair.address.clone(), &InfinityPairQueryMsg::Pair {})
         .unwrap();
-    assert_eq!(test_pair.pair, pair);
+    assert_eq!(pair.internal.total_nfts, preview.remaining_after);
+
+    // The next batch holds the remaining NFTs and no tokens
+    let preview = router
+        .wrap()
+        .query_wasm_smart::<PreviewCloseResponse>(
+            test_pair.address,
+            &InfinityPairQueryMsg::PreviewClose {
+                nft_limit: Some(3),
+            },
+        )
+        .unwrap();
+    assert!(preview.tokens_to_return.is_empty());
+    assert_eq!(preview.nft_ids_this_batch.len(), 2);
+    assert_eq!(preview.remaining_after, 0);
 }
 
 #[test]
@@ -423,6 +1824,69 @@ fn try_withdraw_tokens() {
     assert_eq!(pair.total_tokens.u128(), 0u128);
 }
 
+#[test]
+fn try_token_transfer_events_include_denom() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts,
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let (pair_addr, pair) =
+        create_pair(&mut router, &infinity_global, &infinity_factory, &collection, &accts.owner);
+
+    let assert_event_denom = |response: &AppResponse, event_type: &str| {
+        let event = response.events.iter().find(|e| e.ty == event_type).unwrap();
+        let denom = event.attributes.iter().find(|a| a.key == "denom").unwrap();
+        assert_eq!(denom.value, pair.immutable.denom);
+    };
+
+    let response = router
+        .execute_contract(
+            accts.owner.clone(),
+            pair_addr.clone(),
+            &InfinityPairExecuteMsg::DepositTokens {},
+            &[coin(100_000_000u128, NATIVE_DENOM)],
+        )
+        .unwrap();
+    assert_event_denom(&response, "wasm-deposit-tokens");
+
+    let response = router
+        .execute_contract(
+            accts.owner.clone(),
+            pair_addr.clone(),
+            &InfinityPairExecuteMsg::WithdrawTokens {
+                funds: vec![coin(10_000_000u128, NATIVE_DENOM)],
+                asset_recipient: None,
+            },
+            &[],
+        )
+        .unwrap();
+    assert_event_denom(&response, "wasm-withdraw-tokens");
+
+    let response = router
+        .execute_contract(
+            accts.owner,
+            pair_addr,
+            &InfinityPairExecuteMsg::WithdrawAllTokens {
+                asset_recipient: None,
+            },
+            &[],
+        )
+        .unwrap();
+    assert_event_denom(&response, "wasm-withdraw-tokens");
+}
+
 #[test]
 fn try_withdraw_other_denom_tokens() {
     let vt = minter_two_collections(1000u32);
@@ -460,6 +1924,7 @@ fn try_withdraw_other_denom_tokens() {
                 swap_fee_percent: Decimal::zero(),
                 reinvest_tokens: false,
                 reinvest_nfts: false,
+                max_nfts: None,
             },
             bonding_curve: BondingCurve::Linear {
                 spot_price: Uint128::from(10_000_000u128),
@@ -467,6 +1932,12 @@ fn try_withdraw_other_denom_tokens() {
             },
             is_active: true,
             asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
         },
         10u64,
         Uint128::from(100_000_000u128),
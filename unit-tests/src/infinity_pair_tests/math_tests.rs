@@ -0,0 +1,122 @@
+use cosmwasm_std::{Decimal, Uint128};
+use infinity_pair::math::{
+    calc_exponential_spot_price_user_submits_nft, calc_exponential_spot_price_user_submits_tokens,
+    calc_exponential_trade_buy_from_pair_price, validate_exponential_delta,
+};
+use infinity_shared::InfinityError;
+
+#[test]
+fn try_exponential_spot_price_bps_delta() {
+    let delta = Decimal::bps(500u64);
+
+    // Each step multiplies the spot price by 1.05
+    let mut spot_price = Uint128::from(10_000_000u128);
+    for expected_spot_price in [10_500_000u128, 11_025_000u128, 11_576_250u128] {
+        spot_price = calc_exponential_spot_price_user_submits_tokens(spot_price, delta).unwrap();
+        assert_eq!(spot_price, Uint128::from(expected_spot_price));
+    }
+
+    // Each step divides the spot price by 1.05
+    for expected_spot_price in [11_025_000u128, 10_500_000u128, 10_000_000u128] {
+        spot_price = calc_exponential_spot_price_user_submits_nft(spot_price, delta).unwrap();
+        assert_eq!(spot_price, Uint128::from(expected_spot_price));
+    }
+}
+
+#[test]
+fn try_exponential_delta_out_of_range() {
+    let expected_error =
+        InfinityError::InvalidInput("exponential delta cannot exceed 10000 bps".to_string())
+            .to_string();
+
+    assert!(validate_exponential_delta(Decimal::bps(10_000u64)).is_ok());
+
+    let delta = Decimal::bps(10_001u64);
+    assert_eq!(validate_exponential_delta(delta).unwrap_err().to_string(), expected_error);
+
+    // Pairs stored with a delta above the bound keep quoting
+    let spot_price = Uint128::from(10_000_000u128);
+    assert_eq!(
+        calc_exponential_spot_price_user_submits_tokens(spot_price, delta).unwrap(),
+        Uint128::from(20_001_000u128)
+    );
+    assert_eq!(
+        calc_exponential_spot_price_user_submits_nft(spot_price, delta).unwrap(),
+        Uint128::from(4_999_750u128)
+    );
+    assert_eq!(
+        calc_exponential_trade_buy_from_pair_price(spot_price, delta).unwrap(),
+        Uint128::from(20_001_000u128)
+    );
+}
+
+#[test]
+fn try_exponential_spot_price_small_prices() {
+    // A 1% step on a spot price of 1 still moves the price up by a whole unit
+    let delta = Decimal::percent(1);
+    let spot_price =
+        calc_exponential_spot_price_user_submits_tokens(Uint128::one(), delta).unwrap();
+    assert_eq!(spot_price, Uint128::from(2u128));
+
+    // 2 / 1.01 = 1.98, rounded down
+    let spot_price = calc_exponential_spot_price_user_submits_nft(spot_price, delta).unwrap();
+    assert_eq!(spot_price, Uint128::one());
+
+    // 1 bps on a spot price of 3 rounds up to 4 rather than staying at 3
+    let delta = Decimal::bps(1u64);
+    let spot_price =
+        calc_exponential_spot_price_user_submits_tokens(Uint128::from(3u128), delta).unwrap();
+    assert_eq!(spot_price, Uint128::from(4u128));
+
+    // 1 bps on a spot price of 999 rounds down to 998 rather than staying at 999
+    let spot_price =
+        calc_exponential_spot_price_user_submits_nft(Uint128::from(999u128), delta).unwrap();
+    assert_eq!(spot_price, Uint128::from(998u128));
+
+    let buy_from_pair_price =
+        calc_exponential_trade_buy_from_pair_price(Uint128::from(3u128), delta).unwrap();
+    assert_eq!(buy_from_pair_price, Uint128::from(4u128));
+}
+
+#[test]
+fn try_exponential_spot_price_large_prices() {
+    let delta = Decimal::percent(5);
+
+    let spot_price = Uint128::from(100_000_000_000_000_000_000u128);
+    let spot_price_user_submits_tokens =
+        calc_exponential_spot_price_user_submits_tokens(spot_price, delta).unwrap();
+    assert_eq!(spot_price_user_submits_tokens, Uint128::from(105_000_000_000_000_000_000u128));
+
+    let spot_price_user_submits_nft =
+        calc_exponential_spot_price_user_submits_nft(spot_price, delta).unwrap();
+    assert_eq!(spot_price_user_submits_nft, Uint128::from(95_238_095_238_095_238_095u128));
+
+    let buy_from_pair_price =
+        calc_exponential_trade_buy_from_pair_price(spot_price, delta).unwrap();
+    assert_eq!(buy_from_pair_price, Uint128::from(105_000_000_000_000_000_000u128));
+
+    // Prices above the Decimal range, such as 2000 tokens of an 18 decimal denom, still quote
+    let delta = Decimal::percent(2);
+    let spot_price = Uint128::from(2_000_000_000_000_000_000_000u128);
+    let spot_price_user_submits_tokens =
+        calc_exponential_spot_price_user_submits_tokens(spot_price, delta).unwrap();
+    assert_eq!(spot_price_user_submits_tokens, Uint128::from(2_040_000_000_000_000_000_000u128));
+
+    let spot_price_user_submits_nft =
+        calc_exponential_spot_price_user_submits_nft(spot_price, delta).unwrap();
+    assert_eq!(spot_price_user_submits_nft, Uint128::from(1_960_784_313_725_490_196_078u128));
+
+    let buy_from_pair_price =
+        calc_exponential_trade_buy_from_pair_price(spot_price, delta).unwrap();
+    assert_eq!(buy_from_pair_price, Uint128::from(2_040_000_000_000_000_000_000u128));
+
+    // Prices that overflow Uint128 error, which deactivates the pair
+    let spot_price = Uint128::from(u128::MAX);
+    assert!(calc_exponential_spot_price_user_submits_tokens(spot_price, delta).is_err());
+    assert!(calc_exponential_trade_buy_from_pair_price(spot_price, delta).is_err());
+
+    // Falling prices never overflow
+    let spot_price_user_submits_nft =
+        calc_exponential_spot_price_user_submits_nft(spot_price, delta).unwrap();
+    assert!(spot_price_user_submits_nft < spot_price);
+}
@@ -1,12 +1,22 @@
 #[cfg(test)]
+mod auto_activate_tests;
+#[cfg(test)]
 mod deposit_assets_tests;
 #[cfg(test)]
+mod math_tests;
+#[cfg(test)]
 mod nft_pair_swap_tests;
 #[cfg(test)]
 mod pair_creation_tests;
 #[cfg(test)]
 mod pair_quote_tests;
 #[cfg(test)]
+mod price_oracle_tests;
+#[cfg(test)]
+mod price_snapshot_tests;
+#[cfg(test)]
 mod token_pair_swap_tests;
 #[cfg(test)]
 mod trade_pair_swap_tests;
+#[cfg(test)]
+mod trait_filter_tests;
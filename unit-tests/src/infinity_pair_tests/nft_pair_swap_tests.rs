@@ -1,17 +1,23 @@
-use crate::helpers::nft_functions::{approve, assert_nft_owner, mint_to};
+use crate::helpers::nft_functions::{approve, approve_all, assert_nft_owner, mint_to};
 use crate::helpers::pair_functions::create_pair_with_deposits;
 use crate::helpers::utils::assert_error;
 use crate::setup::setup_accounts::{setup_addtl_account, MarketAccounts, INITIAL_BALANCE};
 use crate::setup::setup_infinity_contracts::UOSMO;
-use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
+use crate::setup::templates::{
+    setup_infinity_test, standard_minter_template, zero_royalty_minter_template, InfinityTestSetup,
+};
 
 use cosmwasm_std::{coin, Addr, Decimal, Uint128};
 use cw_multi_test::Executor;
+use infinity_factory::msg::ExecuteMsg as InfinityFactoryExecuteMsg;
 use infinity_global::{msg::QueryMsg as InfinityGlobalQueryMsg, GlobalConfig};
 use infinity_pair::msg::{ExecuteMsg as InfinityPairExecuteMsg, QueryMsg as InfinityPairQueryMsg};
 use infinity_pair::pair::Pair;
-use infinity_pair::state::{BondingCurve, PairConfig, PairType, QuoteSummary, TokenPayment};
+use infinity_pair::state::{
+    BondingCurve, PairConfig, PairImmutable, PairType, QuoteSummary, TokenPayment,
+};
 use infinity_pair::ContractError;
+use infinity_router::msg::ExecuteMsg as InfinityRouterExecuteMsg;
 use infinity_shared::InfinityError;
 use sg721_base::msg::{CollectionInfoResponse, QueryMsg as Sg721QueryMsg};
 use sg_std::NATIVE_DENOM;
@@ -73,6 +79,12 @@ fn try_nft_pair_invalid_swaps() {
             },
             is_active: false,
             asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
         },
         10u64,
         Uint128::zero(),
@@ -104,6 +116,12 @@ fn try_nft_pair_invalid_swaps() {
             pair_type: None,
             bonding_curve: None,
             asset_recipient: None,
+            auto_activate: None,
+            anti_sandwich: None,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: None,
+            allow_sell: None,
         },
         &[],
     );
@@ -180,6 +198,21 @@ fn try_nft_pair_invalid_swaps() {
     assert_error(response, "Must send reserve token 'ustars'".to_string());
 
     // Cannot swap for unnowned NFT
+    let response = router.execute_contract(
+        bidder.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::SwapTokensForSpecificNft {
+            token_id: "99999".to_string(),
+            asset_recipient: None,
+        },
+        &[coin(10_600_000u128, NATIVE_DENOM)],
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput("pair does not own NFT".to_string()).to_string(),
+    );
+
+    // Unowned NFT is reported even when the funds do not match the quote
     let response = router.execute_contract(
         bidder,
         test_pair.address,
@@ -187,7 +220,7 @@ fn try_nft_pair_invalid_swaps() {
             token_id: "99999".to_string(),
             asset_recipient: None,
         },
-        &[coin(10_600_000u128, NATIVE_DENOM)],
+        &[coin(1u128, NATIVE_DENOM)],
     );
     assert_error(
         response,
@@ -195,6 +228,154 @@ fn try_nft_pair_invalid_swaps() {
     );
 }
 
+#[test]
+fn try_nft_pair_non_native_denom_swaps() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    // Pairs may quote in a denom other than the native denom
+    let response = router.execute_contract(
+        owner.clone(),
+        infinity_factory,
+        &InfinityFactoryExecuteMsg::CreatePair {
+            pair_immutable: PairImmutable {
+                collection: collection.to_string(),
+                owner: owner.to_string(),
+                denom: UOSMO.to_string(),
+                trait_filter: None,
+                price_oracle: None,
+            },
+            pair_config: PairConfig {
+                pair_type: PairType::Nft,
+                bonding_curve: BondingCurve::Linear {
+                    spot_price: Uint128::from(10_000_000u128),
+                    delta: Uint128::from(1_000_000u128),
+                },
+                is_active: true,
+                asset_recipient: None,
+                auto_activate: false,
+                anti_sandwich: false,
+                token_recipient: None,
+                nft_recipient: None,
+                allow_buy: true,
+                allow_sell: true,
+            },
+        },
+        &[global_config.pair_creation_fee.clone()],
+    );
+    let pair_addr = Addr::unchecked(response.unwrap().events[2].attributes[0].value.clone());
+
+    let mut token_ids: Vec<String> = vec![];
+    for _ in 0..2 {
+        token_ids.push(mint_to(&mut router, &creator, &owner, &minter));
+    }
+    approve_all(&mut router, &owner, &collection, &pair_addr);
+    let response = router.execute_contract(
+        owner,
+        pair_addr.clone(),
+        &InfinityPairExecuteMsg::DepositNfts {
+            collection: collection.to_string(),
+            token_ids: token_ids.clone(),
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(pair_addr.clone(), &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    let quote_total = pair.internal.buy_from_pair_quote_summary.unwrap().total();
+
+    // Cannot swap using native denom funds
+    let response = router.execute_contract(
+        bidder.clone(),
+        pair_addr.clone(),
+        &InfinityPairExecuteMsg::SwapTokensForSpecificNft {
+            token_id: token_ids[0].clone(),
+            asset_recipient: None,
+        },
+        &[coin(quote_total.u128(), NATIVE_DENOM)],
+    );
+    assert_error(response, format!("Must send reserve token '{}'", UOSMO));
+
+    // Can swap using the pair denom
+    let response = router.execute_contract(
+        bidder.clone(),
+        pair_addr.clone(),
+        &InfinityPairExecuteMsg::SwapTokensForSpecificNft {
+            token_id: token_ids[0].clone(),
+            asset_recipient: None,
+        },
+        &[coin(quote_total.u128(), UOSMO)],
+    );
+    assert!(response.is_ok());
+    assert_nft_owner(&router, &collection, token_ids[0].clone(), &bidder);
+
+    let pair =
+        router.wrap().query_wasm_smart::<Pair>(pair_addr, &InfinityPairQueryMsg::Pair {}).unwrap();
+    let quote_total = pair.internal.buy_from_pair_quote_summary.unwrap().total();
+
+    // The router only accepts the requested denom
+    let response = router.execute_contract(
+        bidder.clone(),
+        global_config.infinity_router.clone(),
+        &InfinityRouterExecuteMsg::SwapTokensForNfts {
+            collection: collection.to_string(),
+            denom: UOSMO.to_string(),
+            max_inputs: vec![quote_total],
+            min_nfts: None,
+            swap_params: None,
+            filter_sources: None,
+        },
+        &[coin(quote_total.u128(), NATIVE_DENOM)],
+    );
+    assert_error(response, format!("Must send reserve token '{}'", UOSMO));
+
+    let response = router.execute_contract(
+        bidder.clone(),
+        global_config.infinity_router,
+        &InfinityRouterExecuteMsg::SwapTokensForNfts {
+            collection: collection.to_string(),
+            denom: UOSMO.to_string(),
+            max_inputs: vec![quote_total],
+            min_nfts: None,
+            swap_params: None,
+            filter_sources: None,
+        },
+        &[coin(quote_total.u128(), UOSMO)],
+    );
+    assert!(response.is_ok());
+    assert_nft_owner(&router, &collection, token_ids[1].clone(), &bidder);
+}
+
 #[test]
 fn try_nft_pair_linear_user_submits_tokens_swap() {
     let vt = standard_minter_template(1000u32);
@@ -251,6 +432,12 @@ fn try_nft_pair_linear_user_submits_tokens_swap() {
             },
             is_active: true,
             asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
         },
         10u64,
         Uint128::zero(),
@@ -370,6 +557,12 @@ fn try_nft_pair_exponential_user_submits_tokens_swap() {
             },
             is_active: true,
             asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
         },
         10u64,
         Uint128::zero(),
@@ -432,3 +625,123 @@ fn try_nft_pair_exponential_user_submits_tokens_swap() {
         })
     );
 }
+
+#[test]
+fn try_nft_pair_zero_royalty_user_submits_tokens_swap() {
+    let vt = zero_royalty_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    let collection_info = router
+        .wrap()
+        .query_wasm_smart::<CollectionInfoResponse>(
+            collection.clone(),
+            &Sg721QueryMsg::CollectionInfo {},
+        )
+        .unwrap();
+    assert_eq!(collection_info.royalty_info, None);
+
+    let mut test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: PairType::Nft,
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        10u64,
+        Uint128::zero(),
+    );
+
+    assert_eq!(
+        test_pair.pair.internal.buy_from_pair_quote_summary,
+        Some(QuoteSummary {
+            fair_burn: TokenPayment {
+                recipient: global_config.fair_burn.clone(),
+                amount: Uint128::from(100_000u128),
+            },
+            royalty: None,
+            swap: None,
+            seller_amount: Uint128::from(10_000_000u128),
+        })
+    );
+
+    let token_id = test_pair.token_ids[0].clone();
+
+    // Can swap without paying royalties
+    let response = router
+        .execute_contract(
+            bidder.clone(),
+            test_pair.address.clone(),
+            &InfinityPairExecuteMsg::SwapTokensForSpecificNft {
+                token_id: token_id.clone(),
+                asset_recipient: None,
+            },
+            &[coin(10_100_000u128, NATIVE_DENOM)],
+        )
+        .unwrap();
+
+    let swap_event = response.events.iter().find(|e| e.ty == "wasm-swap-tokens-for-nft").unwrap();
+    assert!(!swap_event.attributes.iter().any(|a| a.key == "royalty_fee"));
+
+    assert_nft_owner(&router, &collection, token_id, &bidder);
+
+    test_pair.pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(test_pair.address.clone(), &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+
+    assert_eq!(
+        test_pair.pair.internal.buy_from_pair_quote_summary,
+        Some(QuoteSummary {
+            fair_burn: TokenPayment {
+                recipient: global_config.fair_burn,
+                amount: Uint128::from(110_000u128),
+            },
+            royalty: None,
+            swap: None,
+            seller_amount: Uint128::from(11_000_000u128),
+        })
+    );
+}
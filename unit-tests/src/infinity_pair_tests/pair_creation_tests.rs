@@ -1,16 +1,25 @@
-use crate::helpers::pair_functions::create_pair;
-use crate::helpers::utils::assert_error;
+use crate::helpers::nft_functions::{approve, assert_nft_owner, mint_to};
+use crate::helpers::pair_functions::{create_pair, create_pair_with_deposits};
+use crate::helpers::utils::{assert_error, assert_error_variant};
 use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
 
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{coin, Addr, Coin, Decimal, Uint128};
 use cw_multi_test::Executor;
-use infinity_factory::msg::ExecuteMsg as InfinityFactoryExecuteMsg;
-use infinity_global::{msg::QueryMsg as InfinityGlobalQueryMsg, GlobalConfig};
-use infinity_pair::msg::{ExecuteMsg as InfinityPairExecuteMsg, QueryMsg as InfinityPairQueryMsg};
+use infinity_factory::msg::{
+    ExecuteMsg as InfinityFactoryExecuteMsg, QueryMsg as InfinityFactoryQueryMsg,
+};
+use infinity_global::{
+    msg::{QueryMsg as InfinityGlobalQueryMsg, SudoMsg as InfinityGlobalSudoMsg},
+    GlobalConfig,
+};
+use infinity_pair::msg::{
+    AssetRecipientResponse, ExecuteMsg as InfinityPairExecuteMsg, QueryMsg as InfinityPairQueryMsg,
+};
 use infinity_pair::pair::Pair;
 use infinity_pair::state::{BondingCurve, PairConfig, PairImmutable, PairInternal, PairType};
+use infinity_pair::ContractError;
 use infinity_shared::InfinityError;
-use sg_multi_test::mock_deps;
+use sg_multi_test::{mock_deps, StargazeApp};
 use sg_std::NATIVE_DENOM;
 use test_suite::common_setup::msg::MinterTemplateResponse;
 
@@ -45,6 +54,8 @@ fn try_create_pair() {
         collection: collection.to_string(),
         owner: accts.creator.to_string(),
         denom: NATIVE_DENOM.to_string(),
+        trait_filter: None,
+        price_oracle: None,
     };
 
     let pair_config = PairConfig {
@@ -55,6 +66,12 @@ fn try_create_pair() {
         },
         is_active: false,
         asset_recipient: None,
+        auto_activate: false,
+        anti_sandwich: false,
+        token_recipient: None,
+        nft_recipient: None,
+        allow_buy: true,
+        allow_sell: true,
     };
 
     // Fails without funds sent
@@ -95,6 +112,10 @@ fn try_create_pair() {
             total_nfts: 0u64,
             sell_to_pair_quote_summary: None,
             buy_from_pair_quote_summary: None,
+            price_snapshots: vec![],
+            last_config_update: None,
+            last_swap: None,
+            created_at: Some(router.block_info().time),
         }
     );
 }
@@ -130,6 +151,12 @@ fn try_update_pair_config() {
             pair_type: None,
             bonding_curve: None,
             asset_recipient: None,
+            auto_activate: None,
+            anti_sandwich: None,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: None,
+            allow_sell: None,
         },
         &[],
     );
@@ -147,6 +174,12 @@ fn try_update_pair_config() {
             pair_type: None,
             bonding_curve: None,
             asset_recipient: None,
+            auto_activate: None,
+            anti_sandwich: None,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: None,
+            allow_sell: None,
         },
         &[],
     );
@@ -155,7 +188,10 @@ fn try_update_pair_config() {
     // Owner can update config with args
     let is_active = true;
     let pair_type = PairType::Nft;
-    let bonding_curve = BondingCurve::ConstantProduct;
+    let bonding_curve = BondingCurve::Exponential {
+        spot_price: Uint128::from(10_000_000u128),
+        delta: Decimal::percent(10),
+    };
     let asset_recipient = Addr::unchecked("asset_recipient");
     let response = router.execute_contract(
         accts.owner,
@@ -165,6 +201,12 @@ fn try_update_pair_config() {
             pair_type: Some(pair_type.clone()),
             bonding_curve: Some(bonding_curve.clone()),
             asset_recipient: Some(asset_recipient.to_string()),
+            auto_activate: None,
+            anti_sandwich: None,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: None,
+            allow_sell: None,
         },
         &[],
     );
@@ -177,3 +219,1209 @@ fn try_update_pair_config() {
     assert_eq!(pair.config.bonding_curve, bonding_curve);
     assert_eq!(pair.config.asset_recipient, Some(asset_recipient));
 }
+
+#[test]
+fn try_query_asset_recipient() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts,
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let (pair_addr, _pair) =
+        create_pair(&mut router, &infinity_global, &infinity_factory, &collection, &accts.owner);
+
+    let query_asset_recipient = |router: &StargazeApp| {
+        router
+            .wrap()
+            .query_wasm_smart::<AssetRecipientResponse>(
+                pair_addr.clone(),
+                &InfinityPairQueryMsg::AssetRecipient {},
+            )
+            .unwrap()
+    };
+
+    // Without configured recipients, assets go to the pair owner
+    assert_eq!(
+        query_asset_recipient(&router),
+        AssetRecipientResponse {
+            token_recipient: accts.owner.clone(),
+            nft_recipient: accts.owner.clone(),
+        }
+    );
+
+    // A configured token recipient takes precedence over the asset recipient,
+    // which takes precedence over the pair owner
+    let asset_recipient = Addr::unchecked("asset_recipient");
+    let token_recipient = Addr::unchecked("token_recipient");
+    let response = router.execute_contract(
+        accts.owner,
+        pair_addr.clone(),
+        &InfinityPairExecuteMsg::UpdatePairConfig {
+            is_active: None,
+            pair_type: None,
+            bonding_curve: None,
+            asset_recipient: Some(asset_recipient.to_string()),
+            auto_activate: None,
+            anti_sandwich: None,
+            token_recipient: Some(token_recipient.to_string()),
+            nft_recipient: None,
+            allow_buy: None,
+            allow_sell: None,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    assert_eq!(
+        query_asset_recipient(&router),
+        AssetRecipientResponse {
+            token_recipient,
+            nft_recipient: asset_recipient,
+        }
+    );
+}
+
+#[test]
+fn try_exponential_delta_out_of_range() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts,
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    let invalid_bonding_curve = BondingCurve::Exponential {
+        spot_price: Uint128::from(10_000_000u128),
+        delta: Decimal::bps(10_001u64),
+    };
+    let expected_error =
+        InfinityError::InvalidInput("exponential delta cannot exceed 10000 bps".to_string())
+            .to_string();
+
+    // Cannot create a pair with an exponential delta above 10000 bps
+    let response = router.execute_contract(
+        accts.creator.clone(),
+        infinity_factory.clone(),
+        &InfinityFactoryExecuteMsg::CreatePair {
+            pair_immutable: PairImmutable {
+                collection: collection.to_string(),
+                owner: accts.creator.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                trait_filter: None,
+                price_oracle: None,
+            },
+            pair_config: PairConfig {
+                pair_type: PairType::Token,
+                bonding_curve: invalid_bonding_curve.clone(),
+                is_active: false,
+                asset_recipient: None,
+                auto_activate: false,
+                anti_sandwich: false,
+                token_recipient: None,
+                nft_recipient: None,
+                allow_buy: true,
+                allow_sell: true,
+            },
+        },
+        &[global_config.pair_creation_fee],
+    );
+    assert_eq!(response.unwrap_err().root_cause().to_string(), expected_error);
+
+    // Cannot update a pair to an exponential delta above 10000 bps
+    let (pair_addr, _pair) =
+        create_pair(&mut router, &infinity_global, &infinity_factory, &collection, &accts.owner);
+
+    let response = router.execute_contract(
+        accts.owner,
+        pair_addr,
+        &InfinityPairExecuteMsg::UpdatePairConfig {
+            is_active: None,
+            pair_type: None,
+            bonding_curve: Some(invalid_bonding_curve),
+            asset_recipient: None,
+            auto_activate: None,
+            anti_sandwich: None,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: None,
+            allow_sell: None,
+        },
+        &[],
+    );
+    assert_error(response, expected_error);
+}
+
+#[test]
+fn try_update_pair_config_cooldown() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts,
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let cooldown = 60u64;
+    let response = router.wasm_sudo(
+        infinity_global.clone(),
+        &InfinityGlobalSudoMsg::UpdateConfig {
+            fair_burn: None,
+            royalty_registry: None,
+            marketplace: None,
+            infinity_factory: None,
+            infinity_index: None,
+            infinity_router: None,
+            infinity_pair_code_id: None,
+            pair_creation_fee: None,
+            fair_burn_fee_percent: None,
+            default_royalty_fee_percent: None,
+            max_royalty_fee_percent: None,
+            max_swap_fee_percent: None,
+            max_nfts_per_pair: None,
+            pair_config_update_cooldown_seconds: Some(cooldown),
+            trading_fee_recipient: None,
+            burn_share_percent: None,
+            min_token_deposit: None,
+            min_nft_count: None,
+            pair_creation_fee_discount_percent: None,
+            min_spot_price: None,
+            max_spot_price: None,
+            probe_collection_freeze: None,
+        },
+    );
+    assert!(response.is_ok());
+
+    let (pair_addr, _pair) =
+        create_pair(&mut router, &infinity_global, &infinity_factory, &collection, &accts.owner);
+
+    let update_pair_config_msg = InfinityPairExecuteMsg::UpdatePairConfig {
+        is_active: Some(true),
+        pair_type: None,
+        bonding_curve: None,
+        asset_recipient: None,
+        auto_activate: None,
+        anti_sandwich: None,
+        token_recipient: None,
+        nft_recipient: None,
+        allow_buy: None,
+        allow_sell: None,
+    };
+
+    // First update is allowed
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        pair_addr.clone(),
+        &update_pair_config_msg,
+        &[],
+    );
+    assert!(response.is_ok());
+
+    // Immediate second update is rejected
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        pair_addr.clone(),
+        &update_pair_config_msg,
+        &[],
+    );
+    assert_error_variant(response, ContractError::ConfigUpdateCooldownActive);
+
+    // Deactivating the pair is allowed during the cooldown
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        pair_addr.clone(),
+        &InfinityPairExecuteMsg::UpdatePairConfig {
+            is_active: Some(false),
+            pair_type: None,
+            bonding_curve: None,
+            asset_recipient: None,
+            auto_activate: None,
+            anti_sandwich: None,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: None,
+            allow_sell: None,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(pair_addr.clone(), &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert!(!pair.config.is_active);
+
+    // Reactivating the pair is still subject to the cooldown
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        pair_addr.clone(),
+        &update_pair_config_msg,
+        &[],
+    );
+    assert_error_variant(response, ContractError::ConfigUpdateCooldownActive);
+
+    // Update is allowed once the cooldown has elapsed
+    router.update_block(|block| block.time = block.time.plus_seconds(cooldown));
+    let response = router.execute_contract(accts.owner, pair_addr, &update_pair_config_msg, &[]);
+    assert!(response.is_ok());
+}
+
+#[test]
+fn try_create_pair_allowed_collections() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts,
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    let create_pair_msg = InfinityFactoryExecuteMsg::CreatePair {
+        pair_immutable: PairImmutable {
+            collection: collection.to_string(),
+            owner: accts.owner.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            trait_filter: None,
+            price_oracle: None,
+        },
+        pair_config: PairConfig {
+            pair_type: PairType::Token,
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: false,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+    };
+
+    // Restricting the allow list to another collection rejects the collection
+    let response = router.wasm_sudo(
+        infinity_global.clone(),
+        &InfinityGlobalSudoMsg::UpdateAllowedCollections {
+            add: vec!["other_collection".to_string()],
+            remove: vec![],
+        },
+    );
+    assert!(response.is_ok());
+
+    let allowed = router
+        .wrap()
+        .query_wasm_smart::<bool>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::CollectionAllowed {
+                collection: collection.to_string(),
+            },
+        )
+        .unwrap();
+    assert!(!allowed);
+
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        infinity_factory.clone(),
+        &create_pair_msg,
+        &[global_config.pair_creation_fee.clone()],
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput("collection not allowed".to_string()).to_string(),
+    );
+
+    // Adding the collection to the allow list permits pair creation
+    let response = router.wasm_sudo(
+        infinity_global.clone(),
+        &InfinityGlobalSudoMsg::UpdateAllowedCollections {
+            add: vec![collection.to_string()],
+            remove: vec![],
+        },
+    );
+    assert!(response.is_ok());
+
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        infinity_factory.clone(),
+        &create_pair_msg,
+        &[global_config.pair_creation_fee.clone()],
+    );
+    assert!(response.is_ok());
+
+    // An empty allow list permits all collections
+    let response = router.wasm_sudo(
+        infinity_global.clone(),
+        &InfinityGlobalSudoMsg::UpdateAllowedCollections {
+            add: vec![],
+            remove: vec!["other_collection".to_string(), collection.to_string()],
+        },
+    );
+    assert!(response.is_ok());
+
+    let allowed = router
+        .wrap()
+        .query_wasm_smart::<bool>(
+            infinity_global,
+            &InfinityGlobalQueryMsg::CollectionAllowed {
+                collection: collection.to_string(),
+            },
+        )
+        .unwrap();
+    assert!(allowed);
+
+    let response = router.execute_contract(
+        accts.owner,
+        infinity_factory,
+        &create_pair_msg,
+        &[global_config.pair_creation_fee],
+    );
+    assert!(response.is_ok());
+}
+
+#[test]
+fn try_create_pair_spot_price_bounds() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts,
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    let min_spot_price = Uint128::from(1_000u128);
+    let max_spot_price = Uint128::from(1_000_000_000_000u128);
+    let response = router.wasm_sudo(
+        infinity_global,
+        &InfinityGlobalSudoMsg::UpdateConfig {
+            fair_burn: None,
+            royalty_registry: None,
+            marketplace: None,
+            infinity_factory: None,
+            infinity_index: None,
+            infinity_router: None,
+            infinity_pair_code_id: None,
+            pair_creation_fee: None,
+            fair_burn_fee_percent: None,
+            default_royalty_fee_percent: None,
+            max_royalty_fee_percent: None,
+            max_swap_fee_percent: None,
+            max_nfts_per_pair: None,
+            pair_config_update_cooldown_seconds: None,
+            trading_fee_recipient: None,
+            burn_share_percent: None,
+            min_token_deposit: None,
+            min_nft_count: None,
+            pair_creation_fee_discount_percent: None,
+            min_spot_price: Some(min_spot_price),
+            max_spot_price: Some(max_spot_price),
+            probe_collection_freeze: None,
+        },
+    );
+    assert!(response.is_ok());
+
+    let create_pair_msg = |spot_price: Uint128| InfinityFactoryExecuteMsg::CreatePair {
+        pair_immutable: PairImmutable {
+            collection: collection.to_string(),
+            owner: accts.owner.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            trait_filter: None,
+            price_oracle: None,
+        },
+        pair_config: PairConfig {
+            pair_type: PairType::Token,
+            bonding_curve: BondingCurve::Linear {
+                spot_price,
+                delta: Uint128::from(100u128),
+            },
+            is_active: false,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+    };
+
+    // Spot price below the minimum is rejected
+    let spot_price = Uint128::from(10u128);
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        infinity_factory.clone(),
+        &create_pair_msg(spot_price),
+        &[global_config.pair_creation_fee.clone()],
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput(format!(
+            "spot price must be at least {}, received {}",
+            min_spot_price, spot_price
+        ))
+        .to_string(),
+    );
+
+    // Spot price above the maximum is rejected
+    let spot_price = Uint128::from(10_000_000_000_000u128);
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        infinity_factory.clone(),
+        &create_pair_msg(spot_price),
+        &[global_config.pair_creation_fee.clone()],
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput(format!(
+            "spot price must be at most {}, received {}",
+            max_spot_price, spot_price
+        ))
+        .to_string(),
+    );
+
+    // Spot price within the bounds is accepted
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        infinity_factory,
+        &create_pair_msg(Uint128::from(10_000_000u128)),
+        &[global_config.pair_creation_fee],
+    );
+    assert!(response.is_ok());
+}
+
+#[test]
+fn try_create_pair_repeat_creator_discount() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts,
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let response = router.wasm_sudo(
+        infinity_global.clone(),
+        &InfinityGlobalSudoMsg::UpdateConfig {
+            fair_burn: None,
+            royalty_registry: None,
+            marketplace: None,
+            infinity_factory: None,
+            infinity_index: None,
+            infinity_router: None,
+            infinity_pair_code_id: None,
+            pair_creation_fee: None,
+            fair_burn_fee_percent: None,
+            default_royalty_fee_percent: None,
+            max_royalty_fee_percent: None,
+            max_swap_fee_percent: None,
+            max_nfts_per_pair: None,
+            pair_config_update_cooldown_seconds: None,
+            trading_fee_recipient: None,
+            burn_share_percent: None,
+            min_token_deposit: None,
+            min_nft_count: None,
+            pair_creation_fee_discount_percent: Some(Decimal::percent(40)),
+            min_spot_price: None,
+            max_spot_price: None,
+            probe_collection_freeze: None,
+        },
+    );
+    assert!(response.is_ok());
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global,
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+    let full_fee = global_config.pair_creation_fee.clone();
+
+    let create_pair_msg = InfinityFactoryExecuteMsg::CreatePair {
+        pair_immutable: PairImmutable {
+            collection: collection.to_string(),
+            owner: accts.owner.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            trait_filter: None,
+            price_oracle: None,
+        },
+        pair_config: PairConfig {
+            pair_type: PairType::Token,
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: false,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+    };
+
+    // Each pair is discounted by 40% of the fee per prior pair, bottoming out at zero
+    let expected_fee_percents = [100u64, 60, 20, 0, 0];
+    for (idx, expected_fee_percent) in expected_fee_percents.into_iter().enumerate() {
+        let pair_creation_fee = router
+            .wrap()
+            .query_wasm_smart::<Coin>(
+                infinity_factory.clone(),
+                &InfinityFactoryQueryMsg::PairCreationFee {
+                    owner: accts.owner.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            pair_creation_fee,
+            coin(
+                full_fee.amount.mul_floor(Decimal::percent(expected_fee_percent)).u128(),
+                &full_fee.denom
+            )
+        );
+
+        // The full fee is rejected once a discount applies
+        if idx > 0 {
+            let response = router.execute_contract(
+                accts.owner.clone(),
+                infinity_factory.clone(),
+                &create_pair_msg,
+                &[full_fee.clone()],
+            );
+            assert!(response.is_err());
+        }
+
+        let funds = if pair_creation_fee.amount.is_zero() {
+            vec![]
+        } else {
+            vec![pair_creation_fee]
+        };
+        let response = router.execute_contract(
+            accts.owner.clone(),
+            infinity_factory.clone(),
+            &create_pair_msg,
+            &funds,
+        );
+        assert!(response.is_ok());
+    }
+
+    // Other owners still pay the full fee
+    let pair_creation_fee = router
+        .wrap()
+        .query_wasm_smart::<Coin>(
+            infinity_factory,
+            &InfinityFactoryQueryMsg::PairCreationFee {
+                owner: accts.creator.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(pair_creation_fee, full_fee);
+}
+
+#[test]
+fn try_create_pair_invalid_denom() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts,
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global,
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    let create_pair_msg = |denom: &str| InfinityFactoryExecuteMsg::CreatePair {
+        pair_immutable: PairImmutable {
+            collection: collection.to_string(),
+            owner: accts.owner.to_string(),
+            denom: denom.to_string(),
+            trait_filter: None,
+            price_oracle: None,
+        },
+        pair_config: PairConfig {
+            pair_type: PairType::Token,
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: false,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+    };
+
+    // Empty denom is rejected
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        infinity_factory.clone(),
+        &create_pair_msg(""),
+        &[global_config.pair_creation_fee.clone()],
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput("denom must not be empty".to_string()).to_string(),
+    );
+
+    // Denom without a min price in the global config is rejected
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        infinity_factory.clone(),
+        &create_pair_msg("uatom"),
+        &[global_config.pair_creation_fee.clone()],
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput("denom not supported".to_string()).to_string(),
+    );
+
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        infinity_factory,
+        &create_pair_msg(NATIVE_DENOM),
+        &[global_config.pair_creation_fee],
+    );
+    assert!(response.is_ok());
+}
+
+#[test]
+fn try_create_nft_pair() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts,
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global,
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    let create_nft_pair_msg =
+        |bonding_curve: BondingCurve| InfinityFactoryExecuteMsg::CreateNftPair {
+            collection: collection.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            bonding_curve,
+            is_active: true,
+            token_recipient: None,
+        };
+
+    // ConstantProduct requires both NFTs and tokens, so it is rejected
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        infinity_factory.clone(),
+        &create_nft_pair_msg(BondingCurve::ConstantProduct),
+        &[global_config.pair_creation_fee.clone()],
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput(
+            "nft pairs cannot use a constant product bonding curve".to_string(),
+        )
+        .to_string(),
+    );
+
+    for bonding_curve in [
+        BondingCurve::Linear {
+            spot_price: Uint128::from(10_000_000u128),
+            delta: Uint128::from(1_000_000u128),
+        },
+        BondingCurve::Exponential {
+            spot_price: Uint128::from(10_000_000u128),
+            delta: Decimal::percent(5),
+        },
+    ] {
+        let response = router.execute_contract(
+            accts.owner.clone(),
+            infinity_factory.clone(),
+            &create_nft_pair_msg(bonding_curve.clone()),
+            &[global_config.pair_creation_fee.clone()],
+        );
+        assert!(response.is_ok());
+
+        let pair_addr = response.unwrap().events[2].attributes[0].value.clone();
+        let pair = router
+            .wrap()
+            .query_wasm_smart::<Pair>(pair_addr, &InfinityPairQueryMsg::Pair {})
+            .unwrap();
+        assert_eq!(pair.immutable.owner, accts.owner);
+        assert_eq!(pair.immutable.trait_filter, None);
+        assert_eq!(pair.config.pair_type, PairType::Nft);
+        assert_eq!(pair.config.bonding_curve, bonding_curve);
+        assert!(pair.config.is_active);
+    }
+}
+
+#[test]
+fn try_convert_pair_type() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts,
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &accts.creator,
+        &accts.owner,
+        PairConfig {
+            pair_type: PairType::Token,
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        0u64,
+        Uint128::from(100_000_000u128),
+    );
+    assert_eq!(test_pair.pair.internal.buy_from_pair_quote_summary, None);
+
+    let trade_pair_type = PairType::Trade {
+        swap_fee_percent: Decimal::percent(1),
+        reinvest_tokens: false,
+        reinvest_nfts: false,
+        max_nfts: None,
+    };
+
+    // Only the owner can convert the pair
+    let response = router.execute_contract(
+        accts.bidder.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::ConvertPairType {
+            pair_type: trade_pair_type.clone(),
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::Unauthorized("sender is not the owner of the pair".to_string()).to_string(),
+    );
+
+    // Cannot convert to an nft pair while holding tokens
+    let convert_to_nft_pair_msg = InfinityPairExecuteMsg::ConvertPairType {
+        pair_type: PairType::Nft,
+    };
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        test_pair.address.clone(),
+        &convert_to_nft_pair_msg,
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput(
+            "cannot convert to an nft pair while holding tokens".to_string(),
+        )
+        .to_string(),
+    );
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        test_pair.address.clone(),
+        &convert_to_nft_pair_msg,
+        &[],
+    );
+    assert_error_variant(response, ContractError::NftPairCannotHoldTokens);
+
+    // Cannot deposit NFTs into a token pair
+    let token_id = mint_to(&mut router, &accts.creator, &accts.owner, &minter);
+    approve(&mut router, &accts.owner, &collection, &test_pair.address, token_id.clone());
+    let deposit_nfts_msg = InfinityPairExecuteMsg::DepositNfts {
+        collection: collection.to_string(),
+        token_ids: vec![token_id.clone()],
+    };
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        test_pair.address.clone(),
+        &deposit_nfts_msg,
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput("cannot deposit nfts into a token pair".to_string())
+            .to_string(),
+    );
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        test_pair.address.clone(),
+        &deposit_nfts_msg,
+        &[],
+    );
+    assert_error_variant(response, ContractError::CannotDepositNftsIntoTokenPool);
+    assert_nft_owner(&router, &collection, token_id, &accts.owner);
+
+    // Convert the pair to a trade pair, then deposit NFTs
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::ConvertPairType {
+            pair_type: trade_pair_type.clone(),
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        test_pair.address.clone(),
+        &deposit_nfts_msg,
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(test_pair.address.clone(), &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert_eq!(pair.config.pair_type, trade_pair_type);
+    assert_eq!(pair.internal.total_nfts, 1u64);
+    assert!(pair.internal.sell_to_pair_quote_summary.is_some());
+    assert!(pair.internal.buy_from_pair_quote_summary.is_some());
+
+    // Cannot convert back to a token pair while holding nfts
+    let convert_to_token_pair_msg = InfinityPairExecuteMsg::ConvertPairType {
+        pair_type: PairType::Token,
+    };
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        test_pair.address.clone(),
+        &convert_to_token_pair_msg,
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput(
+            "cannot convert to a token pair while holding nfts".to_string(),
+        )
+        .to_string(),
+    );
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        test_pair.address.clone(),
+        &convert_to_token_pair_msg,
+        &[],
+    );
+    assert_error_variant(response, ContractError::TokenPairCannotHoldNfts);
+
+    // Constant product pairs must be trade pairs holding both assets
+    let (pair_addr, _pair) =
+        create_pair(&mut router, &infinity_global, &infinity_factory, &collection, &accts.owner);
+    let constant_product_msg = InfinityPairExecuteMsg::UpdatePairConfig {
+        is_active: None,
+        pair_type: Some(trade_pair_type),
+        bonding_curve: Some(BondingCurve::ConstantProduct),
+        asset_recipient: None,
+        auto_activate: None,
+        anti_sandwich: None,
+        token_recipient: None,
+        nft_recipient: None,
+        allow_buy: None,
+        allow_sell: None,
+    };
+    let response =
+        router.execute_contract(accts.owner.clone(), pair_addr.clone(), &constant_product_msg, &[]);
+    assert_error(
+        response,
+        InfinityError::InvalidInput(
+            "constant product pairs must be trade pairs holding both nfts and tokens".to_string(),
+        )
+        .to_string(),
+    );
+    let response =
+        router.execute_contract(accts.owner.clone(), pair_addr, &constant_product_msg, &[]);
+    assert_error_variant(response, ContractError::ConstantProductRequiresBothAssets);
+}
+
+#[test]
+fn try_update_pair_config_pair_type_conversion() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts,
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &accts.creator,
+        &accts.owner,
+        PairConfig {
+            pair_type: PairType::Trade {
+                swap_fee_percent: Decimal::percent(1),
+                reinvest_tokens: false,
+                reinvest_nfts: false,
+                max_nfts: None,
+            },
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        1u64,
+        Uint128::from(100_000_000u128),
+    );
+
+    let update_pair_type = |pair_type: PairType| InfinityPairExecuteMsg::UpdatePairConfig {
+        is_active: None,
+        pair_type: Some(pair_type),
+        bonding_curve: None,
+        asset_recipient: None,
+        auto_activate: None,
+        anti_sandwich: None,
+        token_recipient: None,
+        nft_recipient: None,
+        allow_buy: None,
+        allow_sell: None,
+    };
+
+    // UpdatePairConfig applies the same pair type validation as ConvertPairType
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        test_pair.address.clone(),
+        &update_pair_type(PairType::Token),
+        &[],
+    );
+    assert_error_variant(response, ContractError::TokenPairCannotHoldNfts);
+
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        test_pair.address.clone(),
+        &update_pair_type(PairType::Nft),
+        &[],
+    );
+    assert_error_variant(response, ContractError::NftPairCannotHoldTokens);
+
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(test_pair.address, &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert_eq!(pair.config.pair_type, test_pair.pair.config.pair_type);
+
+    // The pair type and bonding curve are validated together, against the final config
+    let update_pair_type_and_curve =
+        |pair_type: Option<PairType>, bonding_curve: Option<BondingCurve>| {
+            InfinityPairExecuteMsg::UpdatePairConfig {
+                is_active: None,
+                pair_type,
+                bonding_curve,
+                asset_recipient: None,
+                auto_activate: None,
+                anti_sandwich: None,
+                token_recipient: None,
+                nft_recipient: None,
+                allow_buy: None,
+                allow_sell: None,
+            }
+        };
+    let token_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &accts.creator,
+        &accts.owner,
+        PairConfig {
+            pair_type: PairType::Token,
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        0u64,
+        Uint128::from(100_000_000u128),
+    );
+
+    // Setting the pair type alongside a constant product curve
+    let response = router.execute_contract(
+        accts.owner.clone(),
+        token_pair.address.clone(),
+        &update_pair_type_and_curve(Some(PairType::Token), Some(BondingCurve::ConstantProduct)),
+        &[],
+    );
+    assert_error_variant(response, ContractError::ConstantProductRequiresBothAssets);
+
+    // Setting only a constant product curve on a one sided pair
+    for bonding_curve in [
+        BondingCurve::ConstantProduct,
+        BondingCurve::WeightedConstantProduct {
+            weight_bps: 8000,
+        },
+    ] {
+        let response = router.execute_contract(
+            accts.owner.clone(),
+            token_pair.address.clone(),
+            &update_pair_type_and_curve(None, Some(bonding_curve)),
+            &[],
+        );
+        assert_error_variant(response, ContractError::ConstantProductRequiresBothAssets);
+    }
+
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(token_pair.address, &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert_eq!(pair.config.bonding_curve, token_pair.pair.config.bonding_curve);
+}
@@ -4,8 +4,13 @@ use crate::setup::templates::{setup_infinity_test, standard_minter_template, Inf
 
 use cosmwasm_std::{Addr, Decimal, Uint128};
 use infinity_global::{msg::QueryMsg as InfinityGlobalQueryMsg, GlobalConfig};
-use infinity_pair::msg::{QueryMsg as InfinityPairQueryMsg, QuotesResponse};
+use infinity_pair::msg::{
+    HypotheticalQuotesResponse, NftsForBudgetResponse, QueryMsg as InfinityPairQueryMsg,
+    QuotesResponse, TransactionType,
+};
+use infinity_pair::pair::Pair;
 use infinity_pair::state::{BondingCurve, PairConfig, PairType};
+use infinity_pair::ContractError;
 use sg_std::NATIVE_DENOM;
 use test_suite::common_setup::msg::MinterTemplateResponse;
 
@@ -61,6 +66,12 @@ fn try_generate_quotes_token_linear() {
             },
             is_active: true,
             asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
         },
         0u64,
         remaining_amount,
@@ -142,6 +153,12 @@ fn try_generate_quotes_token_exponential() {
             },
             is_active: true,
             asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
         },
         0u64,
         remaining_amount,
@@ -223,6 +240,12 @@ fn try_generate_quotes_nft_linear() {
             },
             is_active: true,
             asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
         },
         num_nfts,
         Uint128::zero(),
@@ -255,6 +278,332 @@ fn try_generate_quotes_nft_linear() {
     assert_eq!(quotes_response.buy_from_pair_quotes, expected_quotes);
 }
 
+#[test]
+fn try_buy_from_pair_curve_nft_linear() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        ..
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    let spot_price = Uint128::from(10_000_000u128);
+    let delta = Uint128::from(1_000_000u128);
+    let num_nfts = 5u64;
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: PairType::Nft {},
+            bonding_curve: BondingCurve::Linear {
+                spot_price,
+                delta,
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        num_nfts,
+        Uint128::zero(),
+    );
+
+    let curve = router
+        .wrap()
+        .query_wasm_smart::<Vec<Uint128>>(
+            test_pair.address.clone(),
+            &InfinityPairQueryMsg::BuyFromPairCurve {
+                count: 3,
+            },
+        )
+        .unwrap();
+    // Each step is the amount paid by the buyer, including fees
+    let expected_curve = (0..3u128)
+        .map(|idx| {
+            let price = spot_price + delta * Uint128::from(idx);
+            price
+                + price.mul_ceil(global_config.fair_burn_fee_percent)
+                + price.mul_ceil(global_config.default_royalty_fee_percent)
+        })
+        .collect::<Vec<Uint128>>();
+    assert_eq!(curve, expected_curve);
+
+    // The curve is truncated when the pair runs out of NFTs
+    let curve = router
+        .wrap()
+        .query_wasm_smart::<Vec<Uint128>>(
+            test_pair.address.clone(),
+            &InfinityPairQueryMsg::BuyFromPairCurve {
+                count: 10,
+            },
+        )
+        .unwrap();
+    let quotes_response = router
+        .wrap()
+        .query_wasm_smart::<QuotesResponse>(
+            test_pair.address.clone(),
+            &InfinityPairQueryMsg::SimBuyFromPairSwaps {
+                limit: 10,
+            },
+        )
+        .unwrap();
+    assert_eq!(curve.len(), num_nfts as usize);
+    assert_eq!(curve, quotes_response.buy_from_pair_quotes);
+
+    // The pair is left untouched
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(test_pair.address, &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert_eq!(pair.internal.total_nfts, num_nfts);
+    assert_eq!(
+        pair.config.bonding_curve,
+        BondingCurve::Linear {
+            spot_price,
+            delta,
+        }
+    );
+}
+
+#[test]
+fn try_nfts_for_budget_nft_linear() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        ..
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let num_nfts = 5u64;
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: PairType::Nft {},
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        num_nfts,
+        Uint128::zero(),
+    );
+
+    // The stepped prices paid by the buyer, including fees
+    let quotes_response = router
+        .wrap()
+        .query_wasm_smart::<QuotesResponse>(
+            test_pair.address.clone(),
+            &InfinityPairQueryMsg::SimBuyFromPairSwaps {
+                limit: num_nfts as u32,
+            },
+        )
+        .unwrap();
+    let prices = quotes_response.buy_from_pair_quotes;
+    assert_eq!(prices.len(), num_nfts as usize);
+
+    // A budget just short of the fourth NFT buys three
+    let expected_total_cost = prices[0..3].iter().sum::<Uint128>();
+    let response = router
+        .wrap()
+        .query_wasm_smart::<NftsForBudgetResponse>(
+            test_pair.address.clone(),
+            &InfinityPairQueryMsg::NftsForBudget {
+                budget: expected_total_cost + prices[3] - Uint128::one(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        response,
+        NftsForBudgetResponse {
+            num_nfts: 3,
+            total_cost: expected_total_cost,
+        }
+    );
+
+    // The walk stops when the pair runs out of NFTs
+    let response = router
+        .wrap()
+        .query_wasm_smart::<NftsForBudgetResponse>(
+            test_pair.address,
+            &InfinityPairQueryMsg::NftsForBudget {
+                budget: Uint128::from(1_000_000_000u128),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        response,
+        NftsForBudgetResponse {
+            num_nfts: num_nfts as u32,
+            total_cost: prices.iter().sum::<Uint128>(),
+        }
+    );
+}
+
+#[test]
+fn try_spot_price_after_swaps_nft_linear() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        ..
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let spot_price = Uint128::from(10_000_000u128);
+    let delta = Uint128::from(1_000_000u128);
+    let num_nfts = 5u64;
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: PairType::Nft {},
+            bonding_curve: BondingCurve::Linear {
+                spot_price,
+                delta,
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        num_nfts,
+        Uint128::zero(),
+    );
+
+    for count in 0..=num_nfts as u32 {
+        let spot_price_after_swaps = router
+            .wrap()
+            .query_wasm_smart::<Option<Uint128>>(
+                test_pair.address.clone(),
+                &InfinityPairQueryMsg::SpotPriceAfterSwaps {
+                    transaction_type: TransactionType::UserSubmitsTokens,
+                    count,
+                },
+            )
+            .unwrap();
+        assert_eq!(spot_price_after_swaps, Some(spot_price + delta * Uint128::from(count)));
+    }
+
+    // The pair cannot complete more swaps than it has NFTs
+    let spot_price_after_swaps = router
+        .wrap()
+        .query_wasm_smart::<Option<Uint128>>(
+            test_pair.address.clone(),
+            &InfinityPairQueryMsg::SpotPriceAfterSwaps {
+                transaction_type: TransactionType::UserSubmitsTokens,
+                count: num_nfts as u32 + 1,
+            },
+        )
+        .unwrap();
+    assert_eq!(spot_price_after_swaps, None);
+
+    // An NFT pair does not buy NFTs
+    let spot_price_after_swaps = router
+        .wrap()
+        .query_wasm_smart::<Option<Uint128>>(
+            test_pair.address.clone(),
+            &InfinityPairQueryMsg::SpotPriceAfterSwaps {
+                transaction_type: TransactionType::UserSubmitsNfts,
+                count: 1,
+            },
+        )
+        .unwrap();
+    assert_eq!(spot_price_after_swaps, None);
+
+    // The pair is left untouched
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(test_pair.address, &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert_eq!(pair.config.bonding_curve, test_pair.pair.config.bonding_curve);
+}
+
 #[test]
 fn try_generate_quotes_nft_exponential() {
     let vt = standard_minter_template(1000u32);
@@ -307,6 +656,12 @@ fn try_generate_quotes_nft_exponential() {
             },
             is_active: true,
             asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
         },
         num_nfts,
         Uint128::zero(),
@@ -389,6 +744,7 @@ fn try_generate_quotes_trade_linear() {
                 swap_fee_percent,
                 reinvest_tokens: false,
                 reinvest_nfts: false,
+                max_nfts: None,
             },
             bonding_curve: BondingCurve::Linear {
                 spot_price: original_spot_price,
@@ -396,6 +752,12 @@ fn try_generate_quotes_trade_linear() {
             },
             is_active: true,
             asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
         },
         num_nfts,
         remaining_amount,
@@ -506,6 +868,7 @@ fn try_generate_quotes_trade_exponential() {
                 swap_fee_percent,
                 reinvest_tokens: false,
                 reinvest_nfts: false,
+                max_nfts: None,
             },
             bonding_curve: BondingCurve::Exponential {
                 spot_price: original_spot_price,
@@ -513,6 +876,12 @@ fn try_generate_quotes_trade_exponential() {
             },
             is_active: true,
             asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
         },
         num_nfts,
         remaining_amount,
@@ -620,10 +989,17 @@ fn try_generate_quotes_trade_cp() {
                 swap_fee_percent,
                 reinvest_tokens: false,
                 reinvest_nfts: false,
+                max_nfts: None,
             },
             bonding_curve: BondingCurve::ConstantProduct {},
             is_active: true,
             asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
         },
         original_num_nfts,
         original_remaining_amount,
@@ -689,3 +1065,206 @@ fn try_generate_quotes_trade_cp() {
     assert_eq!(quotes_response.denom, NATIVE_DENOM.to_string());
     assert_eq!(quotes_response.buy_from_pair_quotes, expected_quotes);
 }
+
+#[test]
+fn try_sim_quote_for_hypothetical_pool() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        ..
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let pair_type = PairType::Trade {
+        swap_fee_percent: Decimal::percent(1),
+        reinvest_tokens: false,
+        reinvest_nfts: false,
+        max_nfts: None,
+    };
+    let num_nfts = 10u64;
+    let num_tokens = Uint128::from(100_000_000u128);
+
+    let real_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: pair_type.clone(),
+            bonding_curve: BondingCurve::ConstantProduct {},
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        num_nfts,
+        num_tokens,
+    );
+
+    let other_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: PairType::Token {},
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(1_000_000u128),
+                delta: Uint128::from(100_000u128),
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        0u64,
+        Uint128::from(5_000_000u128),
+    );
+
+    // The hypothetical quotes match those of a real pair with the same parameters
+    let hypothetical_quotes = router
+        .wrap()
+        .query_wasm_smart::<HypotheticalQuotesResponse>(
+            other_pair.address.clone(),
+            &InfinityPairQueryMsg::SimQuoteForHypotheticalPool {
+                pair_type,
+                bonding_curve: BondingCurve::ConstantProduct {},
+                total_tokens: num_tokens,
+                total_nfts: num_nfts,
+            },
+        )
+        .unwrap();
+
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(real_pair.address, &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+
+    assert_eq!(hypothetical_quotes.denom, NATIVE_DENOM.to_string());
+    assert_eq!(
+        hypothetical_quotes.sell_to_pair_quote,
+        pair.internal.sell_to_pair_quote_summary.map(|qs| qs.seller_amount)
+    );
+    assert_eq!(
+        hypothetical_quotes.buy_from_pair_quote,
+        pair.internal.buy_from_pair_quote_summary.map(|qs| qs.total())
+    );
+    assert!(hypothetical_quotes.sell_to_pair_quote.is_some());
+    assert!(hypothetical_quotes.buy_from_pair_quote.is_some());
+
+    // The pair queried is left untouched
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(other_pair.address.clone(), &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert_eq!(pair.config.pair_type, PairType::Token {});
+    assert!(pair.internal.buy_from_pair_quote_summary.is_none());
+
+    // A hypothetical nft pair without any nfts cannot be quoted
+    let hypothetical_quotes = router
+        .wrap()
+        .query_wasm_smart::<HypotheticalQuotesResponse>(
+            other_pair.address.clone(),
+            &InfinityPairQueryMsg::SimQuoteForHypotheticalPool {
+                pair_type: PairType::Nft {},
+                bonding_curve: BondingCurve::Linear {
+                    spot_price: Uint128::from(1_000_000u128),
+                    delta: Uint128::from(100_000u128),
+                },
+                total_tokens: Uint128::zero(),
+                total_nfts: 0u64,
+            },
+        )
+        .unwrap();
+    assert_eq!(hypothetical_quotes.sell_to_pair_quote, None);
+    assert_eq!(hypothetical_quotes.buy_from_pair_quote, None);
+
+    // An invalid bonding curve is rejected
+    let response = router.wrap().query_wasm_smart::<HypotheticalQuotesResponse>(
+        other_pair.address.clone(),
+        &InfinityPairQueryMsg::SimQuoteForHypotheticalPool {
+            pair_type: PairType::Token {},
+            bonding_curve: BondingCurve::Exponential {
+                spot_price: Uint128::from(1_000_000u128),
+                delta: Decimal::percent(101),
+            },
+            total_tokens: Uint128::from(5_000_000u128),
+            total_nfts: 0u64,
+        },
+    );
+    assert!(response.is_err());
+
+    // Pair types that cannot hold the given balances, or use the given curve, are rejected
+    let invalid_hypothetical_pools = [
+        (
+            PairType::Token {},
+            BondingCurve::ConstantProduct {},
+            num_tokens,
+            0u64,
+            ContractError::ConstantProductRequiresBothAssets,
+        ),
+        (
+            PairType::Token {},
+            BondingCurve::Linear {
+                spot_price: Uint128::from(1_000_000u128),
+                delta: Uint128::from(100_000u128),
+            },
+            num_tokens,
+            num_nfts,
+            ContractError::TokenPairCannotHoldNfts,
+        ),
+        (
+            PairType::Nft {},
+            BondingCurve::Linear {
+                spot_price: Uint128::from(1_000_000u128),
+                delta: Uint128::from(100_000u128),
+            },
+            num_tokens,
+            num_nfts,
+            ContractError::NftPairCannotHoldTokens,
+        ),
+    ];
+    for (pair_type, bonding_curve, total_tokens, total_nfts, expected_error) in
+        invalid_hypothetical_pools
+    {
+        let response = router.wrap().query_wasm_smart::<HypotheticalQuotesResponse>(
+            other_pair.address.clone(),
+            &InfinityPairQueryMsg::SimQuoteForHypotheticalPool {
+                pair_type,
+                bonding_curve,
+                total_tokens,
+                total_nfts,
+            },
+        );
+        assert!(response.unwrap_err().to_string().contains(&expected_error.to_string()));
+    }
+}
@@ -0,0 +1,221 @@
+use crate::helpers::nft_functions::{approve_all, mint_to};
+use crate::helpers::utils::assert_error;
+use crate::setup::setup_accounts::MarketAccounts;
+use crate::setup::setup_contracts::setup_mock_price_oracle;
+use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
+
+use anyhow::Error;
+use cosmwasm_std::{coin, Addr, Uint128};
+use cw_multi_test::{AppResponse, Executor};
+use infinity_factory::msg::ExecuteMsg as InfinityFactoryExecuteMsg;
+use infinity_global::{msg::QueryMsg as InfinityGlobalQueryMsg, GlobalConfig};
+use infinity_pair::msg::{ExecuteMsg as InfinityPairExecuteMsg, QueryMsg as InfinityPairQueryMsg};
+use infinity_pair::pair::Pair;
+use infinity_pair::state::{BondingCurve, PairConfig, PairImmutable, PairType, PriceOracle};
+use infinity_pair::ContractError as InfinityPairContractError;
+use infinity_shared::InfinityError;
+use sg_multi_test::StargazeApp;
+use sg_std::NATIVE_DENOM;
+use test_suite::common_setup::msg::MinterTemplateResponse;
+
+fn create_price_oracle_pair(
+    router: &mut StargazeApp,
+    infinity_global: &Addr,
+    infinity_factory: &Addr,
+    collection: &Addr,
+    owner: &Addr,
+    price_oracle: PriceOracle<String>,
+) -> Result<AppResponse, Error> {
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global,
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    router.execute_contract(
+        owner.clone(),
+        infinity_factory.clone(),
+        &InfinityFactoryExecuteMsg::CreatePair {
+            pair_immutable: PairImmutable {
+                collection: collection.to_string(),
+                owner: owner.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                trait_filter: None,
+                price_oracle: Some(price_oracle),
+            },
+            pair_config: PairConfig {
+                pair_type: PairType::Nft,
+                bonding_curve: BondingCurve::Linear {
+                    spot_price: Uint128::from(10_000_000u128),
+                    delta: Uint128::from(2_000_000u128),
+                },
+                is_active: true,
+                asset_recipient: None,
+                auto_activate: false,
+                anti_sandwich: false,
+                token_recipient: None,
+                nft_recipient: None,
+                allow_buy: true,
+                allow_sell: true,
+            },
+        },
+        &[global_config.pair_creation_fee],
+    )
+}
+
+#[test]
+fn try_price_oracle_pair() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let oracle = setup_mock_price_oracle(&mut router, &creator, Uint128::from(10_000_000u128));
+
+    // The deviation band cannot exceed 100%
+    let response = create_price_oracle_pair(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &collection,
+        &owner,
+        PriceOracle {
+            oracle: oracle.to_string(),
+            max_deviation_bps: 10_001u64,
+        },
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput("max oracle deviation cannot exceed 10000 bps".to_string())
+            .to_string(),
+    );
+
+    // Swaps are allowed while the spot price is within 10% of the oracle price
+    let response = create_price_oracle_pair(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &collection,
+        &owner,
+        PriceOracle {
+            oracle: oracle.to_string(),
+            max_deviation_bps: 1_000u64,
+        },
+    );
+    let pair_addr = Addr::unchecked(response.unwrap().events[2].attributes[0].value.clone());
+
+    let token_ids =
+        (0..3).map(|_| mint_to(&mut router, &creator, &owner, &minter)).collect::<Vec<String>>();
+    approve_all(&mut router, &owner, &collection, &pair_addr);
+    let response = router.execute_contract(
+        owner.clone(),
+        pair_addr.clone(),
+        &InfinityPairExecuteMsg::DepositNfts {
+            collection: collection.to_string(),
+            token_ids,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(pair_addr.clone(), &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    let quote = pair.internal.buy_from_pair_quote_summary.unwrap().total();
+    let response = router.execute_contract(
+        bidder.clone(),
+        pair_addr.clone(),
+        &InfinityPairExecuteMsg::SwapTokensForAnyNft {
+            asset_recipient: None,
+        },
+        &[coin(quote.u128(), NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+
+    // The swap moved the spot price to 12_000_000, outside of the band
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(pair_addr.clone(), &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert_eq!(pair.spot_price(), Some(Uint128::from(12_000_000u128)));
+    let quote = pair.internal.buy_from_pair_quote_summary.unwrap().total();
+    let response = router.execute_contract(
+        bidder.clone(),
+        pair_addr,
+        &InfinityPairExecuteMsg::SwapTokensForAnyNft {
+            asset_recipient: None,
+        },
+        &[coin(quote.u128(), NATIVE_DENOM)],
+    );
+    assert_error(
+        response,
+        InfinityPairContractError::SwapError(
+            "spot price 12000000 deviates from the oracle price 10000000 by more than 1000 bps"
+                .to_string(),
+        )
+        .to_string(),
+    );
+
+    // Oracle query errors reject the swap
+    let response = create_price_oracle_pair(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &collection,
+        &owner,
+        PriceOracle {
+            oracle: "not_an_oracle".to_string(),
+            max_deviation_bps: 1_000u64,
+        },
+    );
+    let pair_addr = Addr::unchecked(response.unwrap().events[2].attributes[0].value.clone());
+
+    let token_id = mint_to(&mut router, &creator, &owner, &minter);
+    approve_all(&mut router, &owner, &collection, &pair_addr);
+    let response = router.execute_contract(
+        owner,
+        pair_addr.clone(),
+        &InfinityPairExecuteMsg::DepositNfts {
+            collection: collection.to_string(),
+            token_ids: vec![token_id],
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(pair_addr.clone(), &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    let quote = pair.internal.buy_from_pair_quote_summary.unwrap().total();
+    let response = router.execute_contract(
+        bidder,
+        pair_addr,
+        &InfinityPairExecuteMsg::SwapTokensForAnyNft {
+            asset_recipient: None,
+        },
+        &[coin(quote.u128(), NATIVE_DENOM)],
+    );
+    let error = response.unwrap_err().source().unwrap().to_string();
+    assert!(error.starts_with("SwapError: price oracle query failed"));
+}
@@ -0,0 +1,251 @@
+use crate::helpers::nft_functions::{approve, mint_to};
+use crate::helpers::pair_functions::create_pair_with_deposits;
+use crate::setup::setup_accounts::{setup_addtl_account, MarketAccounts, INITIAL_BALANCE};
+use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
+
+use cosmwasm_std::{coin, Timestamp, Uint128};
+use cw_multi_test::Executor;
+use infinity_pair::msg::{
+    ExecuteMsg as InfinityPairExecuteMsg, QueryMsg as InfinityPairQueryMsg, TwapResponse,
+};
+use infinity_pair::pair::Pair;
+use infinity_pair::state::{BondingCurve, PairConfig, PairType};
+use sg_std::NATIVE_DENOM;
+use test_suite::common_setup::msg::MinterTemplateResponse;
+
+#[test]
+fn try_query_twap() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        ..
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: PairType::Token,
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        0u64,
+        Uint128::from(100_000_000u128),
+    );
+
+    // TWAP is empty before any swaps
+    let twap_response = router
+        .wrap()
+        .query_wasm_smart::<TwapResponse>(
+            test_pair.address.clone(),
+            &InfinityPairQueryMsg::Twap {
+                seconds: 300,
+            },
+        )
+        .unwrap();
+    assert_eq!(twap_response.denom, NATIVE_DENOM.to_string());
+    assert_eq!(twap_response.twap, None);
+
+    let seller = setup_addtl_account(&mut router, "seller", INITIAL_BALANCE).unwrap();
+
+    let mut observed_spot_prices: Vec<Uint128> = vec![];
+    for _ in 0..3 {
+        let token_id = mint_to(&mut router, &creator.clone(), &seller.clone(), &minter);
+        approve(&mut router, &seller, &collection, &test_pair.address, token_id.clone());
+
+        let response = router.execute_contract(
+            seller.clone(),
+            test_pair.address.clone(),
+            &InfinityPairExecuteMsg::SwapNftForTokens {
+                token_id,
+                min_output: coin(1u128, NATIVE_DENOM),
+                asset_recipient: None,
+            },
+            &[],
+        );
+        assert!(response.is_ok());
+
+        let pair = router
+            .wrap()
+            .query_wasm_smart::<Pair>(test_pair.address.clone(), &InfinityPairQueryMsg::Pair {})
+            .unwrap();
+        observed_spot_prices.push(pair.internal.price_snapshots.last().unwrap().spot_price);
+
+        router.update_block(|block| block.time = block.time.plus_seconds(100));
+    }
+
+    assert_eq!(
+        observed_spot_prices,
+        vec![
+            Uint128::from(9_000_000u128),
+            Uint128::from(8_000_000u128),
+            Uint128::from(7_000_000u128)
+        ]
+    );
+
+    let twap_response = router
+        .wrap()
+        .query_wasm_smart::<TwapResponse>(
+            test_pair.address,
+            &InfinityPairQueryMsg::Twap {
+                seconds: 300,
+            },
+        )
+        .unwrap();
+
+    let twap = twap_response.twap.unwrap();
+    assert!(twap >= *observed_spot_prices.iter().min().unwrap());
+    assert!(twap <= *observed_spot_prices.iter().max().unwrap());
+    assert_eq!(twap, Uint128::from(8_000_000u128));
+}
+
+#[test]
+fn try_query_price_history() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        ..
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: PairType::Token,
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        0u64,
+        Uint128::from(100_000_000u128),
+    );
+
+    // History is empty before any swaps
+    let price_history = router
+        .wrap()
+        .query_wasm_smart::<Vec<(Timestamp, Uint128)>>(
+            test_pair.address.clone(),
+            &InfinityPairQueryMsg::PriceHistory {
+                limit: 10,
+            },
+        )
+        .unwrap();
+    assert!(price_history.is_empty());
+
+    let seller = setup_addtl_account(&mut router, "seller", INITIAL_BALANCE).unwrap();
+
+    let mut observed_history: Vec<(Timestamp, Uint128)> = vec![];
+    for _ in 0..4 {
+        let token_id = mint_to(&mut router, &creator.clone(), &seller.clone(), &minter);
+        approve(&mut router, &seller, &collection, &test_pair.address, token_id.clone());
+
+        let response = router.execute_contract(
+            seller.clone(),
+            test_pair.address.clone(),
+            &InfinityPairExecuteMsg::SwapNftForTokens {
+                token_id,
+                min_output: coin(1u128, NATIVE_DENOM),
+                asset_recipient: None,
+            },
+            &[],
+        );
+        assert!(response.is_ok());
+
+        let pair = router
+            .wrap()
+            .query_wasm_smart::<Pair>(test_pair.address.clone(), &InfinityPairQueryMsg::Pair {})
+            .unwrap();
+        observed_history.push((router.block_info().time, pair.spot_price().unwrap()));
+
+        router.update_block(|block| {
+            block.time = block.time.plus_seconds(100);
+            block.height += 1;
+        });
+    }
+
+    // The full history is returned oldest first
+    let price_history = router
+        .wrap()
+        .query_wasm_smart::<Vec<(Timestamp, Uint128)>>(
+            test_pair.address.clone(),
+            &InfinityPairQueryMsg::PriceHistory {
+                limit: 10,
+            },
+        )
+        .unwrap();
+    assert_eq!(price_history, observed_history);
+    assert_eq!(price_history[3].1, Uint128::from(6_000_000u128));
+
+    // The limit keeps the most recent snapshots
+    let price_history = router
+        .wrap()
+        .query_wasm_smart::<Vec<(Timestamp, Uint128)>>(
+            test_pair.address,
+            &InfinityPairQueryMsg::PriceHistory {
+                limit: 2,
+            },
+        )
+        .unwrap();
+    assert_eq!(price_history, observed_history[2..].to_vec());
+}
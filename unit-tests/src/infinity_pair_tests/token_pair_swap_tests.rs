@@ -1,14 +1,22 @@
 use crate::helpers::nft_functions::{approve, assert_nft_owner, mint_to};
-use crate::helpers::pair_functions::create_pair_with_deposits;
+use crate::helpers::pair_functions::{create_pair, create_pair_with_deposits};
 use crate::helpers::utils::assert_error;
 use crate::setup::setup_accounts::{setup_addtl_account, MarketAccounts, INITIAL_BALANCE};
+use crate::setup::setup_contracts::{
+    setup_mock_collection, setup_mock_royalty_registry, MockCollectionExecuteMsg,
+};
 use crate::setup::setup_infinity_contracts::UOSMO;
 use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
 
 use cosmwasm_std::{coin, Addr, Decimal, Uint128};
 use cw_multi_test::Executor;
-use infinity_global::{msg::QueryMsg as InfinityGlobalQueryMsg, GlobalConfig};
-use infinity_pair::msg::{ExecuteMsg as InfinityPairExecuteMsg, QueryMsg as InfinityPairQueryMsg};
+use infinity_global::{
+    msg::{QueryMsg as InfinityGlobalQueryMsg, SudoMsg as InfinityGlobalSudoMsg},
+    GlobalConfig,
+};
+use infinity_pair::msg::{
+    ExecuteMsg as InfinityPairExecuteMsg, QueryMsg as InfinityPairQueryMsg, QuotesResponse,
+};
 use infinity_pair::pair::Pair;
 use infinity_pair::state::{BondingCurve, PairConfig, PairType, QuoteSummary, TokenPayment};
 use infinity_pair::ContractError;
@@ -72,6 +80,12 @@ fn try_token_pair_invalid_swaps() {
             },
             is_active: false,
             asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
         },
         0u64,
         Uint128::from(100_000_000u128),
@@ -105,6 +119,12 @@ fn try_token_pair_invalid_swaps() {
             pair_type: None,
             bonding_curve: None,
             asset_recipient: None,
+            auto_activate: None,
+            anti_sandwich: None,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: None,
+            allow_sell: None,
         },
         &[],
     );
@@ -237,6 +257,12 @@ fn try_token_pair_linear_user_submits_nfts_swap() {
             },
             is_active: true,
             asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
         },
         0u64,
         Uint128::from(100_000_000u128),
@@ -359,6 +385,12 @@ fn try_token_pair_exponential_user_submits_nfts_swap() {
             },
             is_active: true,
             asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
         },
         0u64,
         Uint128::from(100_000_000u128),
@@ -424,3 +456,634 @@ fn try_token_pair_exponential_user_submits_nfts_swap() {
     );
     assert_eq!(test_pair.pair.internal.buy_from_pair_quote_summary, None);
 }
+
+#[test]
+fn try_token_pair_royalty_above_max_is_capped() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder: _,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    // Lower the max royalty fee below the collection royalty of 5%
+    let response = router.wasm_sudo(
+        infinity_global.clone(),
+        &InfinityGlobalSudoMsg::UpdateConfig {
+            fair_burn: None,
+            royalty_registry: None,
+            marketplace: None,
+            infinity_factory: None,
+            infinity_index: None,
+            infinity_router: None,
+            infinity_pair_code_id: None,
+            pair_creation_fee: None,
+            fair_burn_fee_percent: None,
+            default_royalty_fee_percent: None,
+            max_royalty_fee_percent: Some(Decimal::percent(2u64)),
+            max_swap_fee_percent: None,
+            max_nfts_per_pair: None,
+            pair_config_update_cooldown_seconds: None,
+            trading_fee_recipient: None,
+            burn_share_percent: None,
+            min_token_deposit: None,
+            min_nft_count: None,
+            pair_creation_fee_discount_percent: None,
+            min_spot_price: None,
+            max_spot_price: None,
+            probe_collection_freeze: None,
+        },
+    );
+    assert!(response.is_ok());
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    let collection_info = router
+        .wrap()
+        .query_wasm_smart::<CollectionInfoResponse>(
+            collection.clone(),
+            &Sg721QueryMsg::CollectionInfo {},
+        )
+        .unwrap();
+    let royalty_info = collection_info.royalty_info.unwrap();
+    assert_eq!(royalty_info.share, Decimal::percent(5u64));
+    let royalty_recipient = Addr::unchecked(royalty_info.payment_address);
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: PairType::Token,
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        0u64,
+        Uint128::from(100_000_000u128),
+    );
+
+    assert_eq!(
+        test_pair.pair.internal.sell_to_pair_quote_summary,
+        Some(QuoteSummary {
+            fair_burn: TokenPayment {
+                recipient: global_config.fair_burn,
+                amount: Uint128::from(100_000u128),
+            },
+            royalty: Some(TokenPayment {
+                recipient: royalty_recipient.clone(),
+                amount: Uint128::from(200_000u128),
+            }),
+            swap: None,
+            seller_amount: Uint128::from(9_700_000u128),
+        })
+    );
+
+    let seller = setup_addtl_account(&mut router, "seller", INITIAL_BALANCE).unwrap();
+    let token_id = mint_to(&mut router, &creator.clone(), &seller.clone(), &minter);
+    approve(&mut router, &seller, &collection, &test_pair.address, token_id.clone());
+
+    let royalty_balance_before =
+        router.wrap().query_balance(&royalty_recipient, NATIVE_DENOM).unwrap().amount;
+    let seller_balance_before = router.wrap().query_balance(&seller, NATIVE_DENOM).unwrap().amount;
+
+    let response = router
+        .execute_contract(
+            seller.clone(),
+            test_pair.address.clone(),
+            &InfinityPairExecuteMsg::SwapNftForTokens {
+                token_id: token_id.clone(),
+                min_output: coin(9_700_000u128, NATIVE_DENOM),
+                asset_recipient: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    // Payout uses the capped royalty
+    let royalty_balance_after =
+        router.wrap().query_balance(&royalty_recipient, NATIVE_DENOM).unwrap().amount;
+    let seller_balance_after = router.wrap().query_balance(&seller, NATIVE_DENOM).unwrap().amount;
+    assert_eq!(royalty_balance_after - royalty_balance_before, Uint128::from(200_000u128));
+    assert_eq!(seller_balance_after - seller_balance_before, Uint128::from(9_700_000u128));
+
+    // Swap event reports the effective royalty
+    let swap_event = response.events.iter().find(|e| e.ty == "wasm-swap-nft-for-tokens").unwrap();
+    let royalty_fee_percent =
+        swap_event.attributes.iter().find(|a| a.key == "royalty_fee_percent").unwrap();
+    assert_eq!(royalty_fee_percent.value, Decimal::percent(2u64).to_string());
+
+    // Lowering the max royalty fee again does not change the quote summary cached by the pair
+    let response = router.wasm_sudo(
+        infinity_global.clone(),
+        &InfinityGlobalSudoMsg::UpdateConfig {
+            fair_burn: None,
+            royalty_registry: None,
+            marketplace: None,
+            infinity_factory: None,
+            infinity_index: None,
+            infinity_router: None,
+            infinity_pair_code_id: None,
+            pair_creation_fee: None,
+            fair_burn_fee_percent: None,
+            default_royalty_fee_percent: None,
+            max_royalty_fee_percent: Some(Decimal::percent(1u64)),
+            max_swap_fee_percent: None,
+            max_nfts_per_pair: None,
+            pair_config_update_cooldown_seconds: None,
+            trading_fee_recipient: None,
+            burn_share_percent: None,
+            min_token_deposit: None,
+            min_nft_count: None,
+            pair_creation_fee_discount_percent: None,
+            min_spot_price: None,
+            max_spot_price: None,
+            probe_collection_freeze: None,
+        },
+    );
+    assert!(response.is_ok());
+
+    let token_id = mint_to(&mut router, &creator.clone(), &seller.clone(), &minter);
+    approve(&mut router, &seller, &collection, &test_pair.address, token_id.clone());
+
+    let royalty_balance_before =
+        router.wrap().query_balance(&royalty_recipient, NATIVE_DENOM).unwrap().amount;
+    let response = router
+        .execute_contract(
+            seller.clone(),
+            test_pair.address.clone(),
+            &InfinityPairExecuteMsg::SwapNftForTokens {
+                token_id,
+                min_output: coin(8_730_000u128, NATIVE_DENOM),
+                asset_recipient: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    // The swap event reports the royalty that was paid out
+    let royalty_balance_after =
+        router.wrap().query_balance(&royalty_recipient, NATIVE_DENOM).unwrap().amount;
+    assert_eq!(royalty_balance_after - royalty_balance_before, Uint128::from(180_000u128));
+
+    let swap_event = response.events.iter().find(|e| e.ty == "wasm-swap-nft-for-tokens").unwrap();
+    let royalty_fee_percent =
+        swap_event.attributes.iter().find(|a| a.key == "royalty_fee_percent").unwrap();
+    assert_eq!(royalty_fee_percent.value, Decimal::percent(2u64).to_string());
+}
+
+#[test]
+fn try_token_pair_swap_events_emit_new_spot_price() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder: _,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: PairType::Token,
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        0u64,
+        Uint128::from(100_000_000u128),
+    );
+
+    let seller = setup_addtl_account(&mut router, "seller", INITIAL_BALANCE).unwrap();
+
+    // Each sell decrements the spot price by delta
+    for expected_spot_price in [9_000_000u128, 8_000_000u128, 7_000_000u128] {
+        let token_id = mint_to(&mut router, &creator.clone(), &seller.clone(), &minter);
+        approve(&mut router, &seller, &collection, &test_pair.address, token_id.clone());
+
+        let response = router
+            .execute_contract(
+                seller.clone(),
+                test_pair.address.clone(),
+                &InfinityPairExecuteMsg::SwapNftForTokens {
+                    token_id,
+                    min_output: coin(1u128, NATIVE_DENOM),
+                    asset_recipient: None,
+                },
+                &[],
+            )
+            .unwrap();
+
+        let swap_event =
+            response.events.iter().find(|e| e.ty == "wasm-swap-nft-for-tokens").unwrap();
+        let new_spot_price =
+            swap_event.attributes.iter().find(|a| a.key == "new_spot_price").unwrap();
+        assert_eq!(new_spot_price.value, expected_spot_price.to_string());
+
+        let denom = swap_event.attributes.iter().find(|a| a.key == "denom").unwrap();
+        assert_eq!(denom.value, NATIVE_DENOM);
+    }
+}
+
+#[test]
+fn try_token_pair_trading_fee_split() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder: _,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    // Split the fair burn fee evenly between the FairBurn contract and a trading fee recipient
+    let trading_fee_recipient = Addr::unchecked("trading_fee_recipient");
+    let response = router.wasm_sudo(
+        infinity_global.clone(),
+        &InfinityGlobalSudoMsg::UpdateConfig {
+            fair_burn: None,
+            royalty_registry: None,
+            marketplace: None,
+            infinity_factory: None,
+            infinity_index: None,
+            infinity_router: None,
+            infinity_pair_code_id: None,
+            pair_creation_fee: None,
+            fair_burn_fee_percent: None,
+            default_royalty_fee_percent: None,
+            max_royalty_fee_percent: None,
+            max_swap_fee_percent: None,
+            max_nfts_per_pair: None,
+            pair_config_update_cooldown_seconds: None,
+            trading_fee_recipient: Some(trading_fee_recipient.to_string()),
+            burn_share_percent: Some(Decimal::percent(50u64)),
+            min_token_deposit: None,
+            min_nft_count: None,
+            pair_creation_fee_discount_percent: None,
+            min_spot_price: None,
+            max_spot_price: None,
+            probe_collection_freeze: None,
+        },
+    );
+    assert!(response.is_ok());
+
+    let collection_info = router
+        .wrap()
+        .query_wasm_smart::<CollectionInfoResponse>(
+            collection.clone(),
+            &Sg721QueryMsg::CollectionInfo {},
+        )
+        .unwrap();
+    let royalty_recipient = Addr::unchecked(collection_info.royalty_info.unwrap().payment_address);
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: PairType::Token,
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        0u64,
+        Uint128::from(100_000_000u128),
+    );
+
+    let quote_summary = test_pair.pair.internal.sell_to_pair_quote_summary.clone().unwrap();
+    assert_eq!(quote_summary.fair_burn.amount, Uint128::from(100_000u128));
+
+    let seller = setup_addtl_account(&mut router, "seller", INITIAL_BALANCE).unwrap();
+    let token_id = mint_to(&mut router, &creator.clone(), &seller.clone(), &minter);
+    approve(&mut router, &seller, &collection, &test_pair.address, token_id.clone());
+
+    let balance = |router: &sg_multi_test::StargazeApp, address: &Addr| {
+        router.wrap().query_balance(address, NATIVE_DENOM).unwrap().amount
+    };
+    let pair_balance_before = balance(&router, &test_pair.address);
+    let seller_balance_before = balance(&router, &seller);
+    let royalty_balance_before = balance(&router, &royalty_recipient);
+    let trading_fee_balance_before = balance(&router, &trading_fee_recipient);
+
+    let response = router.execute_contract(
+        seller.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::SwapNftForTokens {
+            token_id,
+            min_output: coin(quote_summary.seller_amount.u128(), NATIVE_DENOM),
+            asset_recipient: None,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let paid_amount = pair_balance_before - balance(&router, &test_pair.address);
+    let seller_amount = balance(&router, &seller) - seller_balance_before;
+    let royalty_amount = balance(&router, &royalty_recipient) - royalty_balance_before;
+    let trading_fee_amount = balance(&router, &trading_fee_recipient) - trading_fee_balance_before;
+    let burn_amount = paid_amount - seller_amount - royalty_amount - trading_fee_amount;
+
+    assert_eq!(paid_amount, quote_summary.total());
+    assert_eq!(seller_amount, quote_summary.seller_amount);
+    assert_eq!(trading_fee_amount, Uint128::from(50_000u128));
+    assert_eq!(burn_amount, Uint128::from(50_000u128));
+    assert_eq!(trading_fee_amount + burn_amount, quote_summary.fair_burn.amount);
+}
+
+#[test]
+fn try_token_pair_malformed_royalty_recipient() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: PairType::Token,
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        0u64,
+        Uint128::from(100_000_000u128),
+    );
+    assert!(test_pair.pair.internal.sell_to_pair_quote_summary.unwrap().royalty.is_some());
+
+    // Point the global config at a royalty registry that reports a malformed recipient
+    let royalty_registry = setup_mock_royalty_registry(&mut router, &creator, "not-an-address");
+    let response = router.wasm_sudo(
+        infinity_global.clone(),
+        &InfinityGlobalSudoMsg::UpdateConfig {
+            fair_burn: None,
+            royalty_registry: Some(royalty_registry.to_string()),
+            marketplace: None,
+            infinity_factory: None,
+            infinity_index: None,
+            infinity_router: None,
+            infinity_pair_code_id: None,
+            pair_creation_fee: None,
+            fair_burn_fee_percent: None,
+            default_royalty_fee_percent: None,
+            max_royalty_fee_percent: None,
+            max_swap_fee_percent: None,
+            max_nfts_per_pair: None,
+            pair_config_update_cooldown_seconds: None,
+            trading_fee_recipient: None,
+            burn_share_percent: None,
+            min_token_deposit: None,
+            min_nft_count: None,
+            pair_creation_fee_discount_percent: None,
+            min_spot_price: None,
+            max_spot_price: None,
+            probe_collection_freeze: None,
+        },
+    );
+    assert!(response.is_ok());
+
+    // Depositing tokens refreshes the quote summaries with the new royalty info
+    let response = router.execute_contract(
+        owner.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::DepositTokens {},
+        &[coin(1_000_000u128, NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(test_pair.address.clone(), &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    let quote_summary = pair.internal.sell_to_pair_quote_summary.unwrap();
+    assert_eq!(quote_summary.royalty, None);
+
+    // The swap proceeds with no royalty paid
+    let token_id = mint_to(&mut router, &creator, &bidder, &minter);
+    approve(&mut router, &bidder, &collection, &test_pair.address, token_id.clone());
+    let bidder_balance_before = router.wrap().query_balance(&bidder, NATIVE_DENOM).unwrap().amount;
+    let response = router.execute_contract(
+        bidder.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::SwapNftForTokens {
+            token_id: token_id.clone(),
+            min_output: coin(quote_summary.seller_amount.u128(), NATIVE_DENOM),
+            asset_recipient: None,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+    assert_nft_owner(&router, &collection, token_id, &owner);
+
+    let bidder_balance_after = router.wrap().query_balance(&bidder, NATIVE_DENOM).unwrap().amount;
+    assert_eq!(bidder_balance_after - bidder_balance_before, quote_summary.seller_amount);
+    assert_eq!(
+        quote_summary.seller_amount,
+        Uint128::from(10_000_000u128) - quote_summary.fair_burn.amount
+    );
+}
+
+#[test]
+fn try_token_pair_collection_without_collection_info() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        ..
+                    },
+                ..
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    // The collection does not implement the sg721 collection info query
+    let mock_collection = setup_mock_collection(&mut router, &creator, vec![]);
+    let response = router.execute_contract(
+        creator,
+        mock_collection.clone(),
+        &MockCollectionExecuteMsg::RemoveCollectionInfo {},
+        &[],
+    );
+    assert!(response.is_ok());
+
+    // The pair is created with zero royalty
+    let (pair_addr, _pair) =
+        create_pair(&mut router, &infinity_global, &infinity_factory, &mock_collection, &owner);
+
+    let response = router.execute_contract(
+        owner.clone(),
+        pair_addr.clone(),
+        &InfinityPairExecuteMsg::DepositTokens {},
+        &[coin(100_000_000u128, NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+
+    let response = router.execute_contract(
+        owner,
+        pair_addr.clone(),
+        &InfinityPairExecuteMsg::UpdatePairConfig {
+            is_active: Some(true),
+            pair_type: None,
+            bonding_curve: None,
+            asset_recipient: None,
+            auto_activate: None,
+            anti_sandwich: None,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: None,
+            allow_sell: None,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(pair_addr.clone(), &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    let quote_summary = pair.internal.sell_to_pair_quote_summary.unwrap();
+    assert_eq!(quote_summary.royalty, None);
+    assert_eq!(
+        quote_summary.seller_amount,
+        Uint128::from(10_000_000u128) - quote_summary.fair_burn.amount
+    );
+
+    // Swaps still simulate, with no royalty paid
+    let quotes_response = router
+        .wrap()
+        .query_wasm_smart::<QuotesResponse>(
+            pair_addr,
+            &InfinityPairQueryMsg::SimSellToPairSwaps {
+                limit: 1,
+            },
+        )
+        .unwrap();
+    assert_eq!(quotes_response.sell_to_pair_quotes, vec![quote_summary.seller_amount]);
+}
@@ -1,19 +1,28 @@
 use crate::helpers::nft_functions::{approve, approve_all, assert_nft_owner, mint_to};
-use crate::helpers::pair_functions::create_pair_with_deposits;
-use crate::helpers::utils::assert_error;
+use crate::helpers::pair_functions::{create_pair_with_deposits, TestPair};
+use crate::helpers::utils::{assert_error, assert_error_variant};
 use crate::setup::setup_accounts::{setup_addtl_account, MarketAccounts, INITIAL_BALANCE};
 use crate::setup::setup_infinity_contracts::UOSMO;
 use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
 
 use cosmwasm_std::{coin, Addr, Decimal, Uint128};
 use cw_multi_test::Executor;
-use infinity_global::{msg::QueryMsg as InfinityGlobalQueryMsg, GlobalConfig};
-use infinity_pair::msg::{ExecuteMsg as InfinityPairExecuteMsg, QueryMsg as InfinityPairQueryMsg};
+use infinity_global::{
+    msg::{QueryMsg as InfinityGlobalQueryMsg, SudoMsg as InfinityGlobalSudoMsg},
+    GlobalConfig,
+};
+use infinity_index::msg::QueryMsg as InfinityIndexQueryMsg;
+use infinity_index::state::PairQuote;
+use infinity_pair::msg::{
+    ConstantProductInvariantResponse, ExecuteMsg as InfinityPairExecuteMsg,
+    QueryMsg as InfinityPairQueryMsg,
+};
 use infinity_pair::pair::Pair;
 use infinity_pair::state::{BondingCurve, PairConfig, PairType, QuoteSummary, TokenPayment};
 use infinity_pair::ContractError;
 use infinity_shared::InfinityError;
 use sg721_base::msg::{CollectionInfoResponse, QueryMsg as Sg721QueryMsg};
+use sg_multi_test::StargazeApp;
 use sg_std::NATIVE_DENOM;
 use test_suite::common_setup::msg::MinterTemplateResponse;
 
@@ -70,6 +79,7 @@ fn try_trade_pair_invalid_swaps() {
                 swap_fee_percent: Decimal::percent(0),
                 reinvest_tokens: false,
                 reinvest_nfts: false,
+                max_nfts: None,
             },
             bonding_curve: BondingCurve::Linear {
                 spot_price: Uint128::from(10_000_000u128),
@@ -77,6 +87,12 @@ fn try_trade_pair_invalid_swaps() {
             },
             is_active: false,
             asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
         },
         0u64,
         Uint128::zero(),
@@ -111,6 +127,12 @@ fn try_trade_pair_invalid_swaps() {
             pair_type: None,
             bonding_curve: None,
             asset_recipient: None,
+            auto_activate: None,
+            anti_sandwich: None,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: None,
+            allow_sell: None,
         },
         &[],
     );
@@ -308,6 +330,7 @@ fn try_trade_pair_linear_swaps() {
                 swap_fee_percent: Decimal::zero(),
                 reinvest_tokens: false,
                 reinvest_nfts: false,
+                max_nfts: None,
             },
             bonding_curve: BondingCurve::Linear {
                 spot_price: Uint128::from(10_000_000u128),
@@ -315,6 +338,12 @@ fn try_trade_pair_linear_swaps() {
             },
             is_active: true,
             asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
         },
         10u64,
         Uint128::from(100_000_000u128),
@@ -519,6 +548,7 @@ fn try_trade_pair_exponential_swaps() {
                 swap_fee_percent: Decimal::zero(),
                 reinvest_tokens: false,
                 reinvest_nfts: false,
+                max_nfts: None,
             },
             bonding_curve: BondingCurve::Exponential {
                 spot_price: Uint128::from(10_000_000u128),
@@ -526,6 +556,12 @@ fn try_trade_pair_exponential_swaps() {
             },
             is_active: true,
             asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
         },
         10u64,
         Uint128::from(100_000_000u128),
@@ -730,10 +766,17 @@ fn try_trade_pair_constant_product_swaps() {
                 swap_fee_percent: Decimal::zero(),
                 reinvest_tokens: false,
                 reinvest_nfts: false,
+                max_nfts: None,
             },
             bonding_curve: BondingCurve::ConstantProduct,
             is_active: true,
             asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
         },
         10u64,
         Uint128::from(100_000_000u128),
@@ -884,3 +927,909 @@ fn try_trade_pair_constant_product_swaps() {
         })
     );
 }
+
+#[test]
+fn try_trade_pair_anti_sandwich_swaps() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: PairType::Trade {
+                swap_fee_percent: Decimal::zero(),
+                reinvest_tokens: false,
+                reinvest_nfts: false,
+                max_nfts: None,
+            },
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: true,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        10u64,
+        Uint128::from(100_000_000u128),
+    );
+
+    let token_id = test_pair.token_ids[0].clone();
+
+    // Can swap for NFT
+    let response = router.execute_contract(
+        bidder.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::SwapTokensForSpecificNft {
+            token_id: token_id.clone(),
+            asset_recipient: None,
+        },
+        &[coin(11_660_000, NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+    assert_nft_owner(&router, &collection, token_id.clone(), &bidder);
+
+    // Cannot swap in the opposite direction within the same block
+    approve(&mut router, &bidder, &collection, &test_pair.address, token_id.clone());
+    let swap_nft_for_tokens_msg = InfinityPairExecuteMsg::SwapNftForTokens {
+        token_id: token_id.clone(),
+        min_output: coin(9_400_000u128, NATIVE_DENOM),
+        asset_recipient: None,
+    };
+    let response = router.execute_contract(
+        bidder.clone(),
+        test_pair.address.clone(),
+        &swap_nft_for_tokens_msg,
+        &[],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidPair(
+            "opposite swaps within the same block are not allowed".to_string(),
+        )
+        .to_string(),
+    );
+
+    // Can swap in the opposite direction in the next block
+    router.update_block(|block| block.height += 1);
+    let response = router.execute_contract(
+        bidder.clone(),
+        test_pair.address.clone(),
+        &swap_nft_for_tokens_msg,
+        &[],
+    );
+    assert!(response.is_ok());
+    assert_nft_owner(&router, &collection, token_id, &test_pair.pair.asset_recipient());
+}
+
+#[test]
+fn try_trade_pair_distinct_recipients() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let asset_recipient = Addr::unchecked("asset_recipient");
+    let token_recipient = Addr::unchecked("token_recipient");
+    let nft_recipient = Addr::unchecked("nft_recipient");
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: PairType::Trade {
+                swap_fee_percent: Decimal::percent(1),
+                reinvest_tokens: false,
+                reinvest_nfts: false,
+                max_nfts: None,
+            },
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: true,
+            asset_recipient: Some(asset_recipient.to_string()),
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: Some(token_recipient.to_string()),
+            nft_recipient: Some(nft_recipient.to_string()),
+            allow_buy: true,
+            allow_sell: true,
+        },
+        10u64,
+        Uint128::from(100_000_000u128),
+    );
+
+    assert_eq!(test_pair.pair.token_recipient(), token_recipient);
+    assert_eq!(test_pair.pair.nft_recipient(), nft_recipient);
+
+    // Tokens paid for an NFT, including the swap fee, are sent to the token recipient
+    let quote_summary = test_pair.pair.internal.buy_from_pair_quote_summary.clone().unwrap();
+    let token_id = test_pair.token_ids[0].clone();
+    let response = router.execute_contract(
+        bidder.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::SwapTokensForSpecificNft {
+            token_id: token_id.clone(),
+            asset_recipient: None,
+        },
+        &[coin(quote_summary.total().u128(), NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+    assert_nft_owner(&router, &collection, token_id, &bidder);
+
+    let token_recipient_balance =
+        router.wrap().query_balance(&token_recipient, NATIVE_DENOM).unwrap().amount;
+    assert_eq!(
+        token_recipient_balance,
+        quote_summary.seller_amount + quote_summary.swap.unwrap().amount
+    );
+
+    // NFTs sold into the pair are sent to the NFT recipient
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(test_pair.address.clone(), &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    let quote_summary = pair.internal.sell_to_pair_quote_summary.unwrap();
+    let token_id = mint_to(&mut router, &creator, &bidder, &minter);
+    approve(&mut router, &bidder, &collection, &test_pair.address, token_id.clone());
+    let response = router.execute_contract(
+        bidder,
+        test_pair.address,
+        &InfinityPairExecuteMsg::SwapNftForTokens {
+            token_id: token_id.clone(),
+            min_output: coin(quote_summary.seller_amount.u128(), NATIVE_DENOM),
+            asset_recipient: None,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+    assert_nft_owner(&router, &collection, token_id, &nft_recipient);
+
+    // The asset recipient receives neither
+    let asset_recipient_balance =
+        router.wrap().query_balance(&asset_recipient, NATIVE_DENOM).unwrap().amount;
+    assert_eq!(asset_recipient_balance, Uint128::zero());
+}
+
+#[test]
+fn try_trade_pair_reinvest_nfts_max_nfts() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let max_nfts = 3u64;
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: PairType::Trade {
+                swap_fee_percent: Decimal::zero(),
+                reinvest_tokens: false,
+                reinvest_nfts: true,
+                max_nfts: Some(max_nfts),
+            },
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        1u64,
+        Uint128::from(100_000_000u128),
+    );
+
+    // Sells are accepted until the pair holds the max number of NFTs
+    let mut pair = test_pair.pair.clone();
+    for _ in 1..max_nfts {
+        let quote_summary = pair.internal.sell_to_pair_quote_summary.unwrap();
+        let token_id = mint_to(&mut router, &creator, &bidder, &minter);
+        approve(&mut router, &bidder, &collection, &test_pair.address, token_id.clone());
+        let response = router.execute_contract(
+            bidder.clone(),
+            test_pair.address.clone(),
+            &InfinityPairExecuteMsg::SwapNftForTokens {
+                token_id: token_id.clone(),
+                min_output: coin(quote_summary.seller_amount.u128(), NATIVE_DENOM),
+                asset_recipient: None,
+            },
+            &[],
+        );
+        assert!(response.is_ok());
+        assert_nft_owner(&router, &collection, token_id, &test_pair.address);
+
+        pair = router
+            .wrap()
+            .query_wasm_smart::<Pair>(test_pair.address.clone(), &InfinityPairQueryMsg::Pair {})
+            .unwrap();
+    }
+
+    // The pair stops accepting sells, but continues to accept buys
+    assert_eq!(pair.internal.total_nfts, max_nfts);
+    assert!(pair.reached_max_nfts());
+    assert_eq!(pair.internal.sell_to_pair_quote_summary, None);
+    assert!(pair.internal.buy_from_pair_quote_summary.is_some());
+
+    let token_id = mint_to(&mut router, &creator, &bidder, &minter);
+    approve(&mut router, &bidder, &collection, &test_pair.address, token_id.clone());
+    let response = router.execute_contract(
+        bidder,
+        test_pair.address,
+        &InfinityPairExecuteMsg::SwapNftForTokens {
+            token_id,
+            min_output: coin(1u128, NATIVE_DENOM),
+            asset_recipient: None,
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidPair("pair cannot produce quote".to_string()).to_string(),
+    );
+}
+
+#[test]
+fn try_trade_pair_reinvest_nfts_max_nfts_per_pair() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let update_max_nfts_per_pair = |router: &mut StargazeApp, max_nfts_per_pair: u64| {
+        router.wasm_sudo(
+            infinity_global.clone(),
+            &InfinityGlobalSudoMsg::UpdateConfig {
+                fair_burn: None,
+                royalty_registry: None,
+                marketplace: None,
+                infinity_factory: None,
+                infinity_index: None,
+                infinity_router: None,
+                infinity_pair_code_id: None,
+                pair_creation_fee: None,
+                fair_burn_fee_percent: None,
+                default_royalty_fee_percent: None,
+                max_royalty_fee_percent: None,
+                max_swap_fee_percent: None,
+                max_nfts_per_pair: Some(max_nfts_per_pair),
+                pair_config_update_cooldown_seconds: None,
+                trading_fee_recipient: None,
+                burn_share_percent: None,
+                min_token_deposit: None,
+                min_nft_count: None,
+                pair_creation_fee_discount_percent: None,
+                min_spot_price: None,
+                max_spot_price: None,
+                probe_collection_freeze: None,
+            },
+        )
+    };
+    let response = update_max_nfts_per_pair(&mut router, 5u64);
+    assert!(response.is_ok());
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: PairType::Trade {
+                swap_fee_percent: Decimal::zero(),
+                reinvest_tokens: false,
+                reinvest_nfts: true,
+                max_nfts: None,
+            },
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        1u64,
+        Uint128::from(100_000_000u128),
+    );
+
+    // NFTs sold into the pair are reinvested while it holds fewer than the global max
+    let token_id = mint_to(&mut router, &creator, &bidder, &minter);
+    approve(&mut router, &bidder, &collection, &test_pair.address, token_id.clone());
+    let response = router.execute_contract(
+        bidder.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::SwapNftForTokens {
+            token_id: token_id.clone(),
+            min_output: coin(1u128, NATIVE_DENOM),
+            asset_recipient: None,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+    assert_nft_owner(&router, &collection, token_id, &test_pair.address);
+
+    // Lowering the global max does not clear the quote stored by the pair,
+    // but reinvesting past the global max is rejected
+    let response = update_max_nfts_per_pair(&mut router, 2u64);
+    assert!(response.is_ok());
+
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(test_pair.address.clone(), &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert_eq!(pair.internal.total_nfts, 2u64);
+    assert!(pair.internal.sell_to_pair_quote_summary.is_some());
+
+    let token_id = mint_to(&mut router, &creator, &bidder, &minter);
+    approve(&mut router, &bidder, &collection, &test_pair.address, token_id.clone());
+    let response = router.execute_contract(
+        bidder.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::SwapNftForTokens {
+            token_id: token_id.clone(),
+            min_output: coin(1u128, NATIVE_DENOM),
+            asset_recipient: None,
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput("pair cannot hold more than 2 nfts".to_string()).to_string(),
+    );
+    assert_nft_owner(&router, &collection, token_id, &bidder);
+
+    // Once the pair is saved again, it stops quoting sells, but continues to quote buys
+    let response = router.execute_contract(
+        owner,
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::DepositTokens {},
+        &[coin(1_000_000u128, NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(test_pair.address, &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert_eq!(pair.internal.sell_to_pair_quote_summary, None);
+    assert!(pair.internal.buy_from_pair_quote_summary.is_some());
+}
+
+#[test]
+fn try_trade_pair_constant_product_invariant() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    // Reinvesting keeps all traded assets in the pair, so only rounding and swap fees move k
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: PairType::Trade {
+                swap_fee_percent: Decimal::percent(1),
+                reinvest_tokens: true,
+                reinvest_nfts: true,
+                max_nfts: None,
+            },
+            bonding_curve: BondingCurve::ConstantProduct,
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        10u64,
+        Uint128::from(100_000_000u128),
+    );
+
+    let query_invariant = |router: &StargazeApp| {
+        router
+            .wrap()
+            .query_wasm_smart::<ConstantProductInvariantResponse>(
+                test_pair.address.clone(),
+                &InfinityPairQueryMsg::ConstantProductInvariant {},
+            )
+            .unwrap()
+    };
+
+    let invariant = query_invariant(&router);
+    assert_eq!(
+        invariant,
+        ConstantProductInvariantResponse {
+            total_tokens: Uint128::from(100_000_000u128),
+            total_nfts: 10u64,
+            k: Uint128::from(1_000_000_000u128),
+        }
+    );
+
+    let mut prev_k = invariant.k;
+    for _ in 0..3 {
+        // Sell an NFT to the pair
+        let pair = router
+            .wrap()
+            .query_wasm_smart::<Pair>(test_pair.address.clone(), &InfinityPairQueryMsg::Pair {})
+            .unwrap();
+        let seller_amount = pair.internal.sell_to_pair_quote_summary.unwrap().seller_amount;
+        let token_id = mint_to(&mut router, &creator, &bidder, &minter);
+        approve(&mut router, &bidder, &collection, &test_pair.address, token_id.clone());
+        let response = router.execute_contract(
+            bidder.clone(),
+            test_pair.address.clone(),
+            &InfinityPairExecuteMsg::SwapNftForTokens {
+                token_id,
+                min_output: coin(seller_amount.u128(), NATIVE_DENOM),
+                asset_recipient: None,
+            },
+            &[],
+        );
+        assert!(response.is_ok());
+
+        let invariant = query_invariant(&router);
+        assert!(invariant.k >= prev_k);
+        prev_k = invariant.k;
+
+        // Buy NFTs from the pair
+        for _ in 0..2 {
+            let pair = router
+                .wrap()
+                .query_wasm_smart::<Pair>(test_pair.address.clone(), &InfinityPairQueryMsg::Pair {})
+                .unwrap();
+            let total = pair.internal.buy_from_pair_quote_summary.unwrap().total();
+            let response = router.execute_contract(
+                bidder.clone(),
+                test_pair.address.clone(),
+                &InfinityPairExecuteMsg::SwapTokensForAnyNft {
+                    asset_recipient: None,
+                },
+                &[coin(total.u128(), NATIVE_DENOM)],
+            );
+            assert!(response.is_ok());
+
+            let invariant = query_invariant(&router);
+            assert!(invariant.k >= prev_k);
+            prev_k = invariant.k;
+        }
+    }
+
+    let invariant = query_invariant(&router);
+    assert_eq!(invariant.total_nfts, 7u64);
+    assert!(invariant.k > Uint128::from(1_000_000_000u128));
+
+    // The invariant of a weighted pair is not the product of its reserves
+    let response = router.execute_contract(
+        owner,
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::UpdatePairConfig {
+            is_active: None,
+            pair_type: None,
+            bonding_curve: Some(BondingCurve::WeightedConstantProduct {
+                weight_bps: 8000,
+            }),
+            asset_recipient: None,
+            auto_activate: None,
+            anti_sandwich: None,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: None,
+            allow_sell: None,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let response = router.wrap().query_wasm_smart::<ConstantProductInvariantResponse>(
+        test_pair.address,
+        &InfinityPairQueryMsg::ConstantProductInvariant {},
+    );
+    assert!(response.unwrap_err().to_string().contains(
+        "the invariant of a weighted constant product pair is not the product of its reserves"
+    ));
+}
+
+#[test]
+fn try_trade_pair_typed_swap_errors() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: PairType::Trade {
+                swap_fee_percent: Decimal::percent(0),
+                reinvest_tokens: false,
+                reinvest_nfts: false,
+                max_nfts: None,
+            },
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: false,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        0u64,
+        Uint128::zero(),
+    );
+
+    let token_id = mint_to(&mut router, &creator, &bidder, &minter);
+    approve(&mut router, &bidder, &collection, &test_pair.address, token_id.clone());
+
+    let response = router.execute_contract(
+        bidder.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::SwapNftForTokens {
+            token_id: token_id.clone(),
+            min_output: coin(9_000_000u128, NATIVE_DENOM),
+            asset_recipient: None,
+        },
+        &[],
+    );
+    assert_error_variant(response, ContractError::PairInactive);
+
+    let response = router.execute_contract(
+        owner.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::UpdatePairConfig {
+            is_active: Some(true),
+            pair_type: None,
+            bonding_curve: None,
+            asset_recipient: None,
+            auto_activate: None,
+            anti_sandwich: None,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: None,
+            allow_sell: None,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let response = router.execute_contract(
+        bidder.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::SwapNftForTokens {
+            token_id,
+            min_output: coin(9_000_000u128, NATIVE_DENOM),
+            asset_recipient: None,
+        },
+        &[],
+    );
+    assert_error_variant(response, ContractError::PairCannotProduceQuote);
+
+    let response = router.execute_contract(
+        owner.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::DepositTokens {},
+        &[coin(100_000_000u128, NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+
+    let response = router.execute_contract(
+        bidder.clone(),
+        test_pair.address.clone(),
+        &InfinityPairExecuteMsg::SwapTokensForAnyNft {
+            asset_recipient: None,
+        },
+        &[coin(11_000_000u128, NATIVE_DENOM)],
+    );
+    assert_error_variant(response, ContractError::PairHasNoNfts);
+}
+
+#[test]
+fn try_trade_pair_compound_pair() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        infinity_index,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    // Other pairs selling a single NFT each, the most expensive is not bought
+    let nft_pairs = [10_000_000u128, 12_000_000u128, 50_000_000u128]
+        .into_iter()
+        .map(|spot_price| {
+            create_pair_with_deposits(
+                &mut router,
+                &infinity_global,
+                &infinity_factory,
+                &minter,
+                &collection,
+                &creator,
+                &owner,
+                PairConfig {
+                    pair_type: PairType::Nft,
+                    bonding_curve: BondingCurve::Linear {
+                        spot_price: Uint128::from(spot_price),
+                        delta: Uint128::from(1_000_000u128),
+                    },
+                    is_active: true,
+                    asset_recipient: None,
+                    auto_activate: false,
+                    anti_sandwich: false,
+                    token_recipient: None,
+                    nft_recipient: None,
+                    allow_buy: true,
+                    allow_sell: true,
+                },
+                1u64,
+                Uint128::zero(),
+            )
+        })
+        .collect::<Vec<TestPair>>();
+
+    let total_tokens = Uint128::from(100_000_000u128);
+    let trade_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: PairType::Trade {
+                swap_fee_percent: Decimal::zero(),
+                reinvest_tokens: true,
+                reinvest_nfts: true,
+                max_nfts: None,
+            },
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        0u64,
+        total_tokens,
+    );
+
+    let query_buy_from_pair_quotes = |router: &StargazeApp| {
+        router
+            .wrap()
+            .query_wasm_smart::<Vec<PairQuote>>(
+                infinity_index.clone(),
+                &InfinityIndexQueryMsg::BuyFromPairQuotes {
+                    collection: collection.to_string(),
+                    denom: NATIVE_DENOM.to_string(),
+                    query_options: None,
+                },
+            )
+            .unwrap()
+    };
+    assert!(!query_buy_from_pair_quotes(&router)
+        .iter()
+        .any(|pair_quote| pair_quote.address == trade_pair.address));
+
+    // Only the pair owner can compound
+    let compound_pair_msg = InfinityPairExecuteMsg::CompoundPair {
+        max_buys: 2,
+    };
+    let response =
+        router.execute_contract(bidder, trade_pair.address.clone(), &compound_pair_msg, &[]);
+    assert_error(
+        response,
+        InfinityError::Unauthorized("sender is not the owner of the pair".to_string()).to_string(),
+    );
+
+    let response =
+        router.execute_contract(owner, trade_pair.address.clone(), &compound_pair_msg, &[]);
+    assert!(response.is_ok());
+
+    // The cheapest NFTs are held by the trade pair, paid for with its tokens
+    let mut paid_amount = Uint128::zero();
+    for nft_pair in &nft_pairs[0..2] {
+        paid_amount += nft_pair.pair.internal.buy_from_pair_quote_summary.as_ref().unwrap().total();
+        assert_nft_owner(&router, &collection, nft_pair.token_ids[0].clone(), &trade_pair.address);
+    }
+    assert_nft_owner(
+        &router,
+        &collection,
+        nft_pairs[2].token_ids[0].clone(),
+        &nft_pairs[2].address,
+    );
+
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(trade_pair.address.clone(), &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    assert_eq!(pair.internal.total_nfts, 2);
+    assert_eq!(pair.total_tokens, total_tokens - paid_amount);
+
+    // The trade pair is indexed as selling the NFTs it now holds
+    let trade_pair_quote = query_buy_from_pair_quotes(&router)
+        .into_iter()
+        .find(|pair_quote| pair_quote.address == trade_pair.address)
+        .unwrap();
+    assert_eq!(
+        trade_pair_quote.quote.amount,
+        pair.internal.buy_from_pair_quote_summary.unwrap().total()
+    );
+}
@@ -0,0 +1,269 @@
+use crate::helpers::nft_functions::{approve, approve_all, assert_nft_owner, mint_to};
+use crate::helpers::utils::assert_error;
+use crate::setup::setup_accounts::MarketAccounts;
+use crate::setup::setup_contracts::setup_mock_attributes_oracle;
+use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
+
+use cosmwasm_std::{coin, Addr, Decimal, Uint128};
+use cw_multi_test::Executor;
+use infinity_factory::msg::ExecuteMsg as InfinityFactoryExecuteMsg;
+use infinity_global::{msg::QueryMsg as InfinityGlobalQueryMsg, GlobalConfig};
+use infinity_index::msg::QueryMsg as InfinityIndexQueryMsg;
+use infinity_index::state::PairQuote;
+use infinity_pair::msg::{ExecuteMsg as InfinityPairExecuteMsg, QueryMsg as InfinityPairQueryMsg};
+use infinity_pair::pair::Pair;
+use infinity_pair::state::{BondingCurve, PairConfig, PairImmutable, PairType, TraitFilter};
+use infinity_shared::InfinityError;
+use sg_multi_test::StargazeApp;
+use sg_std::NATIVE_DENOM;
+use test_suite::common_setup::msg::MinterTemplateResponse;
+
+fn create_trait_filter_pair(
+    router: &mut StargazeApp,
+    infinity_global: &Addr,
+    infinity_factory: &Addr,
+    collection: &Addr,
+    owner: &Addr,
+    oracle: &Addr,
+) -> Addr {
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global,
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    let response = router.execute_contract(
+        owner.clone(),
+        infinity_factory.clone(),
+        &InfinityFactoryExecuteMsg::CreatePair {
+            pair_immutable: PairImmutable {
+                collection: collection.to_string(),
+                owner: owner.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                trait_filter: Some(TraitFilter {
+                    oracle: oracle.to_string(),
+                    trait_type: "Background".to_string(),
+                    value: "Gold".to_string(),
+                }),
+                price_oracle: None,
+            },
+            pair_config: PairConfig {
+                pair_type: PairType::Trade {
+                    swap_fee_percent: Decimal::zero(),
+                    reinvest_tokens: false,
+                    reinvest_nfts: true,
+                    max_nfts: None,
+                },
+                bonding_curve: BondingCurve::Linear {
+                    spot_price: Uint128::from(10_000_000u128),
+                    delta: Uint128::from(1_000_000u128),
+                },
+                is_active: true,
+                asset_recipient: None,
+                auto_activate: false,
+                anti_sandwich: false,
+                token_recipient: None,
+                nft_recipient: None,
+                allow_buy: true,
+                allow_sell: true,
+            },
+        },
+        &[global_config.pair_creation_fee],
+    );
+    Addr::unchecked(response.unwrap().events[2].attributes[0].value.clone())
+}
+
+#[test]
+fn try_trait_filter_pair() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_index,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let owner_matching_token_id = mint_to(&mut router, &creator, &owner, &minter);
+    let owner_other_token_id = mint_to(&mut router, &creator, &owner, &minter);
+    let bidder_matching_token_id = mint_to(&mut router, &creator, &bidder, &minter);
+    let bidder_other_token_id = mint_to(&mut router, &creator, &bidder, &minter);
+
+    let oracle = setup_mock_attributes_oracle(
+        &mut router,
+        &creator,
+        vec![owner_matching_token_id.clone(), bidder_matching_token_id.clone()],
+    );
+
+    let pair_addr = create_trait_filter_pair(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &collection,
+        &owner,
+        &oracle,
+    );
+
+    let response = router.execute_contract(
+        owner.clone(),
+        pair_addr.clone(),
+        &InfinityPairExecuteMsg::DepositTokens {},
+        &[coin(100_000_000u128, NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+
+    // Cannot deposit an NFT without the trait
+    approve_all(&mut router, &owner, &collection, &pair_addr);
+    let response = router.execute_contract(
+        owner.clone(),
+        pair_addr.clone(),
+        &InfinityPairExecuteMsg::DepositNfts {
+            collection: collection.to_string(),
+            token_ids: vec![owner_other_token_id.clone()],
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput(format!(
+            "nft {} does not match the pair trait filter",
+            owner_other_token_id
+        ))
+        .to_string(),
+    );
+
+    // Can deposit an NFT with the trait
+    let response = router.execute_contract(
+        owner.clone(),
+        pair_addr.clone(),
+        &InfinityPairExecuteMsg::DepositNfts {
+            collection: collection.to_string(),
+            token_ids: vec![owner_matching_token_id.clone()],
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+    assert_nft_owner(&router, &collection, owner_matching_token_id, &pair_addr);
+
+    let pair = router
+        .wrap()
+        .query_wasm_smart::<Pair>(pair_addr.clone(), &InfinityPairQueryMsg::Pair {})
+        .unwrap();
+    let seller_amount = pair.internal.sell_to_pair_quote_summary.unwrap().seller_amount;
+
+    // Cannot sell an NFT without the trait into the pair
+    approve(&mut router, &bidder, &collection, &pair_addr, bidder_other_token_id.clone());
+    let response = router.execute_contract(
+        bidder.clone(),
+        pair_addr.clone(),
+        &InfinityPairExecuteMsg::SwapNftForTokens {
+            token_id: bidder_other_token_id.clone(),
+            min_output: coin(seller_amount.u128(), NATIVE_DENOM),
+            asset_recipient: None,
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput(format!(
+            "nft {} does not match the pair trait filter",
+            bidder_other_token_id
+        ))
+        .to_string(),
+    );
+
+    // Can sell an NFT with the trait into the pair
+    approve(&mut router, &bidder, &collection, &pair_addr, bidder_matching_token_id.clone());
+    let response = router.execute_contract(
+        bidder.clone(),
+        pair_addr.clone(),
+        &InfinityPairExecuteMsg::SwapNftForTokens {
+            token_id: bidder_matching_token_id.clone(),
+            min_output: coin(seller_amount.u128(), NATIVE_DENOM),
+            asset_recipient: None,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+    assert_nft_owner(&router, &collection, bidder_matching_token_id, &pair_addr);
+
+    // Trait filtered pairs are not listed in the collection wide sell to pair index
+    let sell_to_pair_quotes = router
+        .wrap()
+        .query_wasm_smart::<Vec<PairQuote>>(
+            infinity_index,
+            &InfinityIndexQueryMsg::SellToPairQuotes {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                query_options: None,
+            },
+        )
+        .unwrap();
+    assert!(sell_to_pair_quotes.iter().all(|pair_quote| pair_quote.address != pair_addr));
+}
+
+#[test]
+fn try_trait_filter_pair_oracle_error() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        ..
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let token_id = mint_to(&mut router, &creator, &owner, &minter);
+
+    // The oracle is not a contract, so every oracle query fails
+    let pair_addr = create_trait_filter_pair(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &collection,
+        &owner,
+        &Addr::unchecked("invalid_oracle"),
+    );
+
+    approve_all(&mut router, &owner, &collection, &pair_addr);
+    let response = router.execute_contract(
+        owner.clone(),
+        pair_addr,
+        &InfinityPairExecuteMsg::DepositNfts {
+            collection: collection.to_string(),
+            token_ids: vec![token_id],
+        },
+        &[],
+    );
+    let err = response.unwrap_err().source().unwrap().to_string();
+    assert!(err.starts_with("InvalidInput: attributes oracle query failed"));
+}
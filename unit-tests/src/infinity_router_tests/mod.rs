@@ -2,3 +2,5 @@
 mod nfts_for_tokens_router_tests;
 #[cfg(test)]
 mod tokens_for_nfts_router_tests;
+#[cfg(test)]
+mod zero_nft_trade_pair_router_tests;
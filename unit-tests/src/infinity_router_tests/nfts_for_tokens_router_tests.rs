@@ -1,16 +1,29 @@
 use crate::helpers::nft_functions::{approve, assert_nft_owner, mint_to};
 use crate::helpers::pair_functions::create_pair_with_deposits;
-use crate::setup::setup_accounts::MarketAccounts;
+use crate::helpers::utils::assert_error;
+use crate::setup::setup_accounts::{setup_addtl_account, MarketAccounts};
+use crate::setup::setup_contracts::setup_mock_attributes_oracle;
 use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
 
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{coin, Addr, Decimal, Uint128};
 use cw_multi_test::Executor;
+use infinity_factory::msg::ExecuteMsg as InfinityFactoryExecuteMsg;
 use infinity_global::{msg::QueryMsg as InfinityGlobalQueryMsg, GlobalConfig};
-use infinity_pair::state::{BondingCurve, PairConfig, PairType};
+use infinity_index::msg::QuoteSide;
+use infinity_pair::msg::{ExecuteMsg as InfinityPairExecuteMsg, TransactionType};
+use infinity_pair::state::{BondingCurve, PairConfig, PairImmutable, PairType, TraitFilter};
 use infinity_router::msg::{
-    ExecuteMsg as InfinityRouterExecuteMsg, QueryMsg as InfinityRouterQueryMsg, SellOrder,
+    ExecuteMsg as InfinityRouterExecuteMsg, QueryMsg as InfinityRouterQueryMsg,
+    RoutedNftForTokensOrderQuote, SellOrder, SellOrderRoute, SimSellProceedsResponse,
+    SimSwapNftsForTokensBoundedResponse, SimSwapNftsForTokensResponse,
+    SimSwapNftsForTokensRoutedResponse, StaleQuote, StaleQuoteReason, SwapParams, SwapTotals,
+    VolumeWeightedQuoteResponse,
 };
-use infinity_router::nfts_for_tokens_iterators::types::NftForTokensQuote;
+use infinity_router::nfts_for_tokens_iterators::types::{
+    NftForTokensOrderQuote, NftForTokensQuote, NftForTokensSource,
+};
+use infinity_router::ContractError as InfinityRouterContractError;
+use infinity_shared::InfinityError;
 use sg721_base::msg::{CollectionInfoResponse, QueryMsg as Sg721QueryMsg};
 use sg_std::NATIVE_DENOM;
 use test_suite::common_setup::msg::MinterTemplateResponse;
@@ -73,6 +86,12 @@ fn try_router_nfts_for_tokens_swap_simple() {
                 },
                 is_active: true,
                 asset_recipient: None,
+                auto_activate: false,
+                anti_sandwich: false,
+                token_recipient: None,
+                nft_recipient: None,
+                allow_buy: true,
+                allow_sell: true,
             },
             0u64,
             Uint128::from(10_000_000_000u128),
@@ -122,13 +141,1509 @@ fn try_router_nfts_for_tokens_swap_simple() {
                     min_output: quotes[idx].amount,
                 })
                 .collect(),
+            min_total_output: None,
+            swap_params: None,
+            filter_sources: None,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    assert_nft_owner(&router, &collection, token_ids[0].clone(), &owner);
+    assert_nft_owner(&router, &collection, token_ids[1].clone(), &owner);
+}
+
+#[test]
+fn try_router_nfts_for_tokens_volume_weighted_quote() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        ..
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    for spot_price in [100_000_000u128, 95_000_000u128, 90_000_000u128] {
+        create_pair_with_deposits(
+            &mut router,
+            &infinity_global,
+            &infinity_factory,
+            &minter,
+            &collection,
+            &creator,
+            &owner,
+            PairConfig {
+                pair_type: PairType::Token,
+                bonding_curve: BondingCurve::Linear {
+                    spot_price: Uint128::from(spot_price),
+                    delta: Uint128::from(3_000_000u128),
+                },
+                is_active: true,
+                asset_recipient: None,
+                auto_activate: false,
+                anti_sandwich: false,
+                token_recipient: None,
+                nft_recipient: None,
+                allow_buy: true,
+                allow_sell: true,
+            },
+            0u64,
+            Uint128::from(10_000_000_000u128),
+        );
+    }
+
+    let num_nfts = 7u32;
+
+    let quotes = router
+        .wrap()
+        .query_wasm_smart::<Vec<NftForTokensQuote>>(
+            &global_config.infinity_router.clone(),
+            &InfinityRouterQueryMsg::NftsForTokens {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                limit: num_nfts,
+                filter_sources: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(quotes.len(), num_nfts as usize);
+
+    let volume_weighted_quote = router
+        .wrap()
+        .query_wasm_smart::<VolumeWeightedQuoteResponse>(
+            &global_config.infinity_router,
+            &InfinityRouterQueryMsg::VolumeWeightedQuote {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                num_nfts,
+                transaction_type: TransactionType::UserSubmitsNfts,
+            },
+        )
+        .unwrap();
+
+    let total = quotes.iter().map(|quote| quote.amount).sum::<Uint128>();
+    assert_eq!(volume_weighted_quote.num_nfts, num_nfts);
+    assert_eq!(volume_weighted_quote.total, total);
+    assert_eq!(volume_weighted_quote.average, Some(total / Uint128::from(num_nfts)));
+    assert!(volume_weighted_quote.average.unwrap() < quotes[0].amount);
+}
+
+#[test]
+fn try_router_nfts_for_tokens_allow_partial() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    // Pair can only afford to buy two NFTs, 10_000_000 + 9_000_000
+    create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: PairType::Token,
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        0u64,
+        Uint128::from(25_000_000u128),
+    );
+
+    let mut token_ids: Vec<String> = vec![];
+    for _ in 0..3 {
+        let token_id = mint_to(&mut router, &creator.clone(), &bidder.clone(), &minter);
+        approve(
+            &mut router,
+            &bidder,
+            &collection,
+            &global_config.infinity_router,
+            token_id.clone(),
+        );
+        token_ids.push(token_id)
+    }
+
+    let sell_orders = token_ids
+        .iter()
+        .map(|token_id| SellOrder {
+            input_token_id: token_id.clone(),
+            min_output: Uint128::one(),
+        })
+        .collect::<Vec<SellOrder>>();
+
+    // Liquidity shortfall without allow_partial reverts
+    let response = router.execute_contract(
+        bidder.clone(),
+        global_config.infinity_router.clone(),
+        &InfinityRouterExecuteMsg::SwapNftsForTokens {
+            collection: collection.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            sell_orders: sell_orders.clone(),
+            min_total_output: None,
             swap_params: None,
             filter_sources: None,
         },
         &[],
     );
+    assert_error(
+        response,
+        InfinityRouterContractError::SwapError(
+            "unable to swap all nfts for tokens, requested swaps: 3, actual swaps: 2".to_string(),
+        )
+        .to_string(),
+    );
+
+    // Invalid orders revert even with allow_partial
+    let unowned_token_id = mint_to(&mut router, &creator.clone(), &creator.clone(), &minter);
+    let mut invalid_sell_orders = sell_orders.clone();
+    invalid_sell_orders[0].input_token_id = unowned_token_id;
+    let response = router.execute_contract(
+        bidder.clone(),
+        global_config.infinity_router.clone(),
+        &InfinityRouterExecuteMsg::SwapNftsForTokens {
+            collection: collection.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            sell_orders: invalid_sell_orders,
+            min_total_output: None,
+            swap_params: Some(SwapParams {
+                robust: None,
+                allow_partial: Some(true),
+                asset_recipient: None,
+                proceeds_splits: None,
+                max_total_network_fee: None,
+            }),
+            filter_sources: None,
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::Unauthorized("sender is not the owner of the nft".to_string()).to_string(),
+    );
+    assert_nft_owner(&router, &collection, token_ids[1].clone(), &bidder);
+
+    // Liquidity shortfall with allow_partial commits the filled swaps
+    let response = router.execute_contract(
+        bidder.clone(),
+        global_config.infinity_router.clone(),
+        &InfinityRouterExecuteMsg::SwapNftsForTokens {
+            collection: collection.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            sell_orders,
+            min_total_output: None,
+            swap_params: Some(SwapParams {
+                robust: None,
+                allow_partial: Some(true),
+                asset_recipient: None,
+                proceeds_splits: None,
+                max_total_network_fee: None,
+            }),
+            filter_sources: None,
+        },
+        &[],
+    );
     assert!(response.is_ok());
 
     assert_nft_owner(&router, &collection, token_ids[0].clone(), &owner);
     assert_nft_owner(&router, &collection, token_ids[1].clone(), &owner);
+    assert_nft_owner(&router, &collection, token_ids[2].clone(), &bidder);
+}
+
+#[test]
+fn try_router_nfts_for_tokens_robust() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: PairType::Token,
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        0u64,
+        Uint128::from(100_000_000u128),
+    );
+
+    let mut token_ids: Vec<String> = vec![];
+    for _ in 0..3 {
+        let token_id = mint_to(&mut router, &creator.clone(), &bidder.clone(), &minter);
+        approve(
+            &mut router,
+            &bidder,
+            &collection,
+            &global_config.infinity_router,
+            token_id.clone(),
+        );
+        token_ids.push(token_id)
+    }
+
+    // The second order asks for more than the second quote of 8_460_000
+    let mut sell_orders = token_ids
+        .iter()
+        .map(|token_id| SellOrder {
+            input_token_id: token_id.clone(),
+            min_output: Uint128::one(),
+        })
+        .collect::<Vec<SellOrder>>();
+    sell_orders[1].min_output = Uint128::from(9_000_000u128);
+
+    let robust_swap_params = Some(SwapParams {
+        robust: Some(true),
+        allow_partial: None,
+        asset_recipient: None,
+        proceeds_splits: None,
+        max_total_network_fee: None,
+    });
+
+    // Authorization errors abort even in robust mode
+    let unowned_token_id = mint_to(&mut router, &creator.clone(), &creator.clone(), &minter);
+    let mut invalid_sell_orders = sell_orders.clone();
+    invalid_sell_orders[0].input_token_id = unowned_token_id;
+    let response = router.execute_contract(
+        bidder.clone(),
+        global_config.infinity_router.clone(),
+        &InfinityRouterExecuteMsg::SwapNftsForTokens {
+            collection: collection.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            sell_orders: invalid_sell_orders,
+            min_total_output: None,
+            swap_params: robust_swap_params.clone(),
+            filter_sources: None,
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::Unauthorized("sender is not the owner of the nft".to_string()).to_string(),
+    );
+
+    // A price shortfall stops filling at that order, and robust mode commits the filled swaps
+    let response = router.execute_contract(
+        bidder.clone(),
+        global_config.infinity_router.clone(),
+        &InfinityRouterExecuteMsg::SwapNftsForTokens {
+            collection: collection.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            sell_orders,
+            min_total_output: None,
+            swap_params: robust_swap_params,
+            filter_sources: None,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    assert_nft_owner(&router, &collection, token_ids[0].clone(), &owner);
+    assert_nft_owner(&router, &collection, token_ids[1].clone(), &bidder);
+    assert_nft_owner(&router, &collection, token_ids[2].clone(), &bidder);
+}
+
+#[test]
+fn try_router_nfts_for_tokens_min_total_output() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: PairType::Token,
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        0u64,
+        Uint128::from(100_000_000u128),
+    );
+
+    let num_nfts = 2;
+    let quotes = router
+        .wrap()
+        .query_wasm_smart::<Vec<NftForTokensQuote>>(
+            &global_config.infinity_router.clone(),
+            &InfinityRouterQueryMsg::NftsForTokens {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                limit: num_nfts,
+                filter_sources: None,
+            },
+        )
+        .unwrap();
+    let total_output = quotes.iter().map(|quote| quote.amount).sum::<Uint128>();
+
+    let mut token_ids: Vec<String> = vec![];
+    for _ in 0..num_nfts {
+        let token_id = mint_to(&mut router, &creator.clone(), &bidder.clone(), &minter);
+        approve(
+            &mut router,
+            &bidder,
+            &collection,
+            &global_config.infinity_router,
+            token_id.clone(),
+        );
+        token_ids.push(token_id)
+    }
+    let sell_orders = token_ids
+        .iter()
+        .map(|token_id| SellOrder {
+            input_token_id: token_id.clone(),
+            min_output: Uint128::one(),
+        })
+        .collect::<Vec<SellOrder>>();
+
+    // A minimum total output above the achievable proceeds reverts the swap
+    let min_total_output = total_output + Uint128::one();
+    let response = router.execute_contract(
+        bidder.clone(),
+        global_config.infinity_router.clone(),
+        &InfinityRouterExecuteMsg::SwapNftsForTokens {
+            collection: collection.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            sell_orders: sell_orders.clone(),
+            min_total_output: Some(min_total_output),
+            swap_params: None,
+            filter_sources: None,
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityRouterContractError::SwapError(format!(
+            "unable to receive the minimum total output, min total output: {}, actual output: {}",
+            min_total_output, total_output
+        ))
+        .to_string(),
+    );
+
+    // A minimum total output equal to the proceeds succeeds
+    let response = router.execute_contract(
+        bidder.clone(),
+        global_config.infinity_router.clone(),
+        &InfinityRouterExecuteMsg::SwapNftsForTokens {
+            collection: collection.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            sell_orders,
+            min_total_output: Some(total_output),
+            swap_params: None,
+            filter_sources: None,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    assert_nft_owner(&router, &collection, token_ids[0].clone(), &owner);
+    assert_nft_owner(&router, &collection, token_ids[1].clone(), &owner);
+}
+
+#[test]
+fn try_router_sim_swap_nfts_for_tokens_unfilled() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    // Pair can only afford to buy two NFTs, 10_000_000 + 9_000_000
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: PairType::Token,
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        0u64,
+        Uint128::from(25_000_000u128),
+    );
+
+    let mut token_ids: Vec<String> = vec![];
+    for _ in 0..4 {
+        let token_id = mint_to(&mut router, &creator.clone(), &bidder.clone(), &minter);
+        token_ids.push(token_id)
+    }
+
+    let sell_orders = token_ids
+        .iter()
+        .map(|token_id| SellOrder {
+            input_token_id: token_id.clone(),
+            min_output: Uint128::one(),
+        })
+        .collect::<Vec<SellOrder>>();
+
+    let response = router
+        .wrap()
+        .query_wasm_smart::<SimSwapNftsForTokensResponse>(
+            &global_config.infinity_router,
+            &InfinityRouterQueryMsg::SimSwapNftsForTokens {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                sell_orders,
+                filter_sources: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        response,
+        SimSwapNftsForTokensResponse {
+            filled: vec![
+                NftForTokensOrderQuote {
+                    token_id: token_ids[0].clone(),
+                    address: test_pair.address.clone(),
+                    amount: Uint128::from(9_400_000u128),
+                    source: NftForTokensSource::Infinity,
+                },
+                NftForTokensOrderQuote {
+                    token_id: token_ids[1].clone(),
+                    address: test_pair.address,
+                    amount: Uint128::from(8_460_000u128),
+                    source: NftForTokensSource::Infinity,
+                },
+            ],
+            unfilled: token_ids[2..].to_vec(),
+            // Fair burn is 1% and royalties are 5% of each sale price
+            totals: SwapTotals {
+                total_sale_price: Uint128::from(19_000_000u128),
+                total_fair_burn_fee: Uint128::from(190_000u128),
+                total_royalty_fee: Uint128::from(950_000u128),
+                total_swap_fee: Uint128::zero(),
+                total_seller_proceeds: Uint128::from(17_860_000u128),
+            },
+        }
+    );
+}
+
+#[test]
+fn try_router_sim_sell_proceeds() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    let token_id = mint_to(&mut router, &creator, &bidder, &minter);
+
+    // No proceeds are quoted when no pair can buy the NFT
+    let proceeds = router
+        .wrap()
+        .query_wasm_smart::<Option<SimSellProceedsResponse>>(
+            &global_config.infinity_router,
+            &InfinityRouterQueryMsg::SimSellProceeds {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                token_id: token_id.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(proceeds, None);
+
+    let mut pairs = vec![];
+    for spot_price in [90_000_000u128, 100_000_000u128] {
+        pairs.push(create_pair_with_deposits(
+            &mut router,
+            &infinity_global,
+            &infinity_factory,
+            &minter,
+            &collection,
+            &creator,
+            &owner,
+            PairConfig {
+                pair_type: PairType::Token,
+                bonding_curve: BondingCurve::Linear {
+                    spot_price: Uint128::from(spot_price),
+                    delta: Uint128::from(1_000_000u128),
+                },
+                is_active: true,
+                asset_recipient: None,
+                auto_activate: false,
+                anti_sandwich: false,
+                token_recipient: None,
+                nft_recipient: None,
+                allow_buy: true,
+                allow_sell: true,
+            },
+            0u64,
+            Uint128::from(1_000_000_000u128),
+        ));
+    }
+
+    // The proceeds are quoted against the best pair
+    let proceeds = router
+        .wrap()
+        .query_wasm_smart::<Option<SimSellProceedsResponse>>(
+            &global_config.infinity_router,
+            &InfinityRouterQueryMsg::SimSellProceeds {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                token_id: token_id.clone(),
+            },
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(proceeds.token_id, token_id);
+    assert_eq!(proceeds.pair, pairs[1].address);
+    assert_eq!(proceeds.quote_summary.seller_amount, proceeds.net_to_seller);
+    assert_eq!(
+        proceeds.net_to_seller
+            + proceeds.quote_summary.fair_burn.amount
+            + proceeds.quote_summary.royalty.as_ref().unwrap().amount,
+        Uint128::from(100_000_000u128)
+    );
+
+    // The seller receives the quoted proceeds when the order is executed
+    approve(&mut router, &bidder, &collection, &global_config.infinity_router, token_id.clone());
+    let balance_before = router.wrap().query_balance(&bidder, NATIVE_DENOM).unwrap().amount;
+    let response = router.execute_contract(
+        bidder.clone(),
+        global_config.infinity_router.clone(),
+        &InfinityRouterExecuteMsg::SwapNftsForTokens {
+            collection: collection.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            sell_orders: vec![SellOrder {
+                input_token_id: token_id.clone(),
+                min_output: proceeds.net_to_seller,
+            }],
+            min_total_output: None,
+            swap_params: None,
+            filter_sources: None,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+    assert_nft_owner(&router, &collection, token_id, &owner);
+
+    let balance_after = router.wrap().query_balance(&bidder, NATIVE_DENOM).unwrap().amount;
+    assert_eq!(balance_after - balance_before, proceeds.net_to_seller);
+}
+
+#[test]
+fn try_router_sim_sell_proceeds_selects_best_pair() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    let mut pairs = vec![];
+    for (spot_price, num_tokens) in [
+        (30_000_000u128, 1_000_000_000u128),
+        (50_000_000u128, 1_000_000_000u128),
+        (40_000_000u128, 1_000_000_000u128),
+        // The highest priced pair cannot afford a swap, so it is not quoted
+        (60_000_000u128, 10_000_000u128),
+    ] {
+        pairs.push(create_pair_with_deposits(
+            &mut router,
+            &infinity_global,
+            &infinity_factory,
+            &minter,
+            &collection,
+            &creator,
+            &owner,
+            PairConfig {
+                pair_type: PairType::Token,
+                bonding_curve: BondingCurve::Linear {
+                    spot_price: Uint128::from(spot_price),
+                    delta: Uint128::from(1_000_000u128),
+                },
+                is_active: true,
+                asset_recipient: None,
+                auto_activate: false,
+                anti_sandwich: false,
+                token_recipient: None,
+                nft_recipient: None,
+                allow_buy: true,
+                allow_sell: true,
+            },
+            0u64,
+            Uint128::from(num_tokens),
+        ));
+    }
+
+    let token_id = mint_to(&mut router, &creator, &bidder, &minter);
+    let proceeds = router
+        .wrap()
+        .query_wasm_smart::<Option<SimSellProceedsResponse>>(
+            &global_config.infinity_router,
+            &InfinityRouterQueryMsg::SimSellProceeds {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                token_id: token_id.clone(),
+            },
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(proceeds.pair, pairs[1].address);
+
+    // The selected pair matches the pair used by SimSwapNftsForTokens
+    let sim_response = router
+        .wrap()
+        .query_wasm_smart::<SimSwapNftsForTokensResponse>(
+            &global_config.infinity_router,
+            &InfinityRouterQueryMsg::SimSwapNftsForTokens {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                sell_orders: vec![SellOrder {
+                    input_token_id: token_id,
+                    min_output: Uint128::one(),
+                }],
+                filter_sources: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(sim_response.filled[0].address, proceeds.pair);
+    assert_eq!(sim_response.filled[0].amount, proceeds.net_to_seller);
+}
+
+#[test]
+fn try_router_sim_swap_nfts_for_tokens_routed() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    let mut token_ids: Vec<String> = vec![];
+    for _ in 0..5 {
+        let token_id = mint_to(&mut router, &creator.clone(), &bidder.clone(), &minter);
+        token_ids.push(token_id)
+    }
+
+    // Only the first and third NFTs have the trait wanted by the specific pair
+    let oracle = setup_mock_attributes_oracle(
+        &mut router,
+        &creator,
+        vec![token_ids[0].clone(), token_ids[2].clone()],
+    );
+
+    let response = router.execute_contract(
+        owner.clone(),
+        infinity_factory.clone(),
+        &InfinityFactoryExecuteMsg::CreatePair {
+            pair_immutable: PairImmutable {
+                collection: collection.to_string(),
+                owner: owner.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                trait_filter: Some(TraitFilter {
+                    oracle: oracle.to_string(),
+                    trait_type: "Background".to_string(),
+                    value: "Gold".to_string(),
+                }),
+                price_oracle: None,
+            },
+            pair_config: PairConfig {
+                pair_type: PairType::Token,
+                bonding_curve: BondingCurve::Linear {
+                    spot_price: Uint128::from(15_000_000u128),
+                    delta: Uint128::from(1_000_000u128),
+                },
+                is_active: true,
+                asset_recipient: None,
+                auto_activate: false,
+                anti_sandwich: false,
+                token_recipient: None,
+                nft_recipient: None,
+                allow_buy: true,
+                allow_sell: true,
+            },
+        },
+        &[global_config.pair_creation_fee.clone()],
+    );
+    let specific_pair = Addr::unchecked(response.unwrap().events[2].attributes[0].value.clone());
+
+    let response = router.execute_contract(
+        owner.clone(),
+        specific_pair.clone(),
+        &InfinityPairExecuteMsg::DepositTokens {},
+        &[coin(100_000_000u128, NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+
+    let mut trade_pairs = vec![];
+    for spot_price in [10_000_000u128, 12_000_000u128] {
+        trade_pairs.push(create_pair_with_deposits(
+            &mut router,
+            &infinity_global,
+            &infinity_factory,
+            &minter,
+            &collection,
+            &creator,
+            &owner,
+            PairConfig {
+                pair_type: PairType::Trade {
+                    swap_fee_percent: Decimal::zero(),
+                    reinvest_tokens: false,
+                    reinvest_nfts: false,
+                    max_nfts: None,
+                },
+                bonding_curve: BondingCurve::Linear {
+                    spot_price: Uint128::from(spot_price),
+                    delta: Uint128::from(1_000_000u128),
+                },
+                is_active: true,
+                asset_recipient: None,
+                auto_activate: false,
+                anti_sandwich: false,
+                token_recipient: None,
+                nft_recipient: None,
+                allow_buy: true,
+                allow_sell: true,
+            },
+            0u64,
+            Uint128::from(100_000_000u128),
+        ));
+    }
+
+    let mut sell_orders = token_ids
+        .iter()
+        .map(|token_id| SellOrder {
+            input_token_id: token_id.clone(),
+            min_output: Uint128::one(),
+        })
+        .collect::<Vec<SellOrder>>();
+    sell_orders[4].min_output = Uint128::from(100_000_000u128);
+
+    let response = router
+        .wrap()
+        .query_wasm_smart::<SimSwapNftsForTokensRoutedResponse>(
+            &global_config.infinity_router,
+            &InfinityRouterQueryMsg::SimSwapNftsForTokensRouted {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                sell_orders,
+                specific_pairs: vec![specific_pair.to_string()],
+            },
+        )
+        .unwrap();
+
+    // Matching NFTs go to the specific pair, the rest to the best Trade pair,
+    // and the last order cannot be filled at its min output.
+    // Fair burn is 1% and royalties are 5% of each sale price.
+    let routed_quote = |route: SellOrderRoute, token_id: &String, address: &Addr, amount: u128| {
+        RoutedNftForTokensOrderQuote {
+            route,
+            quote: NftForTokensOrderQuote {
+                token_id: token_id.clone(),
+                address: address.clone(),
+                amount: Uint128::from(amount),
+                source: NftForTokensSource::Infinity,
+            },
+        }
+    };
+    assert_eq!(
+        response,
+        SimSwapNftsForTokensRoutedResponse {
+            filled: vec![
+                routed_quote(
+                    SellOrderRoute::SpecificPair,
+                    &token_ids[0],
+                    &specific_pair,
+                    14_100_000u128
+                ),
+                routed_quote(
+                    SellOrderRoute::CollectionQuote,
+                    &token_ids[1],
+                    &trade_pairs[1].address,
+                    11_280_000u128
+                ),
+                routed_quote(
+                    SellOrderRoute::SpecificPair,
+                    &token_ids[2],
+                    &specific_pair,
+                    13_160_000u128
+                ),
+                routed_quote(
+                    SellOrderRoute::CollectionQuote,
+                    &token_ids[3],
+                    &trade_pairs[1].address,
+                    10_340_000u128
+                ),
+            ],
+            unfilled: vec![token_ids[4].clone()],
+            totals: SwapTotals {
+                total_sale_price: Uint128::from(52_000_000u128),
+                total_fair_burn_fee: Uint128::from(520_000u128),
+                total_royalty_fee: Uint128::from(2_600_000u128),
+                total_swap_fee: Uint128::zero(),
+                total_seller_proceeds: Uint128::from(48_880_000u128),
+            },
+        }
+    );
+
+    // Pairs without a trait filter are already covered by the collection wide quotes
+    let response = router.wrap().query_wasm_smart::<SimSwapNftsForTokensRoutedResponse>(
+        &global_config.infinity_router,
+        &InfinityRouterQueryMsg::SimSwapNftsForTokensRouted {
+            collection: collection.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            sell_orders: vec![],
+            specific_pairs: vec![trade_pairs[0].address.to_string()],
+        },
+    );
+    assert!(response.is_err());
+}
+
+#[test]
+fn try_router_index_health() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        ..
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    let mut test_pairs = vec![];
+    for _ in 0..2 {
+        test_pairs.push(create_pair_with_deposits(
+            &mut router,
+            &infinity_global,
+            &infinity_factory,
+            &minter,
+            &collection,
+            &creator,
+            &owner,
+            PairConfig {
+                pair_type: PairType::Trade {
+                    swap_fee_percent: Decimal::zero(),
+                    reinvest_tokens: false,
+                    reinvest_nfts: false,
+                    max_nfts: None,
+                },
+                bonding_curve: BondingCurve::ConstantProduct,
+                is_active: true,
+                asset_recipient: None,
+                auto_activate: false,
+                anti_sandwich: false,
+                token_recipient: None,
+                nft_recipient: None,
+                allow_buy: true,
+                allow_sell: true,
+            },
+            10u64,
+            Uint128::from(100_000_000u128),
+        ));
+    }
+
+    let index_health_msg = InfinityRouterQueryMsg::IndexHealth {
+        collection: collection.to_string(),
+        denom: NATIVE_DENOM.to_string(),
+        limit: 10,
+    };
+
+    // The index matches both pairs after creation
+    let stale_quotes = router
+        .wrap()
+        .query_wasm_smart::<Vec<StaleQuote>>(
+            global_config.infinity_router.clone(),
+            &index_health_msg,
+        )
+        .unwrap();
+    assert!(stale_quotes.is_empty());
+
+    // Sending tokens directly to a constant product pair changes its quotes,
+    // without the pair updating the index
+    let stale_pair = &test_pairs[1];
+    let response = router.send_tokens(
+        owner.clone(),
+        stale_pair.address.clone(),
+        &[coin(50_000_000u128, NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+
+    let stale_quotes = router
+        .wrap()
+        .query_wasm_smart::<Vec<StaleQuote>>(global_config.infinity_router, &index_health_msg)
+        .unwrap();
+    assert_eq!(stale_quotes.len(), 2);
+    for stale_quote in &stale_quotes {
+        assert_eq!(stale_quote.pair, stale_pair.address);
+        assert_eq!(stale_quote.reason, StaleQuoteReason::QuoteMismatch);
+        assert!(stale_quote.expected_amount.is_some());
+        assert_ne!(stale_quote.expected_amount, Some(stale_quote.indexed_amount));
+    }
+    assert_eq!(stale_quotes[0].side, QuoteSide::SellToPair);
+    assert_eq!(
+        stale_quotes[0].indexed_amount,
+        stale_pair.pair.internal.sell_to_pair_quote_summary.as_ref().unwrap().seller_amount
+    );
+    assert_eq!(stale_quotes[1].side, QuoteSide::BuyFromPair);
+    assert_eq!(
+        stale_quotes[1].indexed_amount,
+        stale_pair.pair.internal.buy_from_pair_quote_summary.as_ref().unwrap().total()
+    );
+}
+
+#[test]
+fn try_router_sim_swap_nfts_for_tokens_bounded() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    // Pair can afford to buy all 50 NFTs
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: PairType::Token,
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(1_000_000u128),
+                delta: Uint128::from(10_000u128),
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        0u64,
+        Uint128::from(50_000_000u128),
+    );
+
+    let mut token_ids: Vec<String> = vec![];
+    for _ in 0..50 {
+        let token_id = mint_to(&mut router, &creator.clone(), &bidder.clone(), &minter);
+        token_ids.push(token_id)
+    }
+
+    let sell_orders = token_ids
+        .iter()
+        .map(|token_id| SellOrder {
+            input_token_id: token_id.clone(),
+            min_output: Uint128::one(),
+        })
+        .collect::<Vec<SellOrder>>();
+
+    let response = router
+        .wrap()
+        .query_wasm_smart::<SimSwapNftsForTokensBoundedResponse>(
+            &global_config.infinity_router,
+            &InfinityRouterQueryMsg::SimSwapNftsForTokensBounded {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                sell_orders: sell_orders.clone(),
+                filter_sources: None,
+                max_swaps: 20,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(response.filled.len(), 20);
+    assert_eq!(
+        response.filled.iter().map(|f| f.token_id.clone()).collect::<Vec<String>>(),
+        token_ids[..20].to_vec()
+    );
+    assert!(response.filled.iter().all(|f| f.address == test_pair.address));
+    assert_eq!(response.remaining.len(), 30);
+    assert_eq!(response.remaining, sell_orders[20..].to_vec());
+}
+
+#[test]
+fn try_router_nfts_for_tokens_proceeds_splits() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: PairType::Token,
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        0u64,
+        Uint128::from(100_000_000u128),
+    );
+
+    let mut token_ids: Vec<String> = vec![];
+    for _ in 0..3 {
+        let token_id = mint_to(&mut router, &creator.clone(), &bidder.clone(), &minter);
+        approve(
+            &mut router,
+            &bidder,
+            &collection,
+            &global_config.infinity_router,
+            token_id.clone(),
+        );
+        token_ids.push(token_id)
+    }
+    let sell_orders = token_ids
+        .iter()
+        .map(|token_id| SellOrder {
+            input_token_id: token_id.clone(),
+            min_output: Uint128::one(),
+        })
+        .collect::<Vec<SellOrder>>();
+
+    let sim_response = router
+        .wrap()
+        .query_wasm_smart::<SimSwapNftsForTokensResponse>(
+            &global_config.infinity_router,
+            &InfinityRouterQueryMsg::SimSwapNftsForTokens {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                sell_orders: sell_orders.clone(),
+                filter_sources: None,
+            },
+        )
+        .unwrap();
+    let total_seller_proceeds = sim_response.totals.total_seller_proceeds;
+
+    let splitter = setup_addtl_account(&mut router, "splitter", 0u128).unwrap();
+    let cold_wallet = setup_addtl_account(&mut router, "cold-wallet", 0u128).unwrap();
+
+    // Splits that do not sum to 10000 bps are rejected
+    let response = router.execute_contract(
+        bidder.clone(),
+        global_config.infinity_router.clone(),
+        &InfinityRouterExecuteMsg::SwapNftsForTokens {
+            collection: collection.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            sell_orders: sell_orders.clone(),
+            min_total_output: None,
+            swap_params: Some(SwapParams {
+                robust: None,
+                allow_partial: None,
+                asset_recipient: None,
+                proceeds_splits: Some(vec![
+                    (splitter.to_string(), 7000u16),
+                    (cold_wallet.to_string(), 2000u16),
+                ]),
+                max_total_network_fee: None,
+            }),
+            filter_sources: None,
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::InvalidInput("proceeds splits must sum to 10000 bps".to_string())
+            .to_string(),
+    );
+
+    // Only the router can split the proceeds it has received
+    let response = router.execute_contract(
+        bidder.clone(),
+        global_config.infinity_router.clone(),
+        &InfinityRouterExecuteMsg::SplitProceeds {
+            denom: NATIVE_DENOM.to_string(),
+            balance_before: Uint128::zero(),
+            proceeds_splits: vec![(bidder.to_string(), 10000u16)],
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        InfinityError::Unauthorized("only the router can split proceeds".to_string()).to_string(),
+    );
+
+    // Tokens already held by the router are not part of the proceeds
+    let router_balance_before = Uint128::from(1_000_000u128);
+    let response = router.send_tokens(
+        owner.clone(),
+        global_config.infinity_router.clone(),
+        &[coin(router_balance_before.u128(), NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+
+    let bidder_balance_before = router.wrap().query_balance(&bidder, NATIVE_DENOM).unwrap().amount;
+    let response = router.execute_contract(
+        bidder.clone(),
+        global_config.infinity_router.clone(),
+        &InfinityRouterExecuteMsg::SwapNftsForTokens {
+            collection: collection.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            sell_orders,
+            min_total_output: None,
+            swap_params: Some(SwapParams {
+                robust: None,
+                allow_partial: None,
+                asset_recipient: None,
+                proceeds_splits: Some(vec![
+                    (splitter.to_string(), 7000u16),
+                    (cold_wallet.to_string(), 3000u16),
+                ]),
+                max_total_network_fee: None,
+            }),
+            filter_sources: None,
+        },
+        &[],
+    );
+    let split_event = response
+        .unwrap()
+        .events
+        .into_iter()
+        .find(|event| event.ty == "wasm-router-split-proceeds")
+        .unwrap();
+    assert!(split_event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "proceeds" && attr.value == total_seller_proceeds.to_string()));
+
+    let splitter_amount = router.wrap().query_balance(&splitter, NATIVE_DENOM).unwrap().amount;
+    let cold_wallet_amount =
+        router.wrap().query_balance(&cold_wallet, NATIVE_DENOM).unwrap().amount;
+    assert_eq!(splitter_amount, total_seller_proceeds.multiply_ratio(7000u128, 10000u128));
+    assert_eq!(splitter_amount + cold_wallet_amount, total_seller_proceeds);
+
+    // Neither the seller nor the router keep any of the proceeds
+    let bidder_balance_after = router.wrap().query_balance(&bidder, NATIVE_DENOM).unwrap().amount;
+    assert_eq!(bidder_balance_after, bidder_balance_before);
+    let router_balance =
+        router.wrap().query_balance(&global_config.infinity_router, NATIVE_DENOM).unwrap().amount;
+    assert_eq!(router_balance, router_balance_before);
 }
@@ -1,15 +1,22 @@
-use crate::helpers::pair_functions::create_pair_with_deposits;
+use crate::helpers::nft_functions::assert_nft_owner;
+use crate::helpers::pair_functions::{create_pair, create_pair_with_deposits, TestPair};
+use crate::helpers::utils::assert_error;
 use crate::setup::setup_accounts::MarketAccounts;
 use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
 
 use cosmwasm_std::{coin, Addr, Uint128};
 use cw_multi_test::Executor;
 use infinity_global::{msg::QueryMsg as InfinityGlobalQueryMsg, GlobalConfig};
+use infinity_index::msg::ExecuteMsg as InfinityIndexExecuteMsg;
 use infinity_pair::state::{BondingCurve, PairConfig, PairType};
 use infinity_router::msg::{
-    ExecuteMsg as InfinityRouterExecuteMsg, QueryMsg as InfinityRouterQueryMsg,
+    ExecuteMsg as InfinityRouterExecuteMsg, PairNftSwap, QueryMsg as InfinityRouterQueryMsg,
+    SimSwapTokensForSpecificNftsResponse, SwapParams,
 };
-use infinity_router::tokens_for_nfts_iterators::types::{TokensForNftQuote, TokensForNftSource};
+use infinity_router::tokens_for_nfts_iterators::types::{
+    TokensForAnyNftQuote, TokensForNftQuote, TokensForNftSource,
+};
+use infinity_router::ContractError as InfinityRouterContractError;
 use sg721_base::msg::{CollectionInfoResponse, QueryMsg as Sg721QueryMsg};
 use sg_std::NATIVE_DENOM;
 use test_suite::common_setup::msg::MinterTemplateResponse;
@@ -70,6 +77,12 @@ fn try_router_tokens_for_nfts_swap_simple() {
             },
             is_active: true,
             asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
         },
         100u64,
         Uint128::zero(),
@@ -91,6 +104,12 @@ fn try_router_tokens_for_nfts_swap_simple() {
             },
             is_active: true,
             asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
         },
         100u64,
         Uint128::zero(),
@@ -121,10 +140,907 @@ fn try_router_tokens_for_nfts_swap_simple() {
             collection: collection.to_string(),
             denom: NATIVE_DENOM.to_string(),
             max_inputs,
+            min_nfts: None,
+            swap_params: None,
+            filter_sources: None,
+        },
+        &[coin(total_tokens.u128(), NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+}
+
+#[test]
+fn try_router_sim_swap_tokens_for_any_nfts() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    for spot_price in [10_000_000u128, 10_500_000u128] {
+        create_pair_with_deposits(
+            &mut router,
+            &infinity_global,
+            &infinity_factory,
+            &minter,
+            &collection,
+            &creator,
+            &owner,
+            PairConfig {
+                pair_type: PairType::Nft,
+                bonding_curve: BondingCurve::Linear {
+                    spot_price: Uint128::from(spot_price),
+                    delta: Uint128::from(1_000_000u128),
+                },
+                is_active: true,
+                asset_recipient: None,
+                auto_activate: false,
+                anti_sandwich: false,
+                token_recipient: None,
+                nft_recipient: None,
+                allow_buy: true,
+                allow_sell: true,
+            },
+            5u64,
+            Uint128::zero(),
+        );
+    }
+
+    let sim_quotes = router
+        .wrap()
+        .query_wasm_smart::<Vec<TokensForAnyNftQuote>>(
+            &global_config.infinity_router,
+            &InfinityRouterQueryMsg::SimSwapTokensForAnyNfts {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                limit: 4,
+                filter_sources: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(sim_quotes.len(), 4);
+
+    let max_inputs = sim_quotes.iter().map(|q| q.amount).collect::<Vec<Uint128>>();
+    let total_tokens = max_inputs.iter().sum::<Uint128>();
+    let response = router.execute_contract(
+        bidder.clone(),
+        global_config.infinity_router,
+        &InfinityRouterExecuteMsg::SwapTokensForNfts {
+            collection: collection.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            max_inputs,
+            min_nfts: None,
+            swap_params: None,
+            filter_sources: None,
+        },
+        &[coin(total_tokens.u128(), NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+
+    // Every simulated token id was transferred to the buyer
+    let transferred_token_ids = response
+        .unwrap()
+        .events
+        .iter()
+        .filter(|event| event.ty == "wasm-swap-tokens-for-nft")
+        .map(|event| {
+            event.attributes.iter().find(|attr| attr.key == "token_id").unwrap().value.clone()
+        })
+        .collect::<Vec<String>>();
+    assert_eq!(
+        transferred_token_ids,
+        sim_quotes.iter().map(|q| q.token_id.clone()).collect::<Vec<String>>()
+    );
+    for sim_quote in sim_quotes {
+        assert_nft_owner(&router, &collection, sim_quote.token_id, &bidder);
+    }
+}
+
+#[test]
+fn try_router_sim_swap_tokens_for_specific_nfts() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder: _,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    let test_pairs = [10_000_000u128, 10_500_000u128]
+        .into_iter()
+        .map(|spot_price| {
+            create_pair_with_deposits(
+                &mut router,
+                &infinity_global,
+                &infinity_factory,
+                &minter,
+                &collection,
+                &creator,
+                &owner,
+                PairConfig {
+                    pair_type: PairType::Nft,
+                    bonding_curve: BondingCurve::Linear {
+                        spot_price: Uint128::from(spot_price),
+                        delta: Uint128::from(1_000_000u128),
+                    },
+                    is_active: true,
+                    asset_recipient: None,
+                    auto_activate: false,
+                    anti_sandwich: false,
+                    token_recipient: None,
+                    nft_recipient: None,
+                    allow_buy: true,
+                    allow_sell: true,
+                },
+                2u64,
+                Uint128::zero(),
+            )
+        })
+        .collect::<Vec<TestPair>>();
+    let (pair_a, pair_b) = (&test_pairs[0], &test_pairs[1]);
+
+    // The first NFT of pair A is also requested from pair B, which does not hold it
+    let response = router
+        .wrap()
+        .query_wasm_smart::<SimSwapTokensForSpecificNftsResponse>(
+            &global_config.infinity_router,
+            &InfinityRouterQueryMsg::SimSwapTokensForSpecificNfts {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                pair_nfts_to_swap_for: vec![
+                    PairNftSwap {
+                        pair: pair_a.address.to_string(),
+                        token_ids: pair_a.token_ids.clone(),
+                    },
+                    PairNftSwap {
+                        pair: pair_b.address.to_string(),
+                        token_ids: vec![pair_b.token_ids[0].clone(), pair_a.token_ids[0].clone()],
+                    },
+                ],
+            },
+        )
+        .unwrap();
+
+    assert_eq!(response.unfilled, vec![pair_a.token_ids[0].clone()]);
+    assert_eq!(
+        response
+            .filled
+            .iter()
+            .map(|quote| (quote.pair.clone(), quote.token_id.clone()))
+            .collect::<Vec<(Addr, String)>>(),
+        vec![
+            (pair_a.address.clone(), pair_a.token_ids[0].clone()),
+            (pair_a.address.clone(), pair_a.token_ids[1].clone()),
+            (pair_b.address.clone(), pair_b.token_ids[0].clone()),
+        ]
+    );
+
+    // Each pair quotes the first NFT at its current price, and the price rises after each swap
+    let quote_total = |test_pair: &TestPair| {
+        test_pair.pair.internal.buy_from_pair_quote_summary.as_ref().unwrap().total()
+    };
+    assert_eq!(response.filled[0].amount, quote_total(pair_a));
+    assert!(response.filled[1].amount > response.filled[0].amount);
+    assert_eq!(response.filled[2].amount, quote_total(pair_b));
+    for quote in &response.filled {
+        assert_eq!(quote.amount, quote.quote_summary.total());
+    }
+    assert_eq!(
+        response.totals.total_sale_price,
+        response.filled.iter().map(|quote| quote.amount).sum::<Uint128>()
+    );
+}
+
+#[test]
+fn try_router_sweep_floor() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    for spot_price in [10_000_000u128, 10_500_000u128] {
+        create_pair_with_deposits(
+            &mut router,
+            &infinity_global,
+            &infinity_factory,
+            &minter,
+            &collection,
+            &creator,
+            &owner,
+            PairConfig {
+                pair_type: PairType::Nft,
+                bonding_curve: BondingCurve::Linear {
+                    spot_price: Uint128::from(spot_price),
+                    delta: Uint128::from(1_000_000u128),
+                },
+                is_active: true,
+                asset_recipient: None,
+                auto_activate: false,
+                anti_sandwich: false,
+                token_recipient: None,
+                nft_recipient: None,
+                allow_buy: true,
+                allow_sell: true,
+            },
+            5u64,
+            Uint128::zero(),
+        );
+    }
+
+    // All available quotes, in ascending price order
+    let all_quotes = router
+        .wrap()
+        .query_wasm_smart::<Vec<TokensForAnyNftQuote>>(
+            &global_config.infinity_router,
+            &InfinityRouterQueryMsg::SimSwapTokensForAnyNfts {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                limit: 10,
+                filter_sources: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(all_quotes.len(), 10);
+
+    let count = 3usize;
+    let floor_quotes = &all_quotes[0..count];
+    let floor_total = floor_quotes.iter().map(|q| q.amount).sum::<Uint128>();
+    let max_floor_amount = floor_quotes.iter().map(|q| q.amount).max().unwrap();
+    assert!(all_quotes[count..].iter().all(|q| q.amount >= max_floor_amount));
+
+    // Sweep fails if the budget cannot cover the requested count
+    let max_input = floor_total - Uint128::one();
+    let response = router.execute_contract(
+        bidder.clone(),
+        global_config.infinity_router.clone(),
+        &InfinityRouterExecuteMsg::SweepFloor {
+            collection: collection.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            count: count as u32,
+            max_input,
+            per_unit_price_ceiling: None,
+            swap_params: None,
+            filter_sources: None,
+        },
+        &[coin(max_input.u128(), NATIVE_DENOM)],
+    );
+    assert!(response.is_err());
+
+    // Sweep buys the cheapest NFTs and refunds the unused budget
+    let max_input = Uint128::from(100_000_000u128);
+    let balance_before = router.wrap().query_balance(&bidder, NATIVE_DENOM).unwrap().amount;
+    let response = router
+        .execute_contract(
+            bidder.clone(),
+            global_config.infinity_router,
+            &InfinityRouterExecuteMsg::SweepFloor {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                count: count as u32,
+                max_input,
+                per_unit_price_ceiling: None,
+                swap_params: None,
+                filter_sources: None,
+            },
+            &[coin(max_input.u128(), NATIVE_DENOM)],
+        )
+        .unwrap();
+    let balance_after = router.wrap().query_balance(&bidder, NATIVE_DENOM).unwrap().amount;
+    assert_eq!(balance_before - balance_after, floor_total);
+
+    let sweep_event = response.events.iter().find(|e| e.ty == "wasm-router-sweep-floor").unwrap();
+    let swept_token_ids = sweep_event
+        .attributes
+        .iter()
+        .filter(|attr| attr.key == "token_id")
+        .map(|attr| attr.value.clone())
+        .collect::<Vec<String>>();
+    assert_eq!(
+        swept_token_ids,
+        floor_quotes.iter().map(|q| q.token_id.clone()).collect::<Vec<String>>()
+    );
+    for token_id in swept_token_ids {
+        assert_nft_owner(&router, &collection, token_id, &bidder);
+    }
+}
+
+#[test]
+fn try_router_sweep_floor_per_unit_price_ceiling() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: PairType::Nft,
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        3u64,
+        Uint128::zero(),
+    );
+
+    // Each NFT bought from the pair raises the price of the next one
+    let quotes = router
+        .wrap()
+        .query_wasm_smart::<Vec<TokensForAnyNftQuote>>(
+            &global_config.infinity_router,
+            &InfinityRouterQueryMsg::SimSwapTokensForAnyNfts {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                limit: 3,
+                filter_sources: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(quotes.len(), 3);
+    assert!(quotes[0].amount < quotes[1].amount && quotes[1].amount < quotes[2].amount);
+
+    // The ceiling only covers the first two NFTs
+    let per_unit_price_ceiling = quotes[1].amount;
+    let max_input = Uint128::from(100_000_000u128);
+
+    // Without robust mode, the sweep reverts once the curve passes the ceiling
+    let response = router.execute_contract(
+        bidder.clone(),
+        global_config.infinity_router.clone(),
+        &InfinityRouterExecuteMsg::SweepFloor {
+            collection: collection.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            count: 3,
+            max_input,
+            per_unit_price_ceiling: Some(per_unit_price_ceiling),
+            swap_params: None,
+            filter_sources: None,
+        },
+        &[coin(max_input.u128(), NATIVE_DENOM)],
+    );
+    assert_error(
+        response,
+        InfinityRouterContractError::SwapError(format!(
+            "quote {} exceeds the per unit price ceiling {}",
+            quotes[2].amount, per_unit_price_ceiling
+        ))
+        .to_string(),
+    );
+
+    // In robust mode, NFTs above the ceiling are skipped
+    let balance_before = router.wrap().query_balance(&bidder, NATIVE_DENOM).unwrap().amount;
+    let response = router.execute_contract(
+        bidder.clone(),
+        global_config.infinity_router,
+        &InfinityRouterExecuteMsg::SweepFloor {
+            collection: collection.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            count: 3,
+            max_input,
+            per_unit_price_ceiling: Some(per_unit_price_ceiling),
+            swap_params: Some(SwapParams {
+                robust: Some(true),
+                allow_partial: None,
+                asset_recipient: None,
+                proceeds_splits: None,
+                max_total_network_fee: None,
+            }),
+            filter_sources: None,
+        },
+        &[coin(max_input.u128(), NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+    let balance_after = router.wrap().query_balance(&bidder, NATIVE_DENOM).unwrap().amount;
+    assert_eq!(balance_before - balance_after, quotes[0].amount + quotes[1].amount);
+
+    for quote in &quotes[0..2] {
+        assert_nft_owner(&router, &collection, quote.token_id.clone(), &bidder);
+    }
+    assert_nft_owner(&router, &collection, quotes[2].token_id.clone(), &quotes[2].address);
+}
+
+#[test]
+fn try_router_sweep_floor_max_total_network_fee() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: PairType::Nft,
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        3u64,
+        Uint128::zero(),
+    );
+
+    let quotes = router
+        .wrap()
+        .query_wasm_smart::<Vec<TokensForAnyNftQuote>>(
+            &global_config.infinity_router,
+            &InfinityRouterQueryMsg::SimSwapTokensForAnyNfts {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                limit: 3,
+                filter_sources: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(quotes.len(), 3);
+
+    let network_fees = quotes
+        .iter()
+        .map(|quote| quote.quote_summary.as_ref().unwrap().fair_burn.amount)
+        .collect::<Vec<Uint128>>();
+    assert!(network_fees.iter().all(|fee| !fee.is_zero()));
+
+    // The cap only covers the network fees of the first two NFTs
+    let max_total_network_fee = network_fees[0] + network_fees[1];
+    let max_input = Uint128::from(100_000_000u128);
+
+    // Without robust mode, the sweep reverts once the network fees pass the cap
+    let response = router.execute_contract(
+        bidder.clone(),
+        global_config.infinity_router.clone(),
+        &InfinityRouterExecuteMsg::SweepFloor {
+            collection: collection.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            count: 3,
+            max_input,
+            per_unit_price_ceiling: None,
+            swap_params: Some(SwapParams {
+                robust: None,
+                allow_partial: None,
+                asset_recipient: None,
+                proceeds_splits: None,
+                max_total_network_fee: Some(max_total_network_fee),
+            }),
+            filter_sources: None,
+        },
+        &[coin(max_input.u128(), NATIVE_DENOM)],
+    );
+    assert_error(
+        response,
+        InfinityRouterContractError::SwapError(format!(
+            "total network fee {} exceeds the maximum {}",
+            network_fees.iter().sum::<Uint128>(),
+            max_total_network_fee
+        ))
+        .to_string(),
+    );
+
+    // In robust mode, the sweep stops filling at the cap
+    let balance_before = router.wrap().query_balance(&bidder, NATIVE_DENOM).unwrap().amount;
+    let response = router.execute_contract(
+        bidder.clone(),
+        global_config.infinity_router,
+        &InfinityRouterExecuteMsg::SweepFloor {
+            collection: collection.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            count: 3,
+            max_input,
+            per_unit_price_ceiling: None,
+            swap_params: Some(SwapParams {
+                robust: Some(true),
+                allow_partial: None,
+                asset_recipient: None,
+                proceeds_splits: None,
+                max_total_network_fee: Some(max_total_network_fee),
+            }),
+            filter_sources: None,
+        },
+        &[coin(max_input.u128(), NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+    let balance_after = router.wrap().query_balance(&bidder, NATIVE_DENOM).unwrap().amount;
+    assert_eq!(balance_before - balance_after, quotes[0].amount + quotes[1].amount);
+
+    for quote in &quotes[0..2] {
+        assert_nft_owner(&router, &collection, quote.token_id.clone(), &bidder);
+    }
+    assert_nft_owner(&router, &collection, quotes[2].token_id.clone(), &quotes[2].address);
+}
+
+#[test]
+fn try_router_tokens_for_nfts_min_nfts() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    // Only 2 NFTs are available for purchase
+    create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: PairType::Nft,
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        2u64,
+        Uint128::zero(),
+    );
+
+    let max_inputs = vec![Uint128::from(20_000_000u128); 4];
+    let total_tokens = max_inputs.iter().sum::<Uint128>();
+    let swap_tokens_for_nfts_msg = |min_nfts: u32| InfinityRouterExecuteMsg::SwapTokensForNfts {
+        collection: collection.to_string(),
+        denom: NATIVE_DENOM.to_string(),
+        max_inputs: max_inputs.clone(),
+        min_nfts: Some(min_nfts),
+        swap_params: Some(SwapParams {
+            robust: None,
+            allow_partial: Some(true),
+            asset_recipient: None,
+            proceeds_splits: None,
+            max_total_network_fee: None,
+        }),
+        filter_sources: None,
+    };
+
+    // Reverts when fewer than min_nfts NFTs can be delivered
+    let response = router.execute_contract(
+        bidder.clone(),
+        global_config.infinity_router.clone(),
+        &swap_tokens_for_nfts_msg(3),
+        &[coin(total_tokens.u128(), NATIVE_DENOM)],
+    );
+    assert_error(
+        response,
+        InfinityRouterContractError::SwapError(
+            "unable to swap the minimum number of nfts, min nfts: 3, actual swaps: 2".to_string(),
+        )
+        .to_string(),
+    );
+
+    // Succeeds when at least min_nfts NFTs are delivered
+    let response = router.execute_contract(
+        bidder.clone(),
+        global_config.infinity_router,
+        &swap_tokens_for_nfts_msg(2),
+        &[coin(total_tokens.u128(), NATIVE_DENOM)],
+    );
+    let num_swaps = response
+        .unwrap()
+        .events
+        .iter()
+        .filter(|event| event.ty == "wasm-swap-tokens-for-nft")
+        .count();
+    assert_eq!(num_swaps, 2);
+}
+
+#[test]
+fn try_router_tokens_for_nfts_skips_pair_without_nfts() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_index,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: PairType::Nft,
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        2u64,
+        Uint128::zero(),
+    );
+
+    // Leave a stale, cheaper buy quote in the index for a pair without any NFTs
+    let (empty_pair, _) =
+        create_pair(&mut router, &infinity_global, &infinity_factory, &collection, &owner);
+    let response = router.execute_contract(
+        empty_pair.clone(),
+        infinity_index,
+        &InfinityIndexExecuteMsg::UpdatePairIndices {
+            collection: collection.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            sell_to_pair_quote: None,
+            buy_from_pair_quote: Some(Uint128::from(1_000_000u128)),
+            total_tokens: None,
+            total_nfts: None,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    // The pair without NFTs is not quoted
+    let quotes = router
+        .wrap()
+        .query_wasm_smart::<Vec<TokensForNftQuote>>(
+            &global_config.infinity_router,
+            &InfinityRouterQueryMsg::TokensForNfts {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                limit: 3,
+                filter_sources: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(quotes.len(), 2);
+    assert!(quotes.iter().all(|quote| quote.address == test_pair.address));
+
+    // Swapping for more NFTs than are available returns an error instead of panicking
+    let mut max_inputs = quotes.iter().map(|q| q.amount).collect::<Vec<Uint128>>();
+    max_inputs.push(Uint128::from(20_000_000u128));
+    let total_tokens = max_inputs.iter().sum::<Uint128>();
+    let response = router.execute_contract(
+        bidder.clone(),
+        global_config.infinity_router.clone(),
+        &InfinityRouterExecuteMsg::SwapTokensForNfts {
+            collection: collection.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            max_inputs: max_inputs.clone(),
+            min_nfts: None,
+            swap_params: None,
+            filter_sources: None,
+        },
+        &[coin(total_tokens.u128(), NATIVE_DENOM)],
+    );
+    assert_error(
+        response,
+        InfinityRouterContractError::SwapError(
+            "unable to swap all tokens for nfts, requested swaps: 3, actual swaps: 2".to_string(),
+        )
+        .to_string(),
+    );
+
+    // The available NFTs can be swapped for
+    max_inputs.pop();
+    let total_tokens = max_inputs.iter().sum::<Uint128>();
+    let response = router.execute_contract(
+        bidder.clone(),
+        global_config.infinity_router,
+        &InfinityRouterExecuteMsg::SwapTokensForNfts {
+            collection: collection.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            max_inputs,
+            min_nfts: None,
             swap_params: None,
             filter_sources: None,
         },
         &[coin(total_tokens.u128(), NATIVE_DENOM)],
     );
     assert!(response.is_ok());
+    for token_id in test_pair.token_ids {
+        assert_nft_owner(&router, &collection, token_id, &bidder);
+    }
 }
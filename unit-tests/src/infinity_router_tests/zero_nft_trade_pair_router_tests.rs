@@ -0,0 +1,151 @@
+use crate::helpers::nft_functions::{approve, assert_nft_owner, mint_to};
+use crate::helpers::pair_functions::create_pair_with_deposits;
+use crate::setup::setup_accounts::MarketAccounts;
+use crate::setup::templates::{setup_infinity_test, standard_minter_template, InfinityTestSetup};
+
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw_multi_test::Executor;
+use infinity_global::{msg::QueryMsg as InfinityGlobalQueryMsg, GlobalConfig};
+use infinity_pair::msg::{CapabilitiesResponse, QueryMsg as InfinityPairQueryMsg};
+use infinity_pair::state::{BondingCurve, PairConfig, PairType};
+use infinity_router::msg::{
+    ExecuteMsg as InfinityRouterExecuteMsg, QueryMsg as InfinityRouterQueryMsg, SellOrder,
+};
+use infinity_router::nfts_for_tokens_iterators::types::NftForTokensQuote;
+use infinity_router::tokens_for_nfts_iterators::types::TokensForNftQuote;
+use sg_std::NATIVE_DENOM;
+use test_suite::common_setup::msg::MinterTemplateResponse;
+
+#[test]
+fn try_router_zero_nft_trade_pair() {
+    let vt = standard_minter_template(1000u32);
+    let InfinityTestSetup {
+        vending_template:
+            MinterTemplateResponse {
+                collection_response_vec,
+                mut router,
+                accts:
+                    MarketAccounts {
+                        creator,
+                        owner,
+                        bidder,
+                    },
+            },
+        infinity_global,
+        infinity_factory,
+        ..
+    } = setup_infinity_test(vt).unwrap();
+
+    let collection_resp = &collection_response_vec[0];
+    let minter = collection_resp.minter.clone().unwrap();
+    let collection = collection_resp.collection.clone().unwrap();
+
+    let global_config = router
+        .wrap()
+        .query_wasm_smart::<GlobalConfig<Addr>>(
+            infinity_global.clone(),
+            &InfinityGlobalQueryMsg::GlobalConfig {},
+        )
+        .unwrap();
+
+    let test_pair = create_pair_with_deposits(
+        &mut router,
+        &infinity_global,
+        &infinity_factory,
+        &minter,
+        &collection,
+        &creator,
+        &owner,
+        PairConfig {
+            pair_type: PairType::Trade {
+                swap_fee_percent: Decimal::percent(1),
+                reinvest_tokens: false,
+                reinvest_nfts: false,
+                max_nfts: None,
+            },
+            bonding_curve: BondingCurve::Linear {
+                spot_price: Uint128::from(10_000_000u128),
+                delta: Uint128::from(1_000_000u128),
+            },
+            is_active: true,
+            asset_recipient: None,
+            auto_activate: false,
+            anti_sandwich: false,
+            token_recipient: None,
+            nft_recipient: None,
+            allow_buy: true,
+            allow_sell: true,
+        },
+        0u64,
+        Uint128::from(1_000_000_000u128),
+    );
+
+    // A trade pair without NFTs can buy NFTs, but cannot sell them
+    let capabilities = router
+        .wrap()
+        .query_wasm_smart::<CapabilitiesResponse>(
+            test_pair.address.clone(),
+            &InfinityPairQueryMsg::Capabilities {},
+        )
+        .unwrap();
+    assert_eq!(
+        capabilities,
+        CapabilitiesResponse {
+            sell_to_pair: true,
+            buy_from_pair: false,
+        }
+    );
+
+    // The pair is excluded from buy routing
+    let buy_quotes = router
+        .wrap()
+        .query_wasm_smart::<Vec<TokensForNftQuote>>(
+            &global_config.infinity_router,
+            &InfinityRouterQueryMsg::TokensForNfts {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                limit: 10,
+                filter_sources: None,
+            },
+        )
+        .unwrap();
+    assert!(buy_quotes.is_empty());
+
+    // The pair is included in sell routing
+    let sell_quotes = router
+        .wrap()
+        .query_wasm_smart::<Vec<NftForTokensQuote>>(
+            &global_config.infinity_router,
+            &InfinityRouterQueryMsg::NftsForTokens {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                limit: 10,
+                filter_sources: None,
+            },
+        )
+        .unwrap();
+    assert!(!sell_quotes.is_empty());
+    assert_eq!(sell_quotes[0].address, test_pair.address);
+
+    let token_id = mint_to(&mut router, &creator.clone(), &bidder.clone(), &minter);
+    approve(&mut router, &bidder, &collection, &global_config.infinity_router, token_id.clone());
+
+    let response = router.execute_contract(
+        bidder.clone(),
+        global_config.infinity_router.clone(),
+        &InfinityRouterExecuteMsg::SwapNftsForTokens {
+            collection: collection.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            sell_orders: vec![SellOrder {
+                input_token_id: token_id.clone(),
+                min_output: sell_quotes[0].amount,
+            }],
+            min_total_output: None,
+            swap_params: None,
+            filter_sources: None,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+    assert_nft_owner(&router, &collection, token_id, &owner);
+}
@@ -5,6 +5,8 @@ mod infinity_factory_tests;
 #[cfg(test)]
 mod infinity_global_tests;
 #[cfg(test)]
+mod infinity_index_tests;
+#[cfg(test)]
 mod infinity_pair_tests;
 #[cfg(test)]
 mod infinity_router_tests;
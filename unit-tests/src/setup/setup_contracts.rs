@@ -1,12 +1,27 @@
-use cosmwasm_std::{Addr, Decimal, Uint128};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Decimal, Deps, DepsMut, Empty, Env, MessageInfo, StdError, StdResult,
+    Uint128,
+};
+use cw721::OwnerOfResponse;
 use cw_multi_test::{Contract, ContractWrapper, Executor};
+use cw_storage_plus::{Item, Map};
 use cw_utils::Duration;
+use infinity_pair::msg::{
+    AttributesOracleQueryMsg, HasTraitResponse, PriceOracleQueryMsg, PriceResponse,
+    TransfersFrozenResponse,
+};
+use sg721_base::msg::CollectionInfoResponse;
 use sg_marketplace::ExpiryRange;
 use sg_multi_test::StargazeApp;
-use sg_std::StargazeMsgWrapper;
+use sg_std::{Response, StargazeMsgWrapper};
 use stargaze_fair_burn::msg::InstantiateMsg as FairBurnInstantiateMsg;
 use stargaze_royalty_registry::{
-    msg::InstantiateMsg as RoyaltyRegistryInstantiateMsg, state::Config as RoyaltyRegistryConfig,
+    msg::{
+        InstantiateMsg as RoyaltyRegistryInstantiateMsg, QueryMsg as RoyaltyRegistryQueryMsg,
+        RoyaltyPaymentResponse,
+    },
+    state::{Config as RoyaltyRegistryConfig, RoyaltyDefault, RoyaltyEntry},
 };
 
 pub fn contract_fair_burn() -> Box<dyn Contract<StargazeMsgWrapper>> {
@@ -95,3 +110,388 @@ pub fn setup_marketplace(router: &mut StargazeApp, creator: &Addr) -> Addr {
         .instantiate_contract(marketplace_id, creator.clone(), &msg, &[], "Marketplace", None)
         .unwrap()
 }
+
+#[cw_serde]
+pub struct MockAttributesOracleInstantiateMsg {
+    /// The token ids reported as having any requested trait
+    pub matching_token_ids: Vec<String>,
+}
+
+const MATCHING_TOKEN_IDS: Item<Vec<String>> = Item::new("m");
+
+fn mock_attributes_oracle_instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: MockAttributesOracleInstantiateMsg,
+) -> StdResult<Response> {
+    MATCHING_TOKEN_IDS.save(deps.storage, &msg.matching_token_ids)?;
+    Ok(Response::new())
+}
+
+fn mock_attributes_oracle_execute(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Empty,
+) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+fn mock_attributes_oracle_query(
+    deps: Deps,
+    _env: Env,
+    msg: AttributesOracleQueryMsg,
+) -> StdResult<Binary> {
+    match msg {
+        AttributesOracleQueryMsg::HasTrait {
+            token_id,
+            ..
+        } => to_binary(&HasTraitResponse {
+            has_trait: MATCHING_TOKEN_IDS.load(deps.storage)?.contains(&token_id),
+        }),
+    }
+}
+
+pub fn contract_mock_attributes_oracle() -> Box<dyn Contract<StargazeMsgWrapper>> {
+    let contract = ContractWrapper::new(
+        mock_attributes_oracle_execute,
+        mock_attributes_oracle_instantiate,
+        mock_attributes_oracle_query,
+    );
+    Box::new(contract)
+}
+
+pub fn setup_mock_attributes_oracle(
+    router: &mut StargazeApp,
+    creator: &Addr,
+    matching_token_ids: Vec<String>,
+) -> Addr {
+    let mock_attributes_oracle_id = router.store_code(contract_mock_attributes_oracle());
+    router
+        .instantiate_contract(
+            mock_attributes_oracle_id,
+            creator.clone(),
+            &MockAttributesOracleInstantiateMsg {
+                matching_token_ids,
+            },
+            &[],
+            "MockAttributesOracle",
+            None,
+        )
+        .unwrap()
+}
+
+#[cw_serde]
+pub struct MockPriceOracleInstantiateMsg {
+    /// The price reported for every collection and denom
+    pub price: Uint128,
+}
+
+const ORACLE_PRICE: Item<Uint128> = Item::new("p");
+
+fn mock_price_oracle_instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: MockPriceOracleInstantiateMsg,
+) -> StdResult<Response> {
+    ORACLE_PRICE.save(deps.storage, &msg.price)?;
+    Ok(Response::new())
+}
+
+fn mock_price_oracle_execute(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Empty,
+) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+fn mock_price_oracle_query(deps: Deps, _env: Env, msg: PriceOracleQueryMsg) -> StdResult<Binary> {
+    match msg {
+        PriceOracleQueryMsg::Price {
+            ..
+        } => to_binary(&PriceResponse {
+            price: ORACLE_PRICE.load(deps.storage)?,
+        }),
+    }
+}
+
+pub fn contract_mock_price_oracle() -> Box<dyn Contract<StargazeMsgWrapper>> {
+    let contract = ContractWrapper::new(
+        mock_price_oracle_execute,
+        mock_price_oracle_instantiate,
+        mock_price_oracle_query,
+    );
+    Box::new(contract)
+}
+
+pub fn setup_mock_price_oracle(router: &mut StargazeApp, creator: &Addr, price: Uint128) -> Addr {
+    let mock_price_oracle_id = router.store_code(contract_mock_price_oracle());
+    router
+        .instantiate_contract(
+            mock_price_oracle_id,
+            creator.clone(),
+            &MockPriceOracleInstantiateMsg {
+                price,
+            },
+            &[],
+            "MockPriceOracle",
+            None,
+        )
+        .unwrap()
+}
+
+#[cw_serde]
+pub struct MockRoyaltyRegistryInstantiateMsg {
+    /// The royalty recipient reported for every collection, which is not validated
+    pub recipient: String,
+}
+
+const ROYALTY_RECIPIENT: Item<String> = Item::new("r");
+
+fn mock_royalty_registry_instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: MockRoyaltyRegistryInstantiateMsg,
+) -> StdResult<Response> {
+    ROYALTY_RECIPIENT.save(deps.storage, &msg.recipient)?;
+    Ok(Response::new())
+}
+
+fn mock_royalty_registry_execute(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Empty,
+) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+fn mock_royalty_registry_query(
+    deps: Deps,
+    _env: Env,
+    msg: RoyaltyRegistryQueryMsg,
+) -> StdResult<Binary> {
+    match msg {
+        RoyaltyRegistryQueryMsg::RoyaltyPayment {
+            collection,
+            ..
+        } => to_binary(&RoyaltyPaymentResponse {
+            royalty_default: Some(RoyaltyDefault {
+                collection: Addr::unchecked(collection),
+                royalty_entry: RoyaltyEntry {
+                    recipient: Addr::unchecked(ROYALTY_RECIPIENT.load(deps.storage)?),
+                    share: Decimal::percent(10),
+                    updated: None,
+                },
+            }),
+            royalty_protocol: None,
+        }),
+        _ => Err(StdError::generic_err("unsupported query")),
+    }
+}
+
+pub fn contract_mock_royalty_registry() -> Box<dyn Contract<StargazeMsgWrapper>> {
+    let contract = ContractWrapper::new(
+        mock_royalty_registry_execute,
+        mock_royalty_registry_instantiate,
+        mock_royalty_registry_query,
+    );
+    Box::new(contract)
+}
+
+pub fn setup_mock_royalty_registry(
+    router: &mut StargazeApp,
+    creator: &Addr,
+    recipient: &str,
+) -> Addr {
+    let mock_royalty_registry_id = router.store_code(contract_mock_royalty_registry());
+    router
+        .instantiate_contract(
+            mock_royalty_registry_id,
+            creator.clone(),
+            &MockRoyaltyRegistryInstantiateMsg {
+                recipient: recipient.to_string(),
+            },
+            &[],
+            "MockRoyaltyRegistry",
+            None,
+        )
+        .unwrap()
+}
+
+#[cw_serde]
+pub struct MockCollectionInstantiateMsg {
+    /// The token ids that the collection refuses to transfer
+    pub frozen_token_ids: Vec<String>,
+}
+
+#[cw_serde]
+pub enum MockCollectionExecuteMsg {
+    Mint {
+        token_id: String,
+        owner: String,
+    },
+    TransferNft {
+        recipient: String,
+        token_id: String,
+    },
+    ApproveAll {
+        operator: String,
+    },
+    /// Disables transfers of a single token
+    FreezeToken {
+        token_id: String,
+    },
+    /// Disables transfers of every token
+    Freeze {},
+    /// Stops answering the sg721 collection info query
+    RemoveCollectionInfo {},
+}
+
+/// Supports the queries made by the infinity contracts when creating a pair and depositing NFTs
+#[cw_serde]
+pub enum MockCollectionQueryMsg {
+    OwnerOf {
+        token_id: String,
+        include_expired: Option<bool>,
+    },
+    CollectionInfo {},
+    TransfersFrozen {},
+}
+
+const COLLECTION_CREATOR: Item<Addr> = Item::new("c");
+const COLLECTION_FROZEN: Item<bool> = Item::new("z");
+const COLLECTION_INFO_REMOVED: Item<bool> = Item::new("i");
+const FROZEN_TOKEN_IDS: Item<Vec<String>> = Item::new("f");
+const NFT_OWNERS: Map<String, Addr> = Map::new("o");
+const OPERATORS: Map<(Addr, Addr), bool> = Map::new("a");
+
+fn mock_collection_instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: MockCollectionInstantiateMsg,
+) -> StdResult<Response> {
+    COLLECTION_CREATOR.save(deps.storage, &info.sender)?;
+    COLLECTION_FROZEN.save(deps.storage, &false)?;
+    COLLECTION_INFO_REMOVED.save(deps.storage, &false)?;
+    FROZEN_TOKEN_IDS.save(deps.storage, &msg.frozen_token_ids)?;
+    Ok(Response::new())
+}
+
+fn mock_collection_execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: MockCollectionExecuteMsg,
+) -> StdResult<Response> {
+    match msg {
+        MockCollectionExecuteMsg::Mint {
+            token_id,
+            owner,
+        } => {
+            NFT_OWNERS.save(deps.storage, token_id, &deps.api.addr_validate(&owner)?)?;
+        },
+        MockCollectionExecuteMsg::TransferNft {
+            recipient,
+            token_id,
+        } => {
+            if COLLECTION_FROZEN.load(deps.storage)?
+                || FROZEN_TOKEN_IDS.load(deps.storage)?.contains(&token_id)
+            {
+                return Err(StdError::generic_err("token is frozen"));
+            }
+            let owner = NFT_OWNERS.load(deps.storage, token_id.clone())?;
+            if owner != info.sender && !OPERATORS.has(deps.storage, (owner, info.sender.clone())) {
+                return Err(StdError::generic_err("sender is not the owner"));
+            }
+            NFT_OWNERS.save(deps.storage, token_id, &deps.api.addr_validate(&recipient)?)?;
+        },
+        MockCollectionExecuteMsg::ApproveAll {
+            operator,
+        } => {
+            OPERATORS.save(
+                deps.storage,
+                (info.sender, deps.api.addr_validate(&operator)?),
+                &true,
+            )?;
+        },
+        MockCollectionExecuteMsg::FreezeToken {
+            token_id,
+        } => {
+            let mut frozen_token_ids = FROZEN_TOKEN_IDS.load(deps.storage)?;
+            frozen_token_ids.push(token_id);
+            FROZEN_TOKEN_IDS.save(deps.storage, &frozen_token_ids)?;
+        },
+        MockCollectionExecuteMsg::Freeze {} => {
+            COLLECTION_FROZEN.save(deps.storage, &true)?;
+        },
+        MockCollectionExecuteMsg::RemoveCollectionInfo {} => {
+            COLLECTION_INFO_REMOVED.save(deps.storage, &true)?;
+        },
+    }
+    Ok(Response::new())
+}
+
+fn mock_collection_query(deps: Deps, _env: Env, msg: MockCollectionQueryMsg) -> StdResult<Binary> {
+    match msg {
+        MockCollectionQueryMsg::OwnerOf {
+            token_id,
+            ..
+        } => to_binary(&OwnerOfResponse {
+            owner: NFT_OWNERS.load(deps.storage, token_id)?.to_string(),
+            approvals: vec![],
+        }),
+        MockCollectionQueryMsg::CollectionInfo {} => {
+            if COLLECTION_INFO_REMOVED.load(deps.storage)? {
+                return Err(StdError::generic_err("unsupported query"));
+            }
+            to_binary(&CollectionInfoResponse {
+                creator: COLLECTION_CREATOR.load(deps.storage)?.to_string(),
+                description: "Mock collection".to_string(),
+                image: "ipfs://mock".to_string(),
+                external_link: None,
+                explicit_content: None,
+                start_trading_time: None,
+                royalty_info: None,
+            })
+        },
+        MockCollectionQueryMsg::TransfersFrozen {} => to_binary(&TransfersFrozenResponse {
+            frozen: COLLECTION_FROZEN.load(deps.storage)?,
+        }),
+    }
+}
+
+pub fn contract_mock_collection() -> Box<dyn Contract<StargazeMsgWrapper>> {
+    let contract = ContractWrapper::new(
+        mock_collection_execute,
+        mock_collection_instantiate,
+        mock_collection_query,
+    );
+    Box::new(contract)
+}
+
+pub fn setup_mock_collection(
+    router: &mut StargazeApp,
+    creator: &Addr,
+    frozen_token_ids: Vec<String>,
+) -> Addr {
+    let mock_collection_id = router.store_code(contract_mock_collection());
+    router
+        .instantiate_contract(
+            mock_collection_id,
+            creator.clone(),
+            &MockCollectionInstantiateMsg {
+                frozen_token_ids,
+            },
+            &[],
+            "MockCollection",
+            None,
+        )
+        .unwrap()
+}
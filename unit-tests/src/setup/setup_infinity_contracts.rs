@@ -43,6 +43,16 @@ pub fn setup_infinity_global(
             default_royalty_fee_percent: Decimal::percent(5),
             max_royalty_fee_percent: Decimal::percent(10),
             max_swap_fee_percent: Decimal::percent(5),
+            max_nfts_per_pair: None,
+            pair_config_update_cooldown_seconds: None,
+            trading_fee_recipient: None,
+            burn_share_percent: None,
+            min_token_deposit: None,
+            min_nft_count: None,
+            pair_creation_fee_discount_percent: None,
+            min_spot_price: None,
+            max_spot_price: None,
+            probe_collection_freeze: None,
         },
         min_prices: vec![coin(10u128, NATIVE_DENOM), coin(10u128, UOSMO)],
     };
@@ -64,7 +74,8 @@ pub fn contract_infinity_factory() -> Box<dyn Contract<StargazeMsgWrapper>> {
         infinity_factory::instantiate::instantiate,
         infinity_factory::query::query,
     )
-    .with_sudo(infinity_factory::sudo::sudo);
+    .with_sudo(infinity_factory::sudo::sudo)
+    .with_reply(infinity_factory::reply::reply);
     Box::new(contract)
 }
 
@@ -154,6 +165,7 @@ pub fn contract_infinity_pair() -> Box<dyn Contract<StargazeMsgWrapper>> {
         infinity_pair::execute::execute,
         infinity_pair::instantiate::instantiate,
         infinity_pair::query::query,
-    );
+    )
+    .with_reply(infinity_pair::reply::reply);
     Box::new(contract)
 }
@@ -63,6 +63,32 @@ pub fn standard_minter_template(num_tokens: u32) -> MinterTemplateResponse<Marke
     }
 }
 
+pub fn zero_royalty_minter_template(num_tokens: u32) -> MinterTemplateResponse<MarketAccounts> {
+    let mut app = custom_mock_app();
+    let (owner, bidder, creator) = setup_accounts(&mut app).unwrap();
+    let start_time = Timestamp::from_nanos(GENESIS_MINT_START_TIME);
+    let mut collection_params = mock_collection_params_1(Some(start_time));
+    collection_params.info.royalty_info = None;
+    let minter_params = standard_minter_params_token(num_tokens, collection_params.clone());
+    let code_ids = vending_minter_code_ids(&mut app);
+    let minter_collection_response: Vec<MinterCollectionResponse> = configure_minter(
+        &mut app,
+        creator.clone(),
+        vec![collection_params],
+        vec![minter_params],
+        code_ids,
+    );
+    MinterTemplateResponse {
+        router: app,
+        collection_response_vec: minter_collection_response,
+        accts: MarketAccounts {
+            owner,
+            bidder,
+            creator,
+        },
+    }
+}
+
 pub fn minter_two_collections(num_tokens: u32) -> MinterTemplateResponse<MarketAccounts> {
     let mut app = custom_mock_app();
     let (owner, bidder, creator) = setup_accounts(&mut app).unwrap();